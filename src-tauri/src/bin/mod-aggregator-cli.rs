@@ -0,0 +1,344 @@
+// Консольная утилита для проверки обновлений без GUI (cron, контейнеры).
+// Использует те же Database и ParserEngine, что и десктопное приложение.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use chrono::Utc;
+use mod_aggregator::db_path::{self, DB_PATH_ENV, DB_PATH_FLAG};
+use mod_aggregator::migrations;
+use mod_aggregator::site_config;
+use mod_aggregator::models::{Mod, ModUpdate, Site};
+use mod_aggregator::state::AppState;
+use mod_aggregator::updates;
+
+const USAGE: &str = "\
+mod-aggregator-cli - проверка обновлений модов без GUI
+
+Использование:
+  mod-aggregator-cli [--db-path <путь>] <команда>
+
+Команды:
+  sites list [--json]                       Список сайтов
+  sites add <имя> <url> [--config <файл>]   Добавить сайт (конфигурация парсера в JSON файле;
+                                            без --config - только для сайтов со встроенным парсером)
+  sites remove <id>                         Удалить сайт вместе с его модами
+  check [--site <id>] [--json]              Проверить обновления (все сайты или один)
+  test-parser <id сайта> [--json]           Запустить парсер сайта без сохранения
+  mods list [--site <id>] [--json]          Список модов
+  export [--output <файл>]                  Экспорт сайтов и модов в JSON
+  help                                      Эта справка
+
+База данных: --db-path, переменная MOD_AGGREGATOR_DB или директория данных приложения.
+Подробные логи: RUST_LOG=info.";
+
+/// Команда CLI
+enum Command {
+    SitesList { json: bool },
+    SitesAdd { name: String, url: String, config: Option<PathBuf> },
+    SitesRemove { id: i64 },
+    Check { site: Option<i64>, json: bool },
+    TestParser { site: i64, json: bool },
+    ModsList { site: Option<i64>, json: bool },
+    Export { output: Option<PathBuf> },
+}
+
+/// Разобранные аргументы командной строки
+struct Cli {
+    db_path: Option<PathBuf>,
+    command: Command,
+}
+
+/// Флаги командной строки (общие для всех команд)
+#[derive(Default)]
+struct Flags {
+    db_path: Option<PathBuf>,
+    site: Option<String>,
+    config: Option<PathBuf>,
+    output: Option<PathBuf>,
+    json: bool,
+}
+
+/// Разобрать аргументы командной строки
+///
+/// # Параметры
+/// * `args` - аргументы без имени программы
+///
+/// # Возвращает
+/// Команду и общие параметры, None для справки, или текст ошибки
+fn parse_args(args: Vec<String>) -> Result<Option<Cli>, String> {
+    let mut flags = Flags::default();
+    let mut positional = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = |flag: &str| {
+            inline_value
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("Флаг {} требует значение", flag))
+        };
+        match name.as_str() {
+            DB_PATH_FLAG => flags.db_path = Some(PathBuf::from(value(DB_PATH_FLAG)?)),
+            "--site" => flags.site = Some(value("--site")?),
+            "--config" => flags.config = Some(PathBuf::from(value("--config")?)),
+            "--output" | "-o" => flags.output = Some(PathBuf::from(value("--output")?)),
+            "--json" => flags.json = true,
+            "--help" | "-h" => positional.insert(0, "help".to_string()),
+            flag if flag.starts_with('-') && flag.len() > 1 => {
+                return Err(format!("Неизвестный флаг: {}", flag));
+            }
+            _ => positional.push(arg),
+        }
+    }
+
+    let site = flags.site.as_deref().map(parse_id).transpose()?;
+    let positional: Vec<&str> = positional.iter().map(String::as_str).collect();
+    let command = match positional.as_slice() {
+        [] | ["help", ..] => return Ok(None),
+        ["sites", "list"] => Command::SitesList { json: flags.json },
+        ["sites", "add", name, url] => Command::SitesAdd {
+            name: name.to_string(),
+            url: url.to_string(),
+            config: flags.config,
+        },
+        ["sites", "remove", id] => Command::SitesRemove { id: parse_id(id)? },
+        ["check"] => Command::Check { site, json: flags.json },
+        ["test-parser", id] => Command::TestParser { site: parse_id(id)?, json: flags.json },
+        ["mods", "list"] => Command::ModsList { site, json: flags.json },
+        ["export"] => Command::Export { output: flags.output },
+        _ => return Err(format!("Неизвестная команда: {}", positional.join(" "))),
+    };
+
+    Ok(Some(Cli { db_path: flags.db_path, command }))
+}
+
+/// Разобрать ID сайта
+fn parse_id(value: &str) -> Result<i64, String> {
+    value.parse().map_err(|_| format!("Некорректный ID: {}", value))
+}
+
+/// Открыть базу данных и создать общее состояние
+///
+/// # Параметры
+/// * `cli_path` - путь из флага `--db-path`
+///
+/// # Возвращает
+/// Состояние приложения или текст ошибки
+async fn open_state(cli_path: Option<PathBuf>) -> Result<AppState, String> {
+    let env_path = std::env::var_os(DB_PATH_ENV);
+    let app_data_dir = match (db_path::default_app_data_dir(), &cli_path, &env_path) {
+        (Some(dir), _, _) => dir,
+        // Директория данных не нужна, если путь задан явно
+        (None, Some(_), _) | (None, _, Some(_)) => PathBuf::new(),
+        (None, None, None) => {
+            return Err(format!(
+                "Не удалось определить директорию данных, укажите {} или {}",
+                DB_PATH_FLAG, DB_PATH_ENV
+            ))
+        }
+    };
+    let location = db_path::resolve(&app_data_dir, cli_path, env_path);
+    log::info!("Using database {:?} ({:?})", location.path, location.source);
+
+    // Без директории данных загрузки сохраняются рядом с базой
+    let data_dir = match location.path.parent() {
+        Some(parent) if app_data_dir.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => app_data_dir,
+    };
+    AppState::new(&location.path, &data_dir).await.map_err(|e| e.to_string())
+}
+
+/// Вывести значение в формате JSON
+fn print_json<T: serde::Serialize>(value: &T) -> Result<(), String> {
+    let text = serde_json::to_string_pretty(value).map_err(|e| e.to_string())?;
+    println!("{}", text);
+    Ok(())
+}
+
+/// Вывести список модов в виде таблицы
+fn print_mods(mods: &[Mod]) {
+    for mod_item in mods {
+        println!(
+            "{}\t{}\t{}\t{}\t{}",
+            mod_item.id,
+            mod_item.site_id,
+            mod_item.version.as_deref().unwrap_or("-"),
+            mod_item.title,
+            mod_item.url
+        );
+    }
+}
+
+/// Вывести найденные обновления
+fn print_updates(updates: &[ModUpdate], titles: &HashMap<i64, String>) {
+    if updates.is_empty() {
+        println!("Обновлений нет");
+        return;
+    }
+    for update in updates {
+        let fields: Vec<String> = update
+            .changed_fields
+            .iter()
+            .filter_map(|f| serde_json::to_value(f).ok())
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect();
+        let fields = if update.new_mod { "новый мод".to_string() } else { fields.join(", ") };
+        println!(
+            "{}\t{}\t{} -> {}\t[{}]",
+            update.mod_id,
+            titles.get(&update.mod_id).map(String::as_str).unwrap_or("?"),
+            update.old_version.as_deref().unwrap_or("-"),
+            update.new_version.as_deref().unwrap_or("-"),
+            fields
+        );
+    }
+}
+
+/// Выполнить команду
+///
+/// # Параметры
+/// * `cli` - разобранные аргументы
+///
+/// # Возвращает
+/// Пустой результат при успехе или текст ошибки
+async fn run(cli: Cli) -> Result<(), String> {
+    let state = open_state(cli.db_path).await?;
+    let db = &state.db;
+
+    match cli.command {
+        Command::SitesList { json } => {
+            let sites: Vec<Site> = db
+                .get_sites()
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .map(site_config::redact_site)
+                .collect();
+            if json {
+                print_json(&sites)?;
+            } else {
+                for site in &sites {
+                    println!("{}\t{}\t{}", site.id, site.name, site.url);
+                }
+            }
+        }
+        Command::SitesAdd { name, url, config } => {
+            let parser_config = match config {
+                Some(path) => {
+                    let text = std::fs::read_to_string(&path)
+                        .map_err(|e| format!("Не удалось прочитать {:?}: {}", path, e))?;
+                    serde_json::from_str(&text)
+                        .map_err(|e| format!("Некорректный JSON в {:?}: {}", path, e))?
+                }
+                // Пустая конфигурация подходит только сайтам со встроенным парсером
+                None => match state.parsers.find_for(&url, &serde_json::json!({}))? {
+                    Some(_) => serde_json::json!({}),
+                    None => {
+                        return Err(format!(
+                            "Для {} нет встроенного парсера: укажите конфигурацию парсера через --config <файл.json>",
+                            url
+                        ))
+                    }
+                },
+            };
+            let parser_config = site_config::validate_for_site(&state.parsers, &url, &parser_config)
+                .map_err(|e| e.to_string())?;
+            let site = db.add_site(&name, &url, &parser_config).await.map_err(|e| e.to_string())?;
+            println!("{}", site.id);
+        }
+        Command::SitesRemove { id } => {
+            db.get_site(id).await.map_err(|_| format!("Сайт {} не найден", id))?;
+            db.delete_site(id).await.map_err(|e| e.to_string())?;
+        }
+        Command::Check { site, json } => {
+            let found = updates::check_updates(&state, site).await?;
+            if json {
+                print_json(&found)?;
+            } else {
+                let titles = db
+                    .get_mods(site)
+                    .await
+                    .map_err(|e| e.to_string())?
+                    .into_iter()
+                    .map(|m| (m.id, m.title))
+                    .collect();
+                print_updates(&found, &titles);
+            }
+        }
+        Command::TestParser { site, json } => {
+            let site: Site = db.get_site(site).await.map_err(|_| format!("Сайт {} не найден", site))?;
+            let mods = updates::parse_site(&state, &site).await?;
+            if json {
+                print_json(&mods)?;
+            } else {
+                print_mods(&mods);
+                eprintln!("Найдено модов: {}", mods.len());
+            }
+        }
+        Command::ModsList { site, json } => {
+            let mods = db.get_mods(site).await.map_err(|e| e.to_string())?;
+            if json {
+                print_json(&mods)?;
+            } else {
+                print_mods(&mods);
+            }
+        }
+        Command::Export { output } => {
+            // Токены источников не попадают в файл экспорта
+            let sites: Vec<Site> = db
+                .get_sites()
+                .await
+                .map_err(|e| e.to_string())?
+                .into_iter()
+                .map(site_config::redact_site)
+                .collect();
+            let export = serde_json::json!({
+                "exported_at": Utc::now(),
+                "schema_version": db.schema_version().await.unwrap_or(migrations::latest_version()),
+                "sites": sites,
+                "mods": db.get_mods(None).await.map_err(|e| e.to_string())?,
+            });
+            let text = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+            match output {
+                Some(path) => std::fs::write(&path, text)
+                    .map_err(|e| format!("Не удалось записать {:?}: {}", path, e))?,
+                None => println!("{}", text),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    // Логи идут в stderr, чтобы не смешиваться с выводом команд
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn"))
+        .format_timestamp_secs()
+        .init();
+
+    let cli = match parse_args(std::env::args().skip(1).collect()) {
+        Ok(Some(cli)) => cli,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Ошибка: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,407 @@
+use std::cmp::Ordering;
+use serde::{Deserialize, Serialize};
+use crate::models::{FieldDiff, Mod, ModUpdate, ModVersion, ModVersionDiff};
+
+/// Поле мода, изменение которого отслеживается при проверке обновлений
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModField {
+    Version,
+    Title,
+    Description,
+    ImageUrl,
+    Changes,
+}
+
+/// Характер изменения версии мода
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionChange {
+    /// Новая версия больше старой
+    Upgraded,
+    /// Новая версия меньше старой (откат)
+    Downgraded,
+    /// Строка версии изменилась, но номер тот же ("v1.2" → "1.2.0")
+    Reformatted,
+    /// Версия появилась у мода, у которого ее раньше не было
+    Added,
+    /// Версии несравнимы (нет чисел), но строки различаются
+    Changed,
+}
+
+/// Нормализовать строку версии перед разбором
+///
+/// Убирает пробелы, префиксы "v"/"ver"/"version" и завершающую точку.
+/// Префикс убирается, только если за ним (и за разделителями " ", ".",
+/// "-", "_", ":") следует цифра: "Ver. 2" -> "2", но "verbose-1.0" и
+/// "Very early" не меняются.
+///
+/// # Параметры
+/// * `version` - исходная строка версии
+///
+/// # Возвращает
+/// Строку версии без префиксов
+fn normalize_version(version: &str) -> String {
+    let trimmed = version.trim().trim_end_matches('.');
+    let lower = trimmed.to_lowercase();
+    for prefix in ["version", "ver", "v"] {
+        let Some(rest) = lower.starts_with(prefix).then(|| trimmed.get(prefix.len()..)).flatten() else {
+            continue;
+        };
+        let rest = rest.trim_start_matches([' ', '.', '-', '_', ':']);
+        if rest.starts_with(|c: char| c.is_ascii_digit()) {
+            return rest.to_string();
+        }
+    }
+    trimmed.to_string()
+}
+
+/// Разобрать версию как semver, дополняя недостающие компоненты нулями
+///
+/// # Параметры
+/// * `version` - нормализованная строка версии
+///
+/// # Возвращает
+/// Версию semver или None, если строка не похожа на semver
+fn parse_semver(version: &str) -> Option<semver::Version> {
+    if let Ok(v) = semver::Version::parse(version) {
+        return Some(v);
+    }
+    // "1.2" и "1.2-beta" дополняем до трех компонентов
+    let (core, rest) = match version.find(['-', '+']) {
+        Some(pos) => version.split_at(pos),
+        None => (version, ""),
+    };
+    let parts: Vec<&str> = core.split('.').collect();
+    if parts.is_empty() || parts.len() > 2 || parts.iter().any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+        return None;
+    }
+    let padded = format!("{}{}{}", core, ".0".repeat(3 - parts.len()), rest);
+    semver::Version::parse(&padded).ok()
+}
+
+/// Извлечь все числа из строки версии для "мягкого" сравнения
+///
+/// # Параметры
+/// * `version` - строка версии
+///
+/// # Возвращает
+/// Последовательность числовых компонентов (пустая, если чисел нет)
+fn numeric_components(version: &str) -> Vec<u64> {
+    version
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|s| !s.is_empty())
+        .filter_map(|s| s.parse().ok())
+        .collect()
+}
+
+/// Сравнить две строки версий
+///
+/// Сначала пытается сравнить версии по semver (с учетом pre-release),
+/// затем по числовым компонентам ("1.10" > "1.9", "r25" > "r3").
+/// Недостающие компоненты считаются нулями.
+///
+/// # Параметры
+/// * `old` - старая версия
+/// * `new` - новая версия
+///
+/// # Возвращает
+/// Порядок новой версии относительно старой или None, если версии несравнимы
+pub fn compare_versions(old: &str, new: &str) -> Option<Ordering> {
+    let old_norm = normalize_version(old);
+    let new_norm = normalize_version(new);
+
+    if let (Some(a), Some(b)) = (parse_semver(&old_norm), parse_semver(&new_norm)) {
+        return Some(b.cmp_precedence(&a));
+    }
+
+    let mut a = numeric_components(&old_norm);
+    let mut b = numeric_components(&new_norm);
+    if a.is_empty() || b.is_empty() {
+        return None;
+    }
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    Some(b.cmp(&a))
+}
+
+/// Классифицировать изменение версии
+///
+/// # Параметры
+/// * `old` - версия, сохраненная в базе данных
+/// * `new` - версия, полученная при парсинге
+///
+/// # Возвращает
+/// Характер изменения или None, если версия не изменилась
+pub fn classify_version_change(old: Option<&str>, new: Option<&str>) -> Option<VersionChange> {
+    let new = new.map(str::trim).filter(|v| !v.is_empty())?;
+    let old = match old.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(old) => old,
+        None => return Some(VersionChange::Added),
+    };
+    if old == new {
+        return None;
+    }
+
+    match compare_versions(old, new) {
+        Some(Ordering::Greater) => Some(VersionChange::Upgraded),
+        Some(Ordering::Less) => Some(VersionChange::Downgraded),
+        Some(Ordering::Equal) => Some(VersionChange::Reformatted),
+        None => Some(VersionChange::Changed),
+    }
+}
+
+/// Сравнить необязательное текстовое поле
+///
+/// Пустое значение при парсинге означает "не удалось извлечь", а не
+/// "поле удалено", поэтому изменением не считается.
+///
+/// # Параметры
+/// * `old` - сохраненное значение
+/// * `new` - значение, полученное при парсинге
+///
+/// # Возвращает
+/// true, если поле действительно изменилось
+fn field_changed(old: Option<&str>, new: Option<&str>) -> bool {
+    match new.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(new) => old.map(str::trim) != Some(new),
+        None => false,
+    }
+}
+
+/// Определить изменения мода по данным, полученным при парсинге
+///
+/// Сравнивает версию, название, описание, изображение и список изменений.
+/// Временные метки не учитываются: парсер всегда ставит текущее время.
+///
+/// # Параметры
+/// * `existing` - мод, сохраненный в базе данных
+/// * `scraped` - мод, полученный при парсинге сайта
+///
+/// # Возвращает
+/// Описание обновления или None, если ничего не изменилось
+pub fn detect_changes(existing: &Mod, scraped: &Mod) -> Option<ModUpdate> {
+    let version_change = classify_version_change(existing.version.as_deref(), scraped.version.as_deref());
+
+    let mut changed_fields = Vec::new();
+    if version_change.is_some() {
+        changed_fields.push(ModField::Version);
+    }
+    if field_changed(Some(&existing.title), Some(&scraped.title)) && scraped.title != "Unknown" {
+        changed_fields.push(ModField::Title);
+    }
+    if field_changed(existing.description.as_deref(), scraped.description.as_deref()) {
+        changed_fields.push(ModField::Description);
+    }
+    if field_changed(existing.image_url.as_deref(), scraped.image_url.as_deref()) {
+        changed_fields.push(ModField::ImageUrl);
+    }
+    if field_changed(existing.changes.as_deref(), scraped.changes.as_deref()) {
+        changed_fields.push(ModField::Changes);
+    }
+
+    if changed_fields.is_empty() {
+        return None;
+    }
+
+    Some(ModUpdate {
+        mod_id: existing.id,
+        site_id: existing.site_id,
+        old_version: existing.version.clone(),
+        new_version: scraped.version.clone().or_else(|| existing.version.clone()),
+        changes: scraped.changes.clone(),
+        changed_fields,
+        version_change,
+        new_mod: false,
+        notify: false,
+    })
+}
+
+/// Объединить сохраненный мод с данными, полученными при парсинге
+///
+/// Поля, которые парсер не смог извлечь, берутся из сохраненной записи,
+/// чтобы обновление не затирало ранее найденные данные.
+///
+/// # Параметры
+/// * `existing` - мод, сохраненный в базе данных
+/// * `scraped` - мод, полученный при парсинге сайта
+///
+/// # Возвращает
+/// Мод для записи в базу данных
+pub fn merge_scraped(existing: &Mod, scraped: &Mod) -> Mod {
+    Mod {
+        id: existing.id,
+        site_id: existing.site_id,
+        title: if scraped.title == "Unknown" { existing.title.clone() } else { scraped.title.clone() },
+        url: existing.url.clone(),
+        version: scraped.version.clone().or_else(|| existing.version.clone()),
+        author: scraped.author.clone().or_else(|| existing.author.clone()),
+        description: scraped.description.clone().or_else(|| existing.description.clone()),
+        image_url: scraped.image_url.clone().or_else(|| existing.image_url.clone()),
+        changes: scraped.changes.clone().or_else(|| existing.changes.clone()),
+        download_url: scraped.download_url.clone().or_else(|| existing.download_url.clone()),
+        files: if scraped.files.is_empty() { existing.files.clone() } else { scraped.files.clone() },
+        followed: existing.followed,
+        created_at: existing.created_at,
+        updated_at: scraped.updated_at,
+    }
+}
+
+/// Снимок полей мода для истории версий
+///
+/// Набор ключей совпадает со снимками, созданными миграцией `mod_versions`;
+/// ключ `files` добавляется, только если у мода есть файлы.
+///
+/// # Параметры
+/// * `mod_item` - мод на момент парсинга
+///
+/// # Возвращает
+/// JSON объект с полями мода
+pub fn mod_snapshot(mod_item: &Mod) -> serde_json::Value {
+    let mut snapshot = serde_json::json!({
+        "title": mod_item.title,
+        "url": mod_item.url,
+        "version": mod_item.version,
+        "author": mod_item.author,
+        "description": mod_item.description,
+        "image_url": mod_item.image_url,
+        "changes": mod_item.changes,
+        "download_url": mod_item.download_url,
+    });
+    if !mod_item.files.is_empty() {
+        snapshot["files"] = serde_json::json!(mod_item.files);
+    }
+    snapshot
+}
+
+/// Сравнить две записи истории версий мода
+///
+/// # Параметры
+/// * `from` - более ранняя запись
+/// * `to` - более поздняя запись
+///
+/// # Возвращает
+/// Измененные поля снимка (в алфавитном порядке) и характер изменения версии
+pub fn diff_versions(from: ModVersion, to: ModVersion) -> ModVersionDiff {
+    let empty = serde_json::Map::new();
+    let old = from.snapshot.as_object().unwrap_or(&empty);
+    let new = to.snapshot.as_object().unwrap_or(&empty);
+
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    // null и отсутствующий ключ считаются одинаковыми
+    let value = |map: &serde_json::Map<String, serde_json::Value>, key: &str| {
+        map.get(key).filter(|v| !v.is_null()).cloned()
+    };
+    let fields = keys
+        .into_iter()
+        .filter_map(|key| {
+            let old_value = value(old, key);
+            let new_value = value(new, key);
+            (old_value != new_value).then(|| FieldDiff {
+                field: key.clone(),
+                old_value,
+                new_value,
+            })
+        })
+        .collect();
+
+    ModVersionDiff {
+        mod_id: to.mod_id,
+        version_change: classify_version_change(from.version.as_deref(), to.version.as_deref()),
+        fields,
+        from,
+        to,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    #[test]
+    fn version_changes_are_classified() {
+        let cases = [
+            (Some("v1.2"), Some("1.2.0"), Some(VersionChange::Reformatted)),
+            (Some("Version 2"), Some("2.0"), Some(VersionChange::Reformatted)),
+            (Some("ver. 3"), Some("V3.0"), Some(VersionChange::Reformatted)),
+            (Some("1.9"), Some("1.10"), Some(VersionChange::Upgraded)),
+            (Some("r3"), Some("r25"), Some(VersionChange::Upgraded)),
+            (Some("1.0.0-beta"), Some("1.0.0"), Some(VersionChange::Upgraded)),
+            (Some("1.0.0"), Some("1.0.0-beta"), Some(VersionChange::Downgraded)),
+            (Some("2.1"), Some("2.0.5"), Some(VersionChange::Downgraded)),
+            (Some("beta"), Some("gamma"), Some(VersionChange::Changed)),
+            (None, Some("1.0"), Some(VersionChange::Added)),
+            (Some(" 1.0 "), Some("1.0"), None),
+            // Пустая версия при парсинге - не удалось извлечь, а не удалена
+            (Some("1.0"), Some(""), None),
+            (Some("1.0"), None, None),
+        ];
+        for (old, new, expected) in cases {
+            assert_eq!(classify_version_change(old, new), expected, "{:?} -> {:?}", old, new);
+        }
+        assert_eq!(compare_versions("1.9", "1.10"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("r25", "r3"), Some(Ordering::Less));
+        assert_eq!(compare_versions("stable", "1.0"), None);
+
+        // Префикс убирается, только если за ним идет номер версии
+        let normalized = [
+            ("v1.2", "1.2"),
+            ("Version 2", "2"),
+            ("ver.3", "3"),
+            ("V-4.", "4"),
+            ("verbose-1.0", "verbose-1.0"),
+            ("Very early", "Very early"),
+            ("vanilla", "vanilla"),
+            ("version", "version"),
+        ];
+        for (raw, expected) in normalized {
+            assert_eq!(normalize_version(raw), expected, "{:?}", raw);
+        }
+    }
+
+    #[test]
+    fn timestamps_alone_are_not_changes() {
+        let existing = Mod {
+            id: 7,
+            site_id: 1,
+            title: "Trees".to_string(),
+            url: "https://example.com/trees".to_string(),
+            version: Some("1.2".to_string()),
+            author: None,
+            description: Some("Big trees".to_string()),
+            image_url: None,
+            changes: None,
+            download_url: None,
+            files: Vec::new(),
+            followed: true,
+            created_at: Utc::now() - Duration::days(30),
+            updated_at: Utc::now() - Duration::days(30),
+        };
+        let mut scraped = existing.clone();
+        scraped.id = 0;
+        scraped.created_at = Utc::now();
+        scraped.updated_at = Utc::now();
+        assert!(detect_changes(&existing, &scraped).is_none());
+
+        // Поля, которые парсер не извлек, не считаются измененными
+        scraped.title = "Unknown".to_string();
+        scraped.description = None;
+        assert!(detect_changes(&existing, &scraped).is_none());
+
+        scraped.version = Some("1.1".to_string());
+        scraped.description = Some("Bigger trees".to_string());
+        let update = detect_changes(&existing, &scraped).unwrap();
+        assert_eq!(update.version_change, Some(VersionChange::Downgraded));
+        assert_eq!(update.changed_fields, vec![ModField::Version, ModField::Description]);
+        assert_eq!((update.old_version.as_deref(), update.new_version.as_deref()), (Some("1.2"), Some("1.1")));
+
+        let merged = merge_scraped(&existing, &scraped);
+        assert_eq!((merged.id, merged.title.as_str(), merged.created_at), (7, "Trees", existing.created_at));
+        assert_eq!(merged.updated_at, scraped.updated_at);
+    }
+}
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{broadcast, OwnedSemaphorePermit, Semaphore};
+use crate::models::{CheckRun, Site, SiteCheckProgress, SiteCheckStatus};
+
+/// Имя события Tauri с ходом проверки сайта
+pub const CHECK_PROGRESS_EVENT: &str = "site-check-progress";
+
+/// Сколько сайтов проверяется одновременно
+pub const MAX_CONCURRENT_CHECKS: usize = 4;
+
+/// Сколько сайтов одного хоста проверяется одновременно
+pub const MAX_CHECKS_PER_HOST: usize = 1;
+
+/// Максимальное время проверки одного сайта (все страницы каталога)
+pub const SITE_CHECK_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Ограничения одновременных проверок сайтов
+#[derive(Debug, Clone, Copy)]
+pub struct CheckLimits {
+    /// Одновременных проверок всего
+    pub max_concurrent: usize,
+    /// Одновременных проверок сайтов одного хоста
+    pub max_per_host: usize,
+    /// Таймаут проверки одного сайта
+    pub site_timeout: Duration,
+}
+
+impl Default for CheckLimits {
+    fn default() -> Self {
+        CheckLimits {
+            max_concurrent: MAX_CONCURRENT_CHECKS,
+            max_per_host: MAX_CHECKS_PER_HOST,
+            site_timeout: SITE_CHECK_TIMEOUT,
+        }
+    }
+}
+
+/// Разрешение на проверку сайта (освобождает места при удалении)
+pub struct CheckPermit {
+    _host: OwnedSemaphorePermit,
+    _global: OwnedSemaphorePermit,
+}
+
+/// Очередь проверок сайтов
+///
+/// Ограничивает одновременные проверки общим лимитом и лимитом на хост,
+/// чтобы медленный сайт не задерживал остальные, а сайты одного хоста не
+/// проверялись разом. Лимиты общие для всех проверок приложения (команды,
+/// фоновая проверка). Этапы проверок рассылаются подписчикам (`subscribe`),
+/// десктопное приложение пересылает их событием `CHECK_PROGRESS_EVENT`.
+/// Клонирование дешевое: все клоны используют одни лимиты.
+#[derive(Clone)]
+pub struct CheckQueue {
+    limits: CheckLimits,
+    global: Arc<Semaphore>,
+    /// Лимиты хостов (хост -> семафор)
+    hosts: Arc<Mutex<HashMap<String, Arc<Semaphore>>>>,
+    events: broadcast::Sender<SiteCheckProgress>,
+}
+
+impl CheckQueue {
+    /// Создать очередь проверок
+    ///
+    /// # Параметры
+    /// * `limits` - ограничения одновременных проверок (лимиты не меньше 1)
+    pub fn new(limits: CheckLimits) -> Self {
+        let (events, _) = broadcast::channel(256);
+        CheckQueue {
+            global: Arc::new(Semaphore::new(limits.max_concurrent.max(1))),
+            hosts: Arc::new(Mutex::new(HashMap::new())),
+            limits,
+            events,
+        }
+    }
+
+    /// Подписаться на ход проверок сайтов
+    pub fn subscribe(&self) -> broadcast::Receiver<SiteCheckProgress> {
+        self.events.subscribe()
+    }
+
+    /// Таймаут проверки одного сайта
+    pub fn site_timeout(&self) -> Duration {
+        self.limits.site_timeout
+    }
+
+    /// Дождаться места для проверки сайта
+    ///
+    /// Сообщает `queued`, затем ждет места на хосте сайта и в общем лимите
+    /// и сообщает `running`. Место на хосте занимается первым, чтобы сайт,
+    /// ждущий свой хост, не занимал место в общем лимите.
+    ///
+    /// # Параметры
+    /// * `site` - проверяемый сайт
+    ///
+    /// # Возвращает
+    /// Разрешение, действующее до конца проверки
+    pub async fn acquire(&self, site: &Site) -> CheckPermit {
+        self.send(site, SiteCheckStatus::Queued, None);
+        let host = self.host_semaphore(site);
+        // Семафоры не закрываются, поэтому ошибки получения быть не может
+        let host = host.acquire_owned().await.expect("check semaphore closed");
+        let global = self.global.clone().acquire_owned().await.expect("check semaphore closed");
+        self.send(site, SiteCheckStatus::Running, None);
+        CheckPermit {
+            _host: host,
+            _global: global,
+        }
+    }
+
+    /// Сообщить о завершении проверки сайта
+    ///
+    /// # Параметры
+    /// * `site` - сайт
+    /// * `run` - запись о проверке (с ошибкой - `failed`)
+    pub fn finished(&self, site: &Site, run: &CheckRun) {
+        let status = if run.error.is_some() {
+            SiteCheckStatus::Failed
+        } else {
+            SiteCheckStatus::Completed
+        };
+        self.send(site, status, Some(run.clone()));
+    }
+
+    fn send(&self, site: &Site, status: SiteCheckStatus, run: Option<CheckRun>) {
+        // Ошибка означает только отсутствие подписчиков
+        let _ = self.events.send(SiteCheckProgress {
+            site_id: site.id,
+            site_name: site.name.clone(),
+            status,
+            run,
+        });
+    }
+
+    /// Семафор хоста сайта (сайты с некорректным URL ограничиваются по URL)
+    fn host_semaphore(&self, site: &Site) -> Arc<Semaphore> {
+        let host = url::Url::parse(&site.url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase))
+            .unwrap_or_else(|| site.url.clone());
+        let mut hosts = self.hosts.lock().unwrap();
+        hosts
+            .entry(host)
+            .or_insert_with(|| Arc::new(Semaphore::new(self.limits.max_per_host.max(1))))
+            .clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::test_support::{test_state, MockRequest, MockResponse, MockServer};
+    use crate::updates;
+
+    /// Лента с модом, ссылка на который зависит от пути ленты, чтобы моды
+    /// сайтов одного сервера не совпадали
+    fn feed(request: &MockRequest, delay: Duration) -> MockResponse {
+        let path = request.path.trim_end_matches('/');
+        let body = format!(
+            "<rss version=\"2.0\"><channel><item><title>Trees v1.0</title><link>{}/trees</link></item></channel></rss>",
+            path
+        );
+        MockResponse::ok(body).header("Content-Type", "application/rss+xml").delay(delay)
+    }
+
+    #[tokio::test]
+    async fn sites_are_checked_concurrently_within_limits() {
+        let (_dir, mut state) = test_state().await;
+        state.checks = CheckQueue::new(CheckLimits {
+            max_concurrent: 3,
+            max_per_host: 1,
+            site_timeout: Duration::from_millis(500),
+        });
+        let mut events = state.checks.subscribe();
+
+        let stalled = MockServer::start(|request| feed(request, Duration::from_secs(5))).await;
+        let shared = MockServer::start(|request| feed(request, Duration::from_millis(100))).await;
+        let config = json!({"source": {"type": "feed"}});
+        let slow = state.db.add_site("Slow", stalled.address(), &config).await.unwrap();
+        // Два сайта одного хоста проверяются по очереди
+        state.db.add_site("First", &shared.url("/first"), &config).await.unwrap();
+        state.db.add_site("Second", &shared.url("/second"), &config).await.unwrap();
+
+        let started = std::time::Instant::now();
+        let found = updates::check_updates(&state, None).await.unwrap();
+        assert_eq!(found.len(), 2);
+        // Медленный сайт остановлен таймаутом и не задерживает остальные
+        assert!(started.elapsed() < Duration::from_secs(3));
+        assert_eq!(shared.max_in_flight(), 1);
+
+        let slow_run = &state.db.get_check_runs(Some(slow.id), 1).await.unwrap()[0];
+        assert!(slow_run.error.as_deref().unwrap().contains("не завершилась"));
+
+        let mut statuses = Vec::new();
+        while let Ok(progress) = events.try_recv() {
+            statuses.push((progress.site_id, progress.status, progress.run.is_some()));
+        }
+        assert_eq!(statuses.len(), 9);
+        assert_eq!(statuses.iter().filter(|(_, status, _)| *status == SiteCheckStatus::Queued).count(), 3);
+        assert!(statuses.contains(&(slow.id, SiteCheckStatus::Failed, true)));
+        assert_eq!(statuses.iter().filter(|(_, status, run)| *status == SiteCheckStatus::Completed && *run).count(), 2);
+    }
+}
@@ -0,0 +1,318 @@
+use std::cell::RefCell;
+use std::future::Future;
+use crate::database::Database;
+use crate::models::{CheckRun, HealthStatus, SiteHealth};
+
+/// Сколько последних проверок учитывается при оценке здоровья сайта
+pub const HEALTH_WINDOW: i64 = 20;
+
+/// Падение количества модов не отслеживается, если обычно их меньше
+const MIN_BASELINE_MODS: f64 = 3.0;
+
+/// Падение, если найдено меньше этой доли от обычного количества модов
+const COUNT_DROP_RATIO: f64 = 0.5;
+
+/// Максимальный размер сохраняемой страницы проверки (для снимка при сбое парсера)
+const MAX_CAPTURED_PAGE: usize = 5 * 1024 * 1024;
+
+/// Первый ответ, полученный во время проверки сайта
+#[derive(Debug, Clone, Default)]
+pub struct CapturedResponse {
+    /// HTTP статус первого ответа
+    pub http_status: Option<u16>,
+    /// URL и HTML/XML первой загруженной страницы (каталог или лента)
+    pub page: Option<(String, String)>,
+}
+
+tokio::task_local! {
+    /// Первый ответ текущей проверки сайта
+    static CAPTURE: RefCell<CapturedResponse>;
+}
+
+/// Запомнить HTTP статус ответа для записи проверки
+///
+/// Сохраняется только первый статус проверки - ответ страницы каталога,
+/// ленты или API. Вне `capture_responses` (например, при тесте парсера)
+/// ничего не делает.
+///
+/// # Параметры
+/// * `status` - статус ответа
+pub fn record_http_status(status: reqwest::StatusCode) {
+    let _ = CAPTURE.try_with(|capture| {
+        let mut capture = capture.borrow_mut();
+        if capture.http_status.is_none() {
+            capture.http_status = Some(status.as_u16());
+        }
+    });
+}
+
+/// Запомнить первую загруженную страницу проверки
+///
+/// # Параметры
+/// * `url` - URL страницы
+/// * `body` - содержимое страницы
+pub fn record_page(url: &str, body: &str) {
+    let _ = CAPTURE.try_with(|capture| {
+        let mut capture = capture.borrow_mut();
+        if capture.page.is_none() && body.len() <= MAX_CAPTURED_PAGE {
+            capture.page = Some((url.to_string(), body.to_string()));
+        }
+    });
+}
+
+/// Выполнить проверку, запоминая первый ответ
+///
+/// # Параметры
+/// * `check` - проверка сайта
+///
+/// # Возвращает
+/// Результат проверки и первый ответ (статус и страницу)
+pub async fn capture_responses<F: Future>(check: F) -> (F::Output, CapturedResponse) {
+    CAPTURE
+        .scope(RefCell::new(CapturedResponse::default()), async move {
+            let output = check.await;
+            (output, CAPTURE.with(|capture| capture.take()))
+        })
+        .await
+}
+
+/// Оценить здоровье сайта по последним проверкам
+///
+/// - `failing` - последняя проверка завершилась ошибкой
+/// - `degraded` - последняя проверка отличается от истории сайта (см. drift.rs),
+///   нашла меньше половины обычного количества модов (медианы предыдущих
+///   успешных проверок окна, если обычно модов не меньше трех, см.
+///   `count_drop`) или не нашла ни одного мода, хотя предыдущая
+///   успешная находила (обычно сломался селектор или разметка сайта).
+///   Так 500 -> 1 и 1 -> 0 считаются деградацией, а 4 -> 2 и 2 -> 1 - нет
+/// - `ok` - последняя проверка нашла моды (или сайт всегда был пустым)
+/// - `unknown` - проверок еще не было
+///
+/// # Параметры
+/// * `site_id` - ID сайта
+/// * `runs` - последние проверки сайта от новых к старым
+///
+/// # Возвращает
+/// Здоровье сайта
+pub fn evaluate_health(site_id: i64, runs: &[CheckRun]) -> SiteHealth {
+    let consecutive_failures = runs.iter().take_while(|run| run.error.is_some()).count() as i64;
+    let mut successful = runs.iter().filter(|run| run.error.is_none());
+    let last_success = successful.next();
+    // Обычное количество модов, если последняя проверка нашла намного меньше
+    let history: Vec<CheckRun> = successful.clone().cloned().collect();
+    let dropped_from = runs.first().and_then(|last| count_drop(last.mods_found, &history));
+
+    let (status, reason) = match (runs.first(), last_success) {
+        (None, _) => (HealthStatus::Unknown, None),
+        (Some(last), _) if last.error.is_some() => {
+            let error = last.error.clone().unwrap_or_default();
+            let reason = match last.http_status {
+                Some(status) => format!("HTTP {}: {}", status, error),
+                None => error,
+            };
+            (HealthStatus::Failing, Some(reason))
+        }
+        (Some(last), _) if last.drift.is_some() => (
+            HealthStatus::Degraded,
+            last.drift.as_ref().map(|drift| format!("Парсер, вероятно, сломан: {}", drift)),
+        ),
+        (Some(last), _) if dropped_from.is_some() => (
+            HealthStatus::Degraded,
+            dropped_from.map(|usual| format!("Найдено модов: {} (обычно около {})", last.mods_found, usual.round())),
+        ),
+        (Some(last), _) if last.mods_found == 0 => match successful.next().filter(|previous| previous.mods_found > 0) {
+            Some(previous) => (
+                HealthStatus::Degraded,
+                Some(format!(
+                    "Моды не найдены, при проверке {} было найдено {}",
+                    previous.started_at.format("%Y-%m-%d %H:%M"),
+                    previous.mods_found
+                )),
+            ),
+            None => (HealthStatus::Ok, None),
+        },
+        _ => (HealthStatus::Ok, None),
+    };
+
+    SiteHealth {
+        site_id,
+        status,
+        consecutive_failures,
+        reason,
+        last_run: runs.first().cloned(),
+        last_success_at: last_success.map(|run| run.started_at),
+    }
+}
+
+/// Проверить, упало ли количество найденных модов относительно истории
+///
+/// Количество считается упавшим, если оно меньше половины медианы истории.
+/// Истории, где обычно меньше трех модов, не сравниваются - для них падение
+/// не отличить от обычных колебаний.
+///
+/// # Параметры
+/// * `mods_found` - количество модов текущей проверки
+/// * `history` - предыдущие успешные проверки
+///
+/// # Возвращает
+/// Обычное количество модов (медиану истории), если количество упало
+pub fn count_drop(mods_found: i64, history: &[CheckRun]) -> Option<f64> {
+    let usual = median(history.iter().map(|previous| previous.mods_found as f64).collect())?;
+    (usual >= MIN_BASELINE_MODS && (mods_found as f64) < usual * COUNT_DROP_RATIO).then_some(usual)
+}
+
+/// Медиана значений (None для пустого списка)
+pub(crate) fn median(mut values: Vec<f64>) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let middle = values.len() / 2;
+    Some(if values.len().is_multiple_of(2) {
+        (values[middle - 1] + values[middle]) / 2.0
+    } else {
+        values[middle]
+    })
+}
+
+/// Получить здоровье сайта
+///
+/// # Параметры
+/// * `db` - база данных
+/// * `site_id` - ID сайта
+///
+/// # Возвращает
+/// Здоровье сайта по последним `HEALTH_WINDOW` проверкам или ошибку
+pub async fn site_health(db: &Database, site_id: i64) -> Result<SiteHealth, sqlx::Error> {
+    let runs = db.get_check_runs(Some(site_id), HEALTH_WINDOW).await?;
+    Ok(evaluate_health(site_id, &runs))
+}
+
+/// Получить здоровье всех сайтов
+///
+/// # Параметры
+/// * `db` - база данных
+///
+/// # Возвращает
+/// Здоровье сайтов в порядке списка сайтов или ошибку
+pub async fn sites_health(db: &Database) -> Result<Vec<SiteHealth>, sqlx::Error> {
+    let mut health = Vec::new();
+    for site in db.get_sites().await? {
+        health.push(site_health(db, site.id).await?);
+    }
+    Ok(health)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use chrono::Utc;
+    use serde_json::json;
+    use crate::test_support::{test_state, MockResponse, MockServer};
+    use crate::updates;
+
+    #[tokio::test]
+    async fn runs_are_recorded_and_health_is_evaluated() {
+        let (_dir, state) = test_state().await;
+        let items = "<rss version=\"2.0\"><channel><item><title>Trees v1.0</title><link>/trees</link></item><item><title>Rocks v1.0</title><link>/rocks</link></item></channel></rss>";
+        let response = Arc::new(Mutex::new((200, items.to_string())));
+        let server = MockServer::start({
+            let response = response.clone();
+            move |_| {
+                let (status, body) = response.lock().unwrap().clone();
+                MockResponse::new(status, body).header("Content-Type", "application/rss+xml")
+            }
+        })
+        .await;
+        let site = state
+            .db
+            .add_site("Feed", server.address(), &json!({"source": {"type": "feed"}}))
+            .await
+            .unwrap();
+        assert_eq!(site_health(&state.db, site.id).await.unwrap().status, HealthStatus::Unknown);
+
+        updates::check_site(&state, &site).await.unwrap();
+        let runs = state.db.get_check_runs(Some(site.id), 10).await.unwrap();
+        assert_eq!(runs.len(), 1);
+        assert_eq!((runs[0].http_status, runs[0].mods_found, runs[0].new_mods, runs[0].updated_mods), (Some(200), 2, 2, 0));
+        assert_eq!(site_health(&state.db, site.id).await.unwrap().status, HealthStatus::Ok);
+
+        // Разметка сменилась: лента пустая
+        *response.lock().unwrap() = (200, "<rss version=\"2.0\"><channel></channel></rss>".to_string());
+        updates::check_site(&state, &site).await.unwrap();
+        let health = site_health(&state.db, site.id).await.unwrap();
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert!(health.reason.unwrap().contains("найдено 2"));
+
+        *response.lock().unwrap() = (503, String::new());
+        assert!(updates::check_site(&state, &site).await.is_err());
+        assert!(updates::check_site(&state, &site).await.is_err());
+        let health = site_health(&state.db, site.id).await.unwrap();
+        assert_eq!((health.status, health.consecutive_failures), (HealthStatus::Failing, 2));
+        assert_eq!(health.last_run.unwrap().http_status, Some(503));
+        assert!(health.reason.unwrap().starts_with("HTTP 503"));
+        assert_eq!(sites_health(&state.db).await.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn empty_site_is_not_degraded() {
+        let run = |mods_found: i64| CheckRun {
+            id: 0,
+            site_id: 1,
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            http_status: Some(200),
+            mods_found,
+            new_mods: 0,
+            updated_mods: 0,
+            error: None,
+            null_fraction: None,
+            drift: None,
+            saved_page_id: None,
+        };
+        assert_eq!(evaluate_health(1, &[run(0), run(0)]).status, HealthStatus::Ok);
+        assert_eq!(evaluate_health(1, &[run(0), run(3)]).status, HealthStatus::Degraded);
+        assert_eq!(evaluate_health(1, &[run(3), run(0)]).status, HealthStatus::Ok);
+    }
+
+    #[test]
+    fn mod_count_drop_boundaries() {
+        let run = |mods_found: i64| CheckRun {
+            id: 0,
+            site_id: 1,
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            http_status: Some(200),
+            mods_found,
+            new_mods: 0,
+            updated_mods: 0,
+            error: None,
+            null_fraction: None,
+            drift: None,
+            saved_page_id: None,
+        };
+        let status = |counts: &[i64]| evaluate_health(1, &counts.iter().map(|count| run(*count)).collect::<Vec<_>>()).status;
+        let cases: &[(&[i64], HealthStatus)] = &[
+            (&[0, 1], HealthStatus::Degraded),
+            (&[1, 500], HealthStatus::Degraded),
+            (&[1, 2, 500, 500], HealthStatus::Degraded),
+            // Меньше половины медианы истории
+            (&[2, 5], HealthStatus::Degraded),
+            (&[3, 6], HealthStatus::Ok),
+            (&[1, 2], HealthStatus::Ok),
+            (&[2, 4], HealthStatus::Ok),
+            // Медиана, а не последняя проверка: одиночный всплеск не в счет
+            (&[10, 10, 500, 10], HealthStatus::Ok),
+        ];
+        for (counts, expected) in cases {
+            assert_eq!(status(counts), *expected, "{:?}", counts);
+        }
+
+        let mut failed = run(0);
+        failed.error = Some("timeout".to_string());
+        let health = evaluate_health(1, &[run(1), failed, run(500)]);
+        assert_eq!(health.status, HealthStatus::Degraded);
+        assert_eq!(health.reason.as_deref(), Some("Найдено модов: 1 (обычно около 500)"));
+    }
+}
@@ -1,34 +1,79 @@
-use sqlx::{sqlite::SqlitePool, Row};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
+use sqlx::{sqlite::{Sqlite, SqliteConnectOptions, SqliteJournalMode, SqlitePool, SqliteRow}, QueryBuilder, Row, Transaction};
 use chrono::Utc;
-use crate::models::{Site, Mod, Notification};
+use log::info;
+use crate::changes;
+use crate::migrations;
+use crate::search::{self, SearchFilters, SearchHit, SearchResults, SearchSort};
+use crate::site_config;
+use crate::models::{CheckRun, Download, DownloadStatus, Installation, InstalledFile, Site, Mod, ModFile, ModPage, ModSort, ModVersion, Notification, NotificationKind, Profile, ProfileMod, Schedule, SiteSchedule, SortDirection};
+
+/// Сколько последних проверок каждого сайта хранится в `check_runs`
+pub const MAX_CHECK_RUNS_PER_SITE: i64 = 500;
+
+/// Расписания всех сайтов: сайты без записи в `site_schedules` получают NULL колонки
+const SITE_SCHEDULE_QUERY: &str = "SELECT s.id, sc.interval_secs, sc.cron, sc.enabled, sc.last_run_at, sc.next_run_at, sc.failure_count, sc.last_error FROM sites s LEFT JOIN site_schedules sc ON sc.site_id = s.id";
 
 /// Структура для работы с базой данных SQLite
 /// 
 /// Предоставляет методы для работы с сайтами, модами, уведомлениями и сохраненными страницами.
-/// Автоматически создает таблицы и индексы при инициализации.
+/// Автоматически применяет миграции схемы при инициализации.
+/// Клонирование дешевое: все копии используют один пул подключений.
+#[derive(Clone)]
 pub struct Database {
     pool: SqlitePool,
 }
 
 impl Database {
-    /// Создать новое подключение к базе данных
+    /// Открыть базу данных по пути к файлу
     /// 
-    /// Инициализирует подключение к SQLite базе данных, создает необходимые таблицы
-    /// и индексы, если они не существуют.
+    /// Создает директорию и файл базы данных при необходимости и применяет миграции схемы.
+    /// Путь определяется в db_path.rs (директория данных приложения, `--db-path` или `MOD_AGGREGATOR_DB`).
+    /// 
+    /// # Параметры
+    /// * `path` - путь к файлу базы данных
     /// 
     /// # Возвращает
     /// Экземпляр Database или ошибку подключения
-    pub async fn new() -> Result<Self, sqlx::Error> {
-        // Use current directory for database (will be in app directory when running)
-        let db_dir = std::env::current_dir()
-            .unwrap_or_else(|_| std::path::PathBuf::from("."));
-        std::fs::create_dir_all(&db_dir).map_err(|e| {
-            sqlx::Error::Configuration(format!("Failed to create data directory: {}", e).into())
-        })?;
+    pub async fn open(path: &Path) -> Result<Self, sqlx::Error> {
+        if let Some(db_dir) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(db_dir).map_err(|e| {
+                sqlx::Error::Configuration(format!("Failed to create data directory: {}", e).into())
+            })?;
+        }
         
-        let db_path = db_dir.join("mod_aggregator.db");
-        let db_url = format!("sqlite:{}?mode=rwc", db_path.display());
-        let pool = SqlitePool::connect(&db_url).await?;
+        let options = SqliteConnectOptions::new().filename(path);
+        Self::connect_with(options).await
+    }
+
+    /// Подключиться к базе данных по URL и применить миграции схемы
+    /// 
+    /// # Параметры
+    /// * `db_url` - URL подключения SQLite (например, `sqlite:/path/to.db?mode=rwc`)
+    /// 
+    /// # Возвращает
+    /// Экземпляр Database или ошибку подключения/миграции
+    pub async fn connect(db_url: &str) -> Result<Self, sqlx::Error> {
+        Self::connect_with(SqliteConnectOptions::from_str(db_url)?).await
+    }
+
+    /// Подключиться к базе данных с указанными параметрами и применить миграции схемы
+    /// 
+    /// # Параметры
+    /// * `options` - параметры подключения SQLite
+    /// 
+    /// # Возвращает
+    /// Экземпляр Database или ошибку подключения/миграции
+    async fn connect_with(options: SqliteConnectOptions) -> Result<Self, sqlx::Error> {
+        // WAL и ожидание блокировки вместо мгновенной ошибки "database is locked"
+        // при параллельной записи из фоновой проверки и команд UI
+        let options = options
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(Duration::from_secs(10));
+        let pool = SqlitePool::connect_with(options).await?;
         
         let db = Database { pool };
         db.init().await?;
@@ -37,367 +82,1383 @@ impl Database {
 
     /// Инициализировать схему базы данных
     /// 
-    /// Создает все необходимые таблицы (sites, mods, notifications, saved_pages)
-    /// и индексы, если они не существуют.
+    /// Применяет недостающие миграции схемы (см. migrations.rs). База данных,
+    /// созданная более новой версией приложения, не открывается.
     /// 
     /// # Возвращает
     /// Пустой результат при успехе или ошибку
     async fn init(&self) -> Result<(), sqlx::Error> {
-        sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS sites (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                name TEXT NOT NULL,
-                url TEXT NOT NULL UNIQUE,
-                parser_config TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
-            )
-            "#,
+        migrations::run(&self.pool)
+            .await
+            .map_err(|e| sqlx::Error::Configuration(Box::new(e)))?;
+        self.upgrade_parser_configs().await
+    }
+
+    /// Обновить сохраненные конфигурации парсеров до текущей версии схемы
+    /// 
+    /// Конфигурации хранятся как JSON, поэтому обновляются кодом (см.
+    /// site_config.rs), а не SQL миграцией. Уже обновленные не изменяются.
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    async fn upgrade_parser_configs(&self) -> Result<(), sqlx::Error> {
+        let rows = sqlx::query("SELECT id, parser_config FROM sites")
+            .fetch_all(&self.pool)
+            .await?;
+
+        for row in rows {
+            let id: i64 = row.get(0);
+            let config: serde_json::Value = serde_json::from_str(row.get::<String, _>(1).as_str())
+                .unwrap_or(serde_json::json!({}));
+            if !site_config::needs_upgrade(&config) {
+                continue;
+            }
+            let upgraded = site_config::upgrade(&config);
+            sqlx::query("UPDATE sites SET parser_config = ? WHERE id = ?")
+                .bind(upgraded.to_string())
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+            info!("Upgraded parser config of site {} to version {}", id, site_config::CURRENT_CONFIG_VERSION);
+        }
+        Ok(())
+    }
+
+    /// Получить текущую версию схемы базы данных
+    /// 
+    /// # Возвращает
+    /// Номер последней примененной миграции или ошибку
+    pub async fn schema_version(&self) -> Result<i64, sqlx::Error> {
+        migrations::current_version(&self.pool).await
+    }
+
+    /// Получить список всех сайтов из базы данных
+    /// 
+    /// Возвращает все сайты, отсортированные по имени.
+    /// 
+    /// # Возвращает
+    /// Вектор сайтов или ошибку базы данных
+    pub async fn get_sites(&self) -> Result<Vec<Site>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM sites ORDER BY name")
+            .fetch_all(&self.pool)
+            .await?;
+
+        Ok(rows
+            .iter()
+            .map(|row| Site {
+                id: row.get(0),
+                name: row.get(1),
+                url: row.get(2),
+                parser_config: serde_json::from_str(row.get::<String, _>(3).as_str())
+                    .unwrap_or(serde_json::json!({})),
+                created_at: row.get::<String, _>(4).parse().unwrap_or(Utc::now()),
+                updated_at: row.get::<String, _>(5).parse().unwrap_or(Utc::now()),
+                install_dir: row.get(6),
+                notify_new_mods: row.get::<i64, _>(7) != 0,
+            })
+            .collect())
+    }
+
+    /// Получить сайт по ID
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор сайта
+    /// 
+    /// # Возвращает
+    /// Сайт или ошибку, если не найден
+    pub async fn get_site(&self, id: i64) -> Result<Site, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM sites WHERE id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+
+        Ok(Site {
+            id: row.get(0),
+            name: row.get(1),
+            url: row.get(2),
+            parser_config: serde_json::from_str(row.get::<String, _>(3).as_str())
+                .unwrap_or(serde_json::json!({})),
+            created_at: row.get::<String, _>(4).parse().unwrap_or(Utc::now()),
+            updated_at: row.get::<String, _>(5).parse().unwrap_or(Utc::now()),
+            install_dir: row.get(6),
+            notify_new_mods: row.get::<i64, _>(7) != 0,
+        })
+    }
+
+    /// Добавить новый сайт в базу данных
+    /// 
+    /// # Параметры
+    /// * `name` - название сайта
+    /// * `url` - URL сайта
+    /// * `parser_config` - конфигурация парсера в формате JSON
+    /// 
+    /// # Возвращает
+    /// Созданный сайт с присвоенным ID или ошибку
+    pub async fn add_site(
+        &self,
+        name: &str,
+        url: &str,
+        parser_config: &serde_json::Value,
+    ) -> Result<Site, sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let config_str = serde_json::to_string(parser_config).unwrap_or_default();
+
+        let id = sqlx::query(
+            "INSERT INTO sites (name, url, parser_config, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
         )
+        .bind(name)
+        .bind(url)
+        .bind(&config_str)
+        .bind(&now)
+        .bind(&now)
         .execute(&self.pool)
-        .await?;
+        .await?
+        .last_insert_rowid();
+
+        Ok(Site {
+            id,
+            name: name.to_string(),
+            url: url.to_string(),
+            parser_config: parser_config.clone(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            install_dir: None,
+            notify_new_mods: false,
+        })
+    }
+
+    /// Обновить существующий сайт в базе данных
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор сайта для обновления
+    /// * `name` - новое название сайта
+    /// * `url` - новый URL сайта
+    /// * `parser_config` - новая конфигурация парсера в формате JSON
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    pub async fn update_site(
+        &self,
+        id: i64,
+        name: &str,
+        url: &str,
+        parser_config: &serde_json::Value,
+    ) -> Result<(), sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let config_str = serde_json::to_string(parser_config).unwrap_or_default();
 
         sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS mods (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                site_id INTEGER NOT NULL,
-                title TEXT NOT NULL,
-                url TEXT NOT NULL UNIQUE,
-                version TEXT,
-                author TEXT,
-                description TEXT,
-                image_url TEXT,
-                changes TEXT,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                FOREIGN KEY (site_id) REFERENCES sites(id)
-            )
-            "#,
+            "UPDATE sites SET name = ?, url = ?, parser_config = ?, updated_at = ? WHERE id = ?",
         )
+        .bind(name)
+        .bind(url)
+        .bind(&config_str)
+        .bind(&now)
+        .bind(id)
         .execute(&self.pool)
         .await?;
 
+        Ok(())
+    }
+
+    /// Задать директорию установки модов сайта
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор сайта
+    /// * `install_dir` - директория игры/модов (None = не задана)
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    pub async fn set_site_install_dir(&self, id: i64, install_dir: Option<&str>) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE sites SET install_dir = ?, updated_at = ? WHERE id = ?")
+            .bind(install_dir)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Задать, уведомлять ли о новых модах сайта
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор сайта
+    /// * `notify` - true - уведомление о каждом новом моде, false - новые моды сохраняются молча
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    pub async fn set_site_notify_new_mods(&self, id: i64, notify: bool) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE sites SET notify_new_mods = ?, updated_at = ? WHERE id = ?")
+            .bind(notify)
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Удалить сайт из базы данных
+    /// 
+    /// Вместе с сайтом удаляются его моды и все, что на них ссылается: история
+    /// версий, загрузки, установки с их файлами, записи в профилях, а также
+    /// уведомления, расписание, история проверок и сохраненные страницы сайта. Удаление выполняется в одной
+    /// транзакции. Файлы загрузок, установленных модов и сохраненных страниц
+    /// на диске не удаляются.
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор сайта для удаления
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    pub async fn delete_site(&self, id: i64) -> Result<(), sqlx::Error> {
+        // Зависимые записи удаляются раньше тех, на которые они ссылаются
+        let statements = [
+            "DELETE FROM installed_files WHERE installation_id IN \
+             (SELECT id FROM installations WHERE mod_id IN (SELECT id FROM mods WHERE site_id = ?))",
+            "DELETE FROM installations WHERE mod_id IN (SELECT id FROM mods WHERE site_id = ?)",
+            "DELETE FROM downloads WHERE mod_id IN (SELECT id FROM mods WHERE site_id = ?)",
+            "DELETE FROM profile_mods WHERE mod_id IN (SELECT id FROM mods WHERE site_id = ?)",
+            "DELETE FROM mod_versions WHERE mod_id IN (SELECT id FROM mods WHERE site_id = ?)",
+            "DELETE FROM notifications WHERE site_id = ?",
+            "DELETE FROM site_schedules WHERE site_id = ?",
+            "DELETE FROM check_runs WHERE site_id = ?",
+            "DELETE FROM mods WHERE site_id = ?",
+            "DELETE FROM saved_pages WHERE site_id = ?",
+            "DELETE FROM sites WHERE id = ?",
+        ];
+        let mut tx = self.pool.begin().await?;
+        for statement in statements {
+            sqlx::query(statement).bind(id).execute(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Получить расписания проверки всех сайтов
+    /// 
+    /// Для сайтов без сохраненного расписания возвращается расписание по
+    /// умолчанию (раз в час, включено, проверка как можно скорее).
+    /// 
+    /// # Возвращает
+    /// Расписания, отсортированные по ID сайта, или ошибку
+    pub async fn get_site_schedules(&self) -> Result<Vec<SiteSchedule>, sqlx::Error> {
+        let rows = sqlx::query(&format!("{} ORDER BY s.id", SITE_SCHEDULE_QUERY))
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(Self::row_to_site_schedule).collect())
+    }
+
+    /// Получить расписание проверки сайта
+    /// 
+    /// # Параметры
+    /// * `site_id` - ID сайта
+    /// 
+    /// # Возвращает
+    /// Расписание (по умолчанию, если не сохранено) или `RowNotFound`, если сайта нет
+    pub async fn get_site_schedule(&self, site_id: i64) -> Result<SiteSchedule, sqlx::Error> {
+        let row = sqlx::query(&format!("{} WHERE s.id = ?", SITE_SCHEDULE_QUERY))
+            .bind(site_id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(Self::row_to_site_schedule(&row))
+    }
+
+    /// Сохранить расписание и состояние проверки сайта
+    /// 
+    /// # Параметры
+    /// * `schedule` - расписание сайта (запись создается или заменяется)
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    pub async fn save_site_schedule(&self, schedule: &SiteSchedule) -> Result<(), sqlx::Error> {
+        let (interval_secs, cron) = match &schedule.schedule {
+            Schedule::Interval { seconds } => (Some(*seconds), None),
+            Schedule::Cron { expression } => (None, Some(expression.as_str())),
+        };
         sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS notifications (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                mod_id INTEGER NOT NULL,
-                site_id INTEGER NOT NULL,
-                title TEXT NOT NULL,
-                message TEXT NOT NULL,
-                read INTEGER NOT NULL DEFAULT 0,
-                created_at TEXT NOT NULL,
-                FOREIGN KEY (mod_id) REFERENCES mods(id),
-                FOREIGN KEY (site_id) REFERENCES sites(id)
-            )
-            "#,
+            "INSERT OR REPLACE INTO site_schedules (site_id, interval_secs, cron, enabled, last_run_at, next_run_at, failure_count, last_error) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
         )
+        .bind(schedule.site_id)
+        .bind(interval_secs)
+        .bind(cron)
+        .bind(schedule.enabled)
+        .bind(schedule.last_run_at.map(|t| t.to_rfc3339()))
+        .bind(schedule.next_run_at.map(|t| t.to_rfc3339()))
+        .bind(schedule.failure_count)
+        .bind(&schedule.last_error)
         .execute(&self.pool)
         .await?;
+        Ok(())
+    }
 
+    /// Записать проверку сайта
+    /// 
+    /// Хранятся последние `MAX_CHECK_RUNS_PER_SITE` проверок каждого сайта,
+    /// более старые удаляются.
+    /// 
+    /// # Параметры
+    /// * `run` - проверка (поле `id` игнорируется)
+    /// 
+    /// # Возвращает
+    /// Сохраненную проверку с присвоенным ID или ошибку
+    pub async fn add_check_run(&self, run: &CheckRun) -> Result<CheckRun, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let id = sqlx::query(
+            "INSERT INTO check_runs (site_id, started_at, finished_at, http_status, mods_found, new_mods, updated_mods, error, null_fraction, drift, saved_page_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(run.site_id)
+        .bind(run.started_at.to_rfc3339())
+        .bind(run.finished_at.to_rfc3339())
+        .bind(run.http_status.map(i64::from))
+        .bind(run.mods_found)
+        .bind(run.new_mods)
+        .bind(run.updated_mods)
+        .bind(&run.error)
+        .bind(run.null_fraction)
+        .bind(&run.drift)
+        .bind(run.saved_page_id)
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
         sqlx::query(
-            r#"
-            CREATE TABLE IF NOT EXISTS saved_pages (
-                id INTEGER PRIMARY KEY AUTOINCREMENT,
-                site_id INTEGER NOT NULL,
-                url TEXT NOT NULL,
-                folder_path TEXT NOT NULL,
-                version_timestamp TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                FOREIGN KEY (site_id) REFERENCES sites(id)
-            )
-            "#,
+            "DELETE FROM check_runs WHERE site_id = ? AND id NOT IN (SELECT id FROM check_runs WHERE site_id = ? ORDER BY id DESC LIMIT ?)",
         )
-        .execute(&self.pool)
+        .bind(run.site_id)
+        .bind(run.site_id)
+        .bind(MAX_CHECK_RUNS_PER_SITE)
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
+
+        Ok(CheckRun { id, ..run.clone() })
+    }
+
+    /// Получить последние проверки сайтов
+    /// 
+    /// # Параметры
+    /// * `site_id` - ID сайта для фильтрации (None = все сайты)
+    /// * `limit` - максимальное количество записей
+    /// 
+    /// # Возвращает
+    /// Проверки от новых к старым или ошибку
+    pub async fn get_check_runs(&self, site_id: Option<i64>, limit: i64) -> Result<Vec<CheckRun>, sqlx::Error> {
+        let rows = if let Some(id) = site_id {
+            sqlx::query("SELECT * FROM check_runs WHERE site_id = ? ORDER BY id DESC LIMIT ?")
+                .bind(id)
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?
+        } else {
+            sqlx::query("SELECT * FROM check_runs ORDER BY id DESC LIMIT ?")
+                .bind(limit)
+                .fetch_all(&self.pool)
+                .await?
+        };
+        Ok(rows.iter().map(Self::row_to_check_run).collect())
+    }
+
+    fn row_to_check_run(row: &SqliteRow) -> CheckRun {
+        CheckRun {
+            id: row.get(0),
+            site_id: row.get(1),
+            started_at: row.get::<String, _>(2).parse().unwrap_or(Utc::now()),
+            finished_at: row.get::<String, _>(3).parse().unwrap_or(Utc::now()),
+            http_status: row.get::<Option<i64>, _>(4).and_then(|status| u16::try_from(status).ok()),
+            mods_found: row.get(5),
+            new_mods: row.get(6),
+            updated_mods: row.get(7),
+            error: row.get(8),
+            null_fraction: row.get(9),
+            drift: row.get(10),
+            saved_page_id: row.get(11),
+        }
+    }
+
+    fn row_to_site_schedule(row: &SqliteRow) -> SiteSchedule {
+        let parse_time = |value: Option<String>| value.and_then(|v| v.parse().ok());
+        let schedule = match (row.get::<Option<i64>, _>(1), row.get::<Option<String>, _>(2)) {
+            (_, Some(expression)) => Schedule::Cron { expression },
+            (Some(seconds), None) => Schedule::Interval { seconds },
+            (None, None) => Schedule::default(),
+        };
+        SiteSchedule {
+            site_id: row.get(0),
+            schedule,
+            enabled: row.get::<Option<i64>, _>(3).is_none_or(|enabled| enabled != 0),
+            last_run_at: parse_time(row.get(4)),
+            next_run_at: parse_time(row.get(5)),
+            failure_count: row.get::<Option<i64>, _>(6).unwrap_or(0),
+            last_error: row.get(7),
+        }
+    }
+
+    /// Получить список модов из базы данных
+    /// 
+    /// # Параметры
+    /// * `site_id` - ID сайта для фильтрации (None = все сайты)
+    /// 
+    /// # Возвращает
+    /// Вектор модов, отсортированных по дате обновления, или ошибку
+    pub async fn get_mods(&self, site_id: Option<i64>) -> Result<Vec<Mod>, sqlx::Error> {
+        let rows = if let Some(id) = site_id {
+            sqlx::query("SELECT * FROM mods WHERE site_id = ? ORDER BY updated_at DESC")
+                .bind(id)
+                .fetch_all(&self.pool)
+                .await?
+        } else {
+            sqlx::query("SELECT * FROM mods ORDER BY updated_at DESC")
+                .fetch_all(&self.pool)
+                .await?
+        };
+
+        Ok(rows.iter().map(Self::row_to_mod).collect())
+    }
+
+    /// Получить страницу списка модов
+    ///
+    /// Постраничный вариант `get_mods` для интерфейса: сортировка по индексам
+    /// (см. миграцию `mods_listing_indexes`) и общее количество для навигации.
+    /// При равных значениях поля сортировки порядок определяется ID мода,
+    /// моды без автора при сортировке по автору идут последними.
+    ///
+    /// # Параметры
+    /// * `site_id` - ID сайта для фильтрации (None = все сайты)
+    /// * `sort` - поле сортировки
+    /// * `direction` - направление сортировки
+    /// * `limit` - размер страницы (от 1 до `ModPage::MAX_PAGE_SIZE`)
+    /// * `offset` - смещение от начала списка
+    ///
+    /// # Возвращает
+    /// Страницу модов с общим количеством или ошибку
+    pub async fn get_mods_page(
+        &self,
+        site_id: Option<i64>,
+        sort: ModSort,
+        direction: SortDirection,
+        limit: i64,
+        offset: i64,
+    ) -> Result<ModPage, sqlx::Error> {
+        let limit = limit.clamp(1, ModPage::MAX_PAGE_SIZE);
+        let offset = offset.max(0);
+        let dir = match direction {
+            SortDirection::Asc => "ASC",
+            SortDirection::Desc => "DESC",
+        };
+        let order = match sort {
+            ModSort::Title => format!("title COLLATE NOCASE {0}, id {0}", dir),
+            ModSort::Author => format!("author IS NULL, author COLLATE NOCASE {0}, id {0}", dir),
+            ModSort::Created => format!("created_at {0}, id {0}", dir),
+            ModSort::Updated => format!("updated_at {0}, id {0}", dir),
+        };
+        let filter = if site_id.is_some() { "WHERE site_id = ?" } else { "" };
+
+        let count_sql = format!("SELECT COUNT(*) FROM mods {}", filter);
+        let page_sql = format!("SELECT * FROM mods {} ORDER BY {} LIMIT ? OFFSET ?", filter, order);
+        let mut count = sqlx::query(&count_sql);
+        let mut page = sqlx::query(&page_sql);
+        if let Some(id) = site_id {
+            count = count.bind(id);
+            page = page.bind(id);
+        }
+        let total: i64 = count.fetch_one(&self.pool).await?.get(0);
+        let rows = page.bind(limit).bind(offset).fetch_all(&self.pool).await?;
+
+        Ok(ModPage {
+            mods: rows.iter().map(Self::row_to_mod).collect(),
+            total,
+            limit,
+            offset,
+        })
+    }
+
+    /// Полнотекстовый поиск модов
+    ///
+    /// Ищет по индексу FTS5 (`mods_fts`) в названии, авторе, описании и списке
+    /// изменений. Пустой запрос возвращает все моды, подходящие под фильтры.
+    ///
+    /// # Параметры
+    /// * `query` - строка поиска (слова ищутся как префиксы, через AND)
+    /// * `filters` - фильтры по сайту, автору, дате обновления и подписке
+    /// * `sort` - порядок результатов
+    /// * `limit` - размер страницы (ограничен `MAX_SEARCH_LIMIT`)
+    /// * `offset` - смещение от начала результатов
+    ///
+    /// # Возвращает
+    /// Страницу найденных модов со сниппетами и общее количество или ошибку
+    pub async fn search_mods(
+        &self,
+        query: &str,
+        filters: &SearchFilters,
+        sort: SearchSort,
+        limit: i64,
+        offset: i64,
+    ) -> Result<SearchResults, sqlx::Error> {
+        let fts_query = search::fts_query(query);
+
+        let mut count = QueryBuilder::<Sqlite>::new("SELECT COUNT(*)");
+        Self::push_search_conditions(&mut count, fts_query.as_deref(), filters);
+        let total: i64 = count.build().fetch_one(&self.pool).await?.get(0);
+
+        let mut builder = QueryBuilder::<Sqlite>::new("SELECT m.*, ");
+        if fts_query.is_some() {
+            builder.push(format!(
+                "snippet(mods_fts, -1, '{}', '{}', '…', 16)",
+                search::MATCH_START,
+                search::MATCH_END
+            ));
+        } else {
+            builder.push("NULL");
+        }
+        Self::push_search_conditions(&mut builder, fts_query.as_deref(), filters);
+
+        let order = match sort {
+            // Совпадение в названии важнее, чем в авторе, описании и изменениях
+            SearchSort::Relevance if fts_query.is_some() => "bm25(mods_fts, 10.0, 5.0, 2.0, 1.0), m.id DESC",
+            SearchSort::Relevance | SearchSort::Updated => "m.updated_at DESC, m.id DESC",
+            SearchSort::Created => "m.created_at DESC, m.id DESC",
+            SearchSort::Title => "m.title COLLATE NOCASE, m.id",
+        };
+        builder
+            .push(format!(" ORDER BY {} LIMIT ", order))
+            .push_bind(limit.clamp(1, search::MAX_SEARCH_LIMIT))
+            .push(" OFFSET ")
+            .push_bind(offset.max(0));
+
+        let rows = builder.build().fetch_all(&self.pool).await?;
+        Ok(SearchResults {
+            total,
+            hits: rows
+                .iter()
+                .map(|row| SearchHit {
+                    mod_item: Self::row_to_mod(row),
+                    snippet: row.get::<Option<String>, _>(14).map(|s| search::highlight(&s)),
+                })
+                .collect(),
+        })
+    }
+
+    /// Добавить к запросу поиска FROM и условия WHERE
+    fn push_search_conditions(builder: &mut QueryBuilder<'_, Sqlite>, fts_query: Option<&str>, filters: &SearchFilters) {
+        match fts_query {
+            Some(fts_query) => {
+                builder
+                    .push(" FROM mods m JOIN mods_fts ON mods_fts.rowid = m.id WHERE mods_fts MATCH ")
+                    .push_bind(fts_query.to_string());
+            }
+            None => {
+                builder.push(" FROM mods m WHERE 1 = 1");
+            }
+        }
+        if let Some(site_id) = filters.site_id {
+            builder.push(" AND m.site_id = ").push_bind(site_id);
+        }
+        if let Some(author) = filters.author.as_deref().map(str::trim).filter(|a| !a.is_empty()) {
+            builder.push(" AND m.author = ").push_bind(author.to_string()).push(" COLLATE NOCASE");
+        }
+        if let Some(from) = filters.updated_from {
+            builder.push(" AND julianday(m.updated_at) >= julianday(").push_bind(from.to_rfc3339()).push(")");
+        }
+        if let Some(to) = filters.updated_to {
+            builder.push(" AND julianday(m.updated_at) <= julianday(").push_bind(to.to_rfc3339()).push(")");
+        }
+        if filters.followed_only {
+            builder.push(" AND m.followed = 1");
+        }
+    }
+
+    /// Получить мод по URL
+    /// 
+    /// # Параметры
+    /// * `url` - URL мода для поиска
+    /// 
+    /// # Возвращает
+    /// Мод, если найден, или None
+    pub async fn get_mod_by_url(&self, url: &str) -> Result<Option<Mod>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM mods WHERE url = ?")
+            .bind(url)
+            .fetch_optional(&self.pool)
+            .await?;
+
+        Ok(row.as_ref().map(Self::row_to_mod))
+    }
+
+    /// Получить моды, за которыми следит пользователь
+    /// 
+    /// # Возвращает
+    /// Моды, отсортированные по дате обновления, или ошибку
+    pub async fn get_followed_mods(&self) -> Result<Vec<Mod>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM mods WHERE followed = 1 ORDER BY updated_at DESC")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows.iter().map(Self::row_to_mod).collect())
+    }
+
+    /// Подписаться на мод или отписаться от него
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор мода
+    /// * `followed` - следить за модом
+    /// 
+    /// # Возвращает
+    /// Пустой результат или ошибку (RowNotFound, если мода нет)
+    pub async fn set_mod_followed(&self, id: i64, followed: bool) -> Result<(), sqlx::Error> {
+        let result = sqlx::query("UPDATE mods SET followed = ? WHERE id = ?")
+            .bind(followed)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+
+    /// Получить мод по ID
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор мода
+    /// 
+    /// # Возвращает
+    /// Мод или ошибку (RowNotFound, если мода нет)
+    pub async fn get_mod(&self, id: i64) -> Result<Mod, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM mods WHERE id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(Self::row_to_mod(&row))
+    }
+
+    /// Преобразовать строку таблицы mods в мод
+    fn row_to_mod(row: &SqliteRow) -> Mod {
+        Mod {
+            id: row.get(0),
+            site_id: row.get(1),
+            title: row.get(2),
+            url: row.get(3),
+            version: row.get(4),
+            author: row.get(5),
+            description: row.get(6),
+            image_url: row.get(7),
+            changes: row.get(8),
+            download_url: row.get(11),
+            files: Self::files_from_column(row.get(12)),
+            followed: row.get::<i64, _>(13) != 0,
+            created_at: row.get::<String, _>(9).parse().unwrap_or(Utc::now()),
+            updated_at: row.get::<String, _>(10).parse().unwrap_or(Utc::now()),
+        }
+    }
+
+    /// Добавить новый мод в базу данных
+    /// 
+    /// Вместе с модом сохраняет первую запись истории версий. Даты создания и
+    /// обновления берутся из мода (парсеры лент передают даты публикации).
+    /// 
+    /// # Параметры
+    /// * `mod_item` - объект мода для добавления
+    /// 
+    /// # Возвращает
+    /// Созданный мод с присвоенным ID или ошибку
+    pub async fn add_mod(&self, mod_item: &Mod) -> Result<Mod, sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        let id = sqlx::query(
+            "INSERT INTO mods (site_id, title, url, version, author, description, image_url, changes, download_url, files, followed, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(mod_item.site_id)
+        .bind(&mod_item.title)
+        .bind(&mod_item.url)
+        .bind(&mod_item.version)
+        .bind(&mod_item.author)
+        .bind(&mod_item.description)
+        .bind(&mod_item.image_url)
+        .bind(&mod_item.changes)
+        .bind(&mod_item.download_url)
+        .bind(Self::files_to_column(&mod_item.files))
+        .bind(mod_item.followed)
+        .bind(mod_item.created_at.to_rfc3339())
+        .bind(mod_item.updated_at.to_rfc3339())
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+
+        Self::insert_mod_version(&mut tx, id, mod_item, &now).await?;
+        tx.commit().await?;
+
+        Ok(Mod {
+            id,
+            ..mod_item.clone()
+        })
+    }
+
+    /// Обновить существующий мод в базе данных
+    /// 
+    /// Предыдущее состояние не теряется: новое состояние мода добавляется
+    /// в историю версий (`mod_versions`). Дата обновления берется из мода:
+    /// парсеры лент передают `<updated>`/`pubDate` записи, HTML парсеры - время
+    /// парсинга. Время записи в историю версий - всегда текущее.
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор мода для обновления
+    /// * `mod_item` - объект мода с новыми данными
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    pub async fn update_mod(&self, id: i64, mod_item: &Mod) -> Result<(), sqlx::Error> {
+        let now = Utc::now().to_rfc3339();
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "UPDATE mods SET title = ?, version = ?, author = ?, description = ?, image_url = ?, changes = ?, download_url = ?, files = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&mod_item.title)
+        .bind(&mod_item.version)
+        .bind(&mod_item.author)
+        .bind(&mod_item.description)
+        .bind(&mod_item.image_url)
+        .bind(&mod_item.changes)
+        .bind(&mod_item.download_url)
+        .bind(Self::files_to_column(&mod_item.files))
+        .bind(mod_item.updated_at.to_rfc3339())
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+        Self::insert_mod_version(&mut tx, id, mod_item, &now).await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Прочитать файлы мода из колонки `files` (JSON массив)
+    fn files_from_column(value: String) -> Vec<ModFile> {
+        serde_json::from_str(&value).unwrap_or_default()
+    }
+
+    /// Сериализовать файлы мода для колонки `files`
+    fn files_to_column(files: &[ModFile]) -> String {
+        serde_json::to_string(files).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    /// Добавить запись в историю версий мода
+    /// 
+    /// # Параметры
+    /// * `tx` - транзакция, в которой сохраняется мод
+    /// * `mod_id` - идентификатор мода
+    /// * `mod_item` - состояние мода на момент парсинга
+    /// * `scraped_at` - время парсинга (RFC 3339)
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    async fn insert_mod_version(
+        tx: &mut Transaction<'_, Sqlite>,
+        mod_id: i64,
+        mod_item: &Mod,
+        scraped_at: &str,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "INSERT INTO mod_versions (mod_id, version, changes, snapshot, scraped_at) VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(mod_id)
+        .bind(&mod_item.version)
+        .bind(&mod_item.changes)
+        .bind(changes::mod_snapshot(mod_item).to_string())
+        .bind(scraped_at)
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Получить историю версий мода
+    /// 
+    /// # Параметры
+    /// * `mod_id` - идентификатор мода
+    /// 
+    /// # Возвращает
+    /// Записи истории, отсортированные по времени парсинга (новые первыми), или ошибку
+    pub async fn get_mod_history(&self, mod_id: i64) -> Result<Vec<ModVersion>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT id, mod_id, version, changes, snapshot, scraped_at FROM mod_versions WHERE mod_id = ? ORDER BY scraped_at DESC, id DESC",
+        )
+        .bind(mod_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.iter().map(Self::row_to_mod_version).collect())
+    }
+
+    /// Получить запись истории версий по ID
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор записи истории
+    /// 
+    /// # Возвращает
+    /// Запись истории или ошибку (RowNotFound, если запись не найдена)
+    pub async fn get_mod_version(&self, id: i64) -> Result<ModVersion, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT id, mod_id, version, changes, snapshot, scraped_at FROM mod_versions WHERE id = ?",
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(Self::row_to_mod_version(&row))
+    }
+
+    /// Преобразовать строку таблицы mod_versions в запись истории
+    fn row_to_mod_version(row: &SqliteRow) -> ModVersion {
+        ModVersion {
+            id: row.get(0),
+            mod_id: row.get(1),
+            version: row.get(2),
+            changes: row.get(3),
+            snapshot: serde_json::from_str(row.get::<String, _>(4).as_str())
+                .unwrap_or(serde_json::json!({})),
+            scraped_at: row.get::<String, _>(5).parse().unwrap_or(Utc::now()),
+        }
+    }
+
+    /// Добавить загрузку файла мода
+    /// 
+    /// # Параметры
+    /// * `download` - загрузка (поле `id` игнорируется)
+    /// 
+    /// # Возвращает
+    /// Созданную загрузку с присвоенным ID или ошибку
+    pub async fn add_download(&self, download: &Download) -> Result<Download, sqlx::Error> {
+        let now = Utc::now();
+        let id = sqlx::query(
+            "INSERT INTO downloads (mod_id, mod_version_id, version, url, file_name, path, status, total_bytes, downloaded_bytes, content_type, sha256, error, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(download.mod_id)
+        .bind(download.mod_version_id)
+        .bind(&download.version)
+        .bind(&download.url)
+        .bind(&download.file_name)
+        .bind(&download.path)
+        .bind(download.status.as_str())
+        .bind(download.total_bytes)
+        .bind(download.downloaded_bytes)
+        .bind(&download.content_type)
+        .bind(&download.sha256)
+        .bind(&download.error)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&self.pool)
+        .await?
+        .last_insert_rowid();
+
+        Ok(Download {
+            id,
+            created_at: now,
+            updated_at: now,
+            ..download.clone()
+        })
+    }
+
+    /// Сохранить состояние загрузки (статус, прогресс, результат)
+    /// 
+    /// # Параметры
+    /// * `download` - загрузка с новым состоянием
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    pub async fn update_download(&self, download: &Download) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE downloads SET status = ?, total_bytes = ?, downloaded_bytes = ?, content_type = ?, sha256 = ?, error = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(download.status.as_str())
+        .bind(download.total_bytes)
+        .bind(download.downloaded_bytes)
+        .bind(&download.content_type)
+        .bind(&download.sha256)
+        .bind(&download.error)
+        .bind(download.updated_at.to_rfc3339())
+        .bind(download.id)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Получить загрузку по ID
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор загрузки
+    /// 
+    /// # Возвращает
+    /// Загрузку или ошибку (RowNotFound, если загрузки нет)
+    pub async fn get_download(&self, id: i64) -> Result<Download, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM downloads WHERE id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(Self::row_to_download(&row))
+    }
+
+    /// Получить список загрузок
+    /// 
+    /// # Параметры
+    /// * `mod_id` - ID мода для фильтрации (None = все моды)
+    /// 
+    /// # Возвращает
+    /// Загрузки (новые первыми) или ошибку
+    pub async fn get_downloads(&self, mod_id: Option<i64>) -> Result<Vec<Download>, sqlx::Error> {
+        let rows = if let Some(id) = mod_id {
+            sqlx::query("SELECT * FROM downloads WHERE mod_id = ? ORDER BY created_at DESC, id DESC")
+                .bind(id)
+                .fetch_all(&self.pool)
+                .await?
+        } else {
+            sqlx::query("SELECT * FROM downloads ORDER BY created_at DESC, id DESC")
+                .fetch_all(&self.pool)
+                .await?
+        };
+        Ok(rows.iter().map(Self::row_to_download).collect())
+    }
+
+    /// Перевести прерванные загрузки в паузу
+    /// 
+    /// Загрузки в статусе queued/downloading после перезапуска приложения
+    /// никем не выполняются; в паузе их можно продолжить.
+    /// 
+    /// # Возвращает
+    /// Количество измененных загрузок или ошибку
+    pub async fn pause_interrupted_downloads(&self) -> Result<u64, sqlx::Error> {
+        let result = sqlx::query(
+            "UPDATE downloads SET status = ?, updated_at = ? WHERE status IN (?, ?)",
+        )
+        .bind(DownloadStatus::Paused.as_str())
+        .bind(Utc::now().to_rfc3339())
+        .bind(DownloadStatus::Queued.as_str())
+        .bind(DownloadStatus::Downloading.as_str())
+        .execute(&self.pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Преобразовать строку таблицы downloads в загрузку
+    fn row_to_download(row: &SqliteRow) -> Download {
+        Download {
+            id: row.get(0),
+            mod_id: row.get(1),
+            mod_version_id: row.get(2),
+            version: row.get(3),
+            url: row.get(4),
+            file_name: row.get(5),
+            path: row.get(6),
+            status: DownloadStatus::from_db(row.get::<String, _>(7).as_str()),
+            total_bytes: row.get(8),
+            downloaded_bytes: row.get(9),
+            content_type: row.get(10),
+            sha256: row.get(11),
+            error: row.get(12),
+            created_at: row.get::<String, _>(13).parse().unwrap_or(Utc::now()),
+            updated_at: row.get::<String, _>(14).parse().unwrap_or(Utc::now()),
+        }
+    }
+
+    /// Получить установки модов
+    /// 
+    /// # Параметры
+    /// * `mod_id` - ID мода для фильтрации (None = все моды)
+    /// 
+    /// # Возвращает
+    /// Установки (недавно обновленные первыми) или ошибку
+    pub async fn get_installations(&self, mod_id: Option<i64>) -> Result<Vec<Installation>, sqlx::Error> {
+        let rows = if let Some(id) = mod_id {
+            sqlx::query("SELECT * FROM installations WHERE mod_id = ? ORDER BY updated_at DESC, id DESC")
+                .bind(id)
+                .fetch_all(&self.pool)
+                .await?
+        } else {
+            sqlx::query("SELECT * FROM installations ORDER BY updated_at DESC, id DESC")
+                .fetch_all(&self.pool)
+                .await?
+        };
+        Ok(rows.iter().map(Self::row_to_installation).collect())
+    }
+
+    /// Получить установку по ID
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор установки
+    /// 
+    /// # Возвращает
+    /// Установку или ошибку (RowNotFound, если установки нет)
+    pub async fn get_installation(&self, id: i64) -> Result<Installation, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM installations WHERE id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(Self::row_to_installation(&row))
+    }
+
+    /// Найти установку мода в директории
+    /// 
+    /// # Параметры
+    /// * `mod_id` - ID мода
+    /// * `install_dir` - директория установки
+    /// 
+    /// # Возвращает
+    /// Установку, None если мод туда не установлен, или ошибку
+    pub async fn find_installation(&self, mod_id: i64, install_dir: &str) -> Result<Option<Installation>, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM installations WHERE mod_id = ? AND install_dir = ?")
+            .bind(mod_id)
+            .bind(install_dir)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row.as_ref().map(Self::row_to_installation))
+    }
+
+    /// Получить файлы установки
+    /// 
+    /// # Параметры
+    /// * `installation_id` - ID установки
+    /// 
+    /// # Возвращает
+    /// Файлы, отсортированные по пути, или ошибку
+    pub async fn get_installed_files(&self, installation_id: i64) -> Result<Vec<InstalledFile>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM installed_files WHERE installation_id = ? ORDER BY path")
+            .bind(installation_id)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(rows
+            .iter()
+            .map(|row| InstalledFile {
+                id: row.get(0),
+                installation_id: row.get(1),
+                path: row.get(2),
+                size: row.get(3),
+            })
+            .collect())
+    }
+
+    /// Сохранить установку и заменить список ее файлов
+    /// 
+    /// Запись и файлы сохраняются в одной транзакции.
+    /// 
+    /// # Параметры
+    /// * `installation` - установка (id = 0 - новая)
+    /// * `files` - записанные файлы: путь относительно директории установки и размер
+    /// 
+    /// # Возвращает
+    /// Сохраненную установку или ошибку
+    pub async fn save_installation(
+        &self,
+        installation: &Installation,
+        files: &[(String, i64)],
+    ) -> Result<Installation, sqlx::Error> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+
+        let (id, installed_at) = if installation.id == 0 {
+            let id = sqlx::query(
+                "INSERT INTO installations (mod_id, download_id, version, install_dir, installed_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
+            )
+            .bind(installation.mod_id)
+            .bind(installation.download_id)
+            .bind(&installation.version)
+            .bind(&installation.install_dir)
+            .bind(now.to_rfc3339())
+            .bind(now.to_rfc3339())
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+            (id, now)
+        } else {
+            sqlx::query("UPDATE installations SET download_id = ?, version = ?, updated_at = ? WHERE id = ?")
+                .bind(installation.download_id)
+                .bind(&installation.version)
+                .bind(now.to_rfc3339())
+                .bind(installation.id)
+                .execute(&mut *tx)
+                .await?;
+            (installation.id, installation.installed_at)
+        };
+
+        sqlx::query("DELETE FROM installed_files WHERE installation_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        for (path, size) in files {
+            sqlx::query("INSERT INTO installed_files (installation_id, path, size) VALUES (?, ?, ?)")
+                .bind(id)
+                .bind(path)
+                .bind(size)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
 
-        sqlx::query(
-            r#"
-            CREATE INDEX IF NOT EXISTS idx_mods_site_id ON mods(site_id);
-            CREATE INDEX IF NOT EXISTS idx_mods_url ON mods(url);
-            CREATE INDEX IF NOT EXISTS idx_notifications_read ON notifications(read);
-            CREATE INDEX IF NOT EXISTS idx_saved_pages_site_id ON saved_pages(site_id);
-            CREATE INDEX IF NOT EXISTS idx_saved_pages_url ON saved_pages(url);
-            "#,
-        )
-        .execute(&self.pool)
-        .await?;
+        Ok(Installation {
+            id,
+            installed_at,
+            updated_at: now,
+            ..installation.clone()
+        })
+    }
 
+    /// Удалить установку вместе со списком файлов
+    /// 
+    /// # Параметры
+    /// * `id` - идентификатор установки
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    pub async fn delete_installation(&self, id: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM installed_files WHERE installation_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query("DELETE FROM installations WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
         Ok(())
     }
 
-    /// Получить список всех сайтов из базы данных
-    /// 
-    /// Возвращает все сайты, отсортированные по имени.
+    /// Преобразовать строку таблицы installations в установку
+    fn row_to_installation(row: &SqliteRow) -> Installation {
+        Installation {
+            id: row.get(0),
+            mod_id: row.get(1),
+            download_id: row.get(2),
+            version: row.get(3),
+            install_dir: row.get(4),
+            installed_at: row.get::<String, _>(5).parse().unwrap_or(Utc::now()),
+            updated_at: row.get::<String, _>(6).parse().unwrap_or(Utc::now()),
+        }
+    }
+
+    /// Получить все профили, отсортированные по имени
     /// 
     /// # Возвращает
-    /// Вектор сайтов или ошибку базы данных
-    pub async fn get_sites(&self) -> Result<Vec<Site>, sqlx::Error> {
-        let rows = sqlx::query("SELECT * FROM sites ORDER BY name")
+    /// Вектор профилей или ошибку
+    pub async fn get_profiles(&self) -> Result<Vec<Profile>, sqlx::Error> {
+        let rows = sqlx::query("SELECT * FROM profiles ORDER BY name")
             .fetch_all(&self.pool)
             .await?;
-
-        Ok(rows
-            .iter()
-            .map(|row| Site {
-                id: row.get(0),
-                name: row.get(1),
-                url: row.get(2),
-                parser_config: serde_json::from_str(row.get::<String, _>(3).as_str())
-                    .unwrap_or(serde_json::json!({})),
-                created_at: row.get::<String, _>(4).parse().unwrap_or(Utc::now()),
-                updated_at: row.get::<String, _>(5).parse().unwrap_or(Utc::now()),
-            })
-            .collect())
+        Ok(rows.iter().map(Self::row_to_profile).collect())
     }
 
-    /// Получить сайт по ID
+    /// Получить профиль по ID
     /// 
     /// # Параметры
-    /// * `id` - идентификатор сайта
+    /// * `id` - идентификатор профиля
     /// 
     /// # Возвращает
-    /// Сайт или ошибку, если не найден
-    pub async fn get_site(&self, id: i64) -> Result<Site, sqlx::Error> {
-        let row = sqlx::query("SELECT * FROM sites WHERE id = ?")
+    /// Профиль или ошибку (RowNotFound, если профиля нет)
+    pub async fn get_profile(&self, id: i64) -> Result<Profile, sqlx::Error> {
+        let row = sqlx::query("SELECT * FROM profiles WHERE id = ?")
             .bind(id)
             .fetch_one(&self.pool)
             .await?;
-
-        Ok(Site {
-            id: row.get(0),
-            name: row.get(1),
-            url: row.get(2),
-            parser_config: serde_json::from_str(row.get::<String, _>(3).as_str())
-                .unwrap_or(serde_json::json!({})),
-            created_at: row.get::<String, _>(4).parse().unwrap_or(Utc::now()),
-            updated_at: row.get::<String, _>(5).parse().unwrap_or(Utc::now()),
-        })
+        Ok(Self::row_to_profile(&row))
     }
 
-    /// Добавить новый сайт в базу данных
+    /// Добавить профиль
     /// 
     /// # Параметры
-    /// * `name` - название сайта
-    /// * `url` - URL сайта
-    /// * `parser_config` - конфигурация парсера в формате JSON
+    /// * `name` - уникальное имя профиля
+    /// * `description` - описание
+    /// * `install_dir` - директория игры/модов профиля
     /// 
     /// # Возвращает
-    /// Созданный сайт с присвоенным ID или ошибку
-    pub async fn add_site(
+    /// Созданный профиль или ошибку (нарушение уникальности, если имя занято)
+    pub async fn add_profile(
         &self,
         name: &str,
-        url: &str,
-        parser_config: &serde_json::Value,
-    ) -> Result<Site, sqlx::Error> {
-        let now = Utc::now().to_rfc3339();
-        let config_str = serde_json::to_string(parser_config).unwrap_or_default();
-
-        sqlx::query(
-            "INSERT INTO sites (name, url, parser_config, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
+        description: Option<&str>,
+        install_dir: Option<&str>,
+    ) -> Result<Profile, sqlx::Error> {
+        let now = Utc::now();
+        let id = sqlx::query(
+            "INSERT INTO profiles (name, description, install_dir, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
         )
         .bind(name)
-        .bind(url)
-        .bind(&config_str)
-        .bind(&now)
-        .bind(&now)
+        .bind(description)
+        .bind(install_dir)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
         .execute(&self.pool)
-        .await?;
-
-        let id = sqlx::query("SELECT last_insert_rowid()")
-            .fetch_one(&self.pool)
-            .await?
-            .get(0);
+        .await?
+        .last_insert_rowid();
 
-        Ok(Site {
+        Ok(Profile {
             id,
             name: name.to_string(),
-            url: url.to_string(),
-            parser_config: parser_config.clone(),
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            description: description.map(str::to_string),
+            install_dir: install_dir.map(str::to_string),
+            created_at: now,
+            updated_at: now,
         })
     }
 
-    /// Обновить существующий сайт в базе данных
-    /// 
+    /// Скопировать профиль вместе со списком модов в одной транзакции
+    ///
     /// # Параметры
-    /// * `id` - идентификатор сайта для обновления
-    /// * `name` - новое название сайта
-    /// * `url` - новый URL сайта
-    /// * `parser_config` - новая конфигурация парсера в формате JSON
-    /// 
+    /// * `source` - исходный профиль
+    /// * `name` - уникальное имя копии
+    ///
     /// # Возвращает
-    /// Пустой результат при успехе или ошибку
-    pub async fn update_site(
-        &self,
-        id: i64,
-        name: &str,
-        url: &str,
-        parser_config: &serde_json::Value,
-    ) -> Result<(), sqlx::Error> {
-        let now = Utc::now().to_rfc3339();
-        let config_str = serde_json::to_string(parser_config).unwrap_or_default();
-
-        sqlx::query(
-            "UPDATE sites SET name = ?, url = ?, parser_config = ?, updated_at = ? WHERE id = ?",
+    /// Созданный профиль или ошибку (нарушение уникальности, если имя занято)
+    pub async fn clone_profile(&self, source: &Profile, name: &str) -> Result<Profile, sqlx::Error> {
+        let now = Utc::now();
+        let mut tx = self.pool.begin().await?;
+        let id = sqlx::query(
+            "INSERT INTO profiles (name, description, install_dir, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
         )
         .bind(name)
-        .bind(url)
-        .bind(&config_str)
-        .bind(&now)
+        .bind(&source.description)
+        .bind(&source.install_dir)
+        .bind(now.to_rfc3339())
+        .bind(now.to_rfc3339())
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+        sqlx::query(
+            "INSERT INTO profile_mods (profile_id, mod_id, pinned_version, enabled, position) \
+             SELECT ?, mod_id, pinned_version, enabled, position FROM profile_mods WHERE profile_id = ?",
+        )
         .bind(id)
-        .execute(&self.pool)
+        .bind(source.id)
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
 
-        Ok(())
+        Ok(Profile {
+            id,
+            name: name.to_string(),
+            description: source.description.clone(),
+            install_dir: source.install_dir.clone(),
+            created_at: now,
+            updated_at: now,
+        })
     }
 
-    /// Удалить сайт из базы данных
+    /// Обновить профиль
     /// 
     /// # Параметры
-    /// * `id` - идентификатор сайта для удаления
+    /// * `id` - идентификатор профиля
+    /// * `name` - новое имя
+    /// * `description` - новое описание
+    /// * `install_dir` - новая директория игры/модов
     /// 
     /// # Возвращает
     /// Пустой результат при успехе или ошибку
-    pub async fn delete_site(&self, id: i64) -> Result<(), sqlx::Error> {
-        sqlx::query("DELETE FROM sites WHERE id = ?")
+    pub async fn update_profile(
+        &self,
+        id: i64,
+        name: &str,
+        description: Option<&str>,
+        install_dir: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE profiles SET name = ?, description = ?, install_dir = ?, updated_at = ? WHERE id = ?")
+            .bind(name)
+            .bind(description)
+            .bind(install_dir)
+            .bind(Utc::now().to_rfc3339())
             .bind(id)
             .execute(&self.pool)
             .await?;
         Ok(())
     }
 
-    /// Получить список модов из базы данных
-    /// 
-    /// # Параметры
-    /// * `site_id` - ID сайта для фильтрации (None = все сайты)
-    /// 
-    /// # Возвращает
-    /// Вектор модов, отсортированных по дате обновления, или ошибку
-    pub async fn get_mods(&self, site_id: Option<i64>) -> Result<Vec<Mod>, sqlx::Error> {
-        let rows = if let Some(id) = site_id {
-            sqlx::query("SELECT * FROM mods WHERE site_id = ? ORDER BY updated_at DESC")
-                .bind(id)
-                .fetch_all(&self.pool)
-                .await?
-        } else {
-            sqlx::query("SELECT * FROM mods ORDER BY updated_at DESC")
-                .fetch_all(&self.pool)
-                .await?
-        };
-
-        Ok(rows
-            .iter()
-            .map(|row| Mod {
-                id: row.get(0),
-                site_id: row.get(1),
-                title: row.get(2),
-                url: row.get(3),
-                version: row.get(4),
-                author: row.get(5),
-                description: row.get(6),
-                image_url: row.get(7),
-                changes: row.get(8),
-                created_at: row.get::<String, _>(9).parse().unwrap_or(Utc::now()),
-                updated_at: row.get::<String, _>(10).parse().unwrap_or(Utc::now()),
-            })
-            .collect())
-    }
-
-    /// Получить мод по URL
+    /// Удалить профиль вместе со списком модов (сами моды остаются)
     /// 
     /// # Параметры
-    /// * `url` - URL мода для поиска
+    /// * `id` - идентификатор профиля
     /// 
     /// # Возвращает
-    /// Мод, если найден, или None
-    pub async fn get_mod_by_url(&self, url: &str) -> Result<Option<Mod>, sqlx::Error> {
-        let row = sqlx::query("SELECT * FROM mods WHERE url = ?")
-            .bind(url)
-            .fetch_optional(&self.pool)
+    /// Пустой результат при успехе или ошибку
+    pub async fn delete_profile(&self, id: i64) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM profile_mods WHERE profile_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
             .await?;
-
-        Ok(row.map(|r| Mod {
-            id: r.get(0),
-            site_id: r.get(1),
-            title: r.get(2),
-            url: r.get(3),
-            version: r.get(4),
-            author: r.get(5),
-            description: r.get(6),
-            image_url: r.get(7),
-            changes: r.get(8),
-            created_at: r.get::<String, _>(9).parse().unwrap_or(Utc::now()),
-            updated_at: r.get::<String, _>(10).parse().unwrap_or(Utc::now()),
-        }))
+        sqlx::query("DELETE FROM profiles WHERE id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
     }
 
-    /// Добавить новый мод в базу данных
+    /// Получить моды профиля в порядке загрузки
     /// 
     /// # Параметры
-    /// * `mod_item` - объект мода для добавления
+    /// * `profile_id` - ID профиля
     /// 
     /// # Возвращает
-    /// Созданный мод с присвоенным ID или ошибку
-    pub async fn add_mod(&self, mod_item: &Mod) -> Result<Mod, sqlx::Error> {
-        let now = Utc::now().to_rfc3339();
-        sqlx::query(
-            "INSERT INTO mods (site_id, title, url, version, author, description, image_url, changes, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+    /// Моды профиля или ошибку
+    pub async fn get_profile_mods(&self, profile_id: i64) -> Result<Vec<ProfileMod>, sqlx::Error> {
+        let rows = sqlx::query(
+            "SELECT mod_id, pinned_version, enabled, position FROM profile_mods WHERE profile_id = ? ORDER BY position, id",
         )
-        .bind(mod_item.site_id)
-        .bind(&mod_item.title)
-        .bind(&mod_item.url)
-        .bind(&mod_item.version)
-        .bind(&mod_item.author)
-        .bind(&mod_item.description)
-        .bind(&mod_item.image_url)
-        .bind(&mod_item.changes)
-        .bind(&now)
-        .bind(&now)
-        .execute(&self.pool)
+        .bind(profile_id)
+        .fetch_all(&self.pool)
         .await?;
 
-        let id = sqlx::query("SELECT last_insert_rowid()")
-            .fetch_one(&self.pool)
-            .await?
-            .get(0);
-
-        Ok(Mod {
-            id,
-            ..mod_item.clone()
-        })
+        Ok(rows
+            .iter()
+            .map(|row| ProfileMod {
+                mod_id: row.get(0),
+                pinned_version: row.get(1),
+                enabled: row.get::<i64, _>(2) != 0,
+                position: row.get(3),
+            })
+            .collect())
     }
 
-    /// Обновить существующий мод в базе данных
+    /// Заменить список модов профиля
+    /// 
+    /// Порядок загрузки задается порядком в списке (поле `position` пересчитывается).
     /// 
     /// # Параметры
-    /// * `id` - идентификатор мода для обновления
-    /// * `mod_item` - объект мода с новыми данными
+    /// * `profile_id` - ID профиля
+    /// * `mods` - моды профиля в порядке загрузки
     /// 
     /// # Возвращает
     /// Пустой результат при успехе или ошибку
-    pub async fn update_mod(&self, id: i64, mod_item: &Mod) -> Result<(), sqlx::Error> {
-        let now = Utc::now().to_rfc3339();
-        sqlx::query(
-            "UPDATE mods SET title = ?, version = ?, author = ?, description = ?, image_url = ?, changes = ?, updated_at = ? WHERE id = ?",
-        )
-        .bind(&mod_item.title)
-        .bind(&mod_item.version)
-        .bind(&mod_item.author)
-        .bind(&mod_item.description)
-        .bind(&mod_item.image_url)
-        .bind(&mod_item.changes)
-        .bind(&now)
-        .bind(id)
-        .execute(&self.pool)
-        .await?;
+    pub async fn set_profile_mods(&self, profile_id: i64, mods: &[ProfileMod]) -> Result<(), sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM profile_mods WHERE profile_id = ?")
+            .bind(profile_id)
+            .execute(&mut *tx)
+            .await?;
+        for (position, profile_mod) in mods.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO profile_mods (profile_id, mod_id, pinned_version, enabled, position) VALUES (?, ?, ?, ?, ?)",
+            )
+            .bind(profile_id)
+            .bind(profile_mod.mod_id)
+            .bind(&profile_mod.pinned_version)
+            .bind(profile_mod.enabled)
+            .bind(position as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query("UPDATE profiles SET updated_at = ? WHERE id = ?")
+            .bind(Utc::now().to_rfc3339())
+            .bind(profile_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
         Ok(())
     }
 
+    /// Преобразовать строку таблицы profiles в профиль
+    fn row_to_profile(row: &SqliteRow) -> Profile {
+        Profile {
+            id: row.get(0),
+            name: row.get(1),
+            description: row.get(2),
+            install_dir: row.get(3),
+            created_at: row.get::<String, _>(4).parse().unwrap_or(Utc::now()),
+            updated_at: row.get::<String, _>(5).parse().unwrap_or(Utc::now()),
+        }
+    }
+
     /// Получить список всех уведомлений
     /// 
     /// Возвращает последние 100 уведомлений, отсортированных по дате создания (новые первыми).
@@ -419,6 +1480,8 @@ impl Database {
                 message: row.get(4),
                 read: row.get::<i64, _>(5) != 0,
                 created_at: row.get::<String, _>(6).parse().unwrap_or(Utc::now()),
+                kind: NotificationKind::from_db(&row.get::<String, _>(7)),
+                saved_page_id: row.get(8),
             })
             .collect())
     }
@@ -433,7 +1496,7 @@ impl Database {
     pub async fn add_notification(&self, notification: &Notification) -> Result<(), sqlx::Error> {
         let now = Utc::now().to_rfc3339();
         sqlx::query(
-            "INSERT INTO notifications (mod_id, site_id, title, message, read, created_at) VALUES (?, ?, ?, ?, ?, ?)",
+            "INSERT INTO notifications (mod_id, site_id, title, message, read, created_at, kind, saved_page_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(notification.mod_id)
         .bind(notification.site_id)
@@ -441,6 +1504,8 @@ impl Database {
         .bind(&notification.message)
         .bind(if notification.read { 1 } else { 0 })
         .bind(&now)
+        .bind(notification.kind.as_str())
+        .bind(notification.saved_page_id)
         .execute(&self.pool)
         .await?;
         Ok(())
@@ -472,16 +1537,16 @@ impl Database {
     /// * `version_timestamp` - временная метка версии страницы
     /// 
     /// # Возвращает
-    /// Пустой результат при успехе или ошибку
+    /// ID созданной записи или ошибку
     pub async fn save_page_for_site(
         &self,
         site_id: i64,
         url: &str,
         folder_path: &str,
         version_timestamp: &str,
-    ) -> Result<(), sqlx::Error> {
+    ) -> Result<i64, sqlx::Error> {
         let now = Utc::now().to_rfc3339();
-        sqlx::query(
+        let id = sqlx::query(
             "INSERT INTO saved_pages (site_id, url, folder_path, version_timestamp, created_at, updated_at) VALUES (?, ?, ?, ?, ?, ?)",
         )
         .bind(site_id)
@@ -491,8 +1556,9 @@ impl Database {
         .bind(&now)
         .bind(&now)
         .execute(&self.pool)
-        .await?;
-        Ok(())
+        .await?
+        .last_insert_rowid();
+        Ok(id)
     }
 
     /// Получить последнюю сохраненную страницу для сайта по URL
@@ -572,7 +1638,8 @@ impl Database {
 
     /// Удалить конкретную версию сохраненной страницы
     /// 
-    /// Удаляет запись о версии страницы из базы данных.
+    /// Удаляет запись о версии страницы из базы данных. Ссылки на нее из
+    /// уведомлений и проверок сайта сбрасываются.
     /// 
     /// # Параметры
     /// * `page_id` - ID версии страницы для удаления
@@ -580,6 +1647,14 @@ impl Database {
     /// # Возвращает
     /// Пустой результат при успехе или ошибку
     pub async fn delete_saved_page_version(&self, page_id: i64) -> Result<(), sqlx::Error> {
+        sqlx::query("UPDATE notifications SET saved_page_id = NULL WHERE saved_page_id = ?")
+            .bind(page_id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("UPDATE check_runs SET saved_page_id = NULL WHERE saved_page_id = ?")
+            .bind(page_id)
+            .execute(&self.pool)
+            .await?;
         sqlx::query("DELETE FROM saved_pages WHERE id = ?")
             .bind(page_id)
             .execute(&self.pool)
@@ -588,3 +1663,156 @@ impl Database {
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_mod(site_id: i64, title: &str, author: Option<&str>) -> Mod {
+        Mod {
+            id: 0,
+            site_id,
+            title: title.to_string(),
+            url: format!("https://example.com/{}", title),
+            version: None,
+            author: author.map(str::to_string),
+            description: None,
+            image_url: None,
+            changes: None,
+            download_url: None,
+            files: Vec::new(),
+            followed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn mods_page_is_sorted_and_counted() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).await.unwrap();
+        let site = db.add_site("Site", "https://example.com", &serde_json::json!({})).await.unwrap();
+        let other = db.add_site("Other", "https://other.example.com", &serde_json::json!({})).await.unwrap();
+        db.add_mod(&test_mod(site.id, "beta", Some("Zed"))).await.unwrap();
+        db.add_mod(&test_mod(site.id, "Alpha", None)).await.unwrap();
+        db.add_mod(&test_mod(site.id, "gamma", Some("amy"))).await.unwrap();
+        db.add_mod(&test_mod(other.id, "Delta", Some("Bob"))).await.unwrap();
+
+        let titles = |page: &ModPage| page.mods.iter().map(|m| m.title.clone()).collect::<Vec<_>>();
+
+        let page = db.get_mods_page(None, ModSort::Title, SortDirection::Asc, 2, 1).await.unwrap();
+        assert_eq!((page.total, titles(&page)), (4, vec!["beta".to_string(), "Delta".to_string()]));
+
+        let page = db.get_mods_page(Some(site.id), ModSort::Author, SortDirection::Desc, 10, 0).await.unwrap();
+        assert_eq!((page.total, titles(&page)), (3, vec!["beta".to_string(), "gamma".to_string(), "Alpha".to_string()]));
+
+        let page = db.get_mods_page(None, ModSort::Updated, SortDirection::Desc, 1000, -5).await.unwrap();
+        assert_eq!((page.limit, page.offset, page.mods.len()), (ModPage::MAX_PAGE_SIZE, 0, 4));
+        assert_eq!(page.mods[0].title, "Delta");
+    }
+
+    #[tokio::test]
+    async fn update_keeps_scraped_updated_at() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).await.unwrap();
+        let site = db.add_site("Site", "https://example.com", &serde_json::json!({})).await.unwrap();
+        let added = db.add_mod(&test_mod(site.id, "trees", None)).await.unwrap();
+
+        let published = "2024-03-01T12:00:00Z".parse::<chrono::DateTime<Utc>>().unwrap();
+        let mut changed = test_mod(site.id, "trees", None);
+        changed.version = Some("1.1".to_string());
+        changed.updated_at = published;
+        db.update_mod(added.id, &changed).await.unwrap();
+        assert_eq!(db.get_mod(added.id).await.unwrap().updated_at, published);
+    }
+
+    #[tokio::test]
+    async fn site_is_deleted_with_its_mods() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).await.unwrap();
+        let site = db.add_site("Site", "https://example.com", &serde_json::json!({})).await.unwrap();
+        let other = db.add_site("Other", "https://other.example.com", &serde_json::json!({})).await.unwrap();
+        let trees = db.add_mod(&test_mod(site.id, "trees", None)).await.unwrap();
+        let rocks = db.add_mod(&test_mod(other.id, "rocks", None)).await.unwrap();
+
+        let download = db
+            .add_download(&Download {
+                id: 0,
+                mod_id: trees.id,
+                mod_version_id: None,
+                version: None,
+                url: "https://example.com/trees.zip".to_string(),
+                file_name: "trees.zip".to_string(),
+                path: "downloads/trees.zip".to_string(),
+                status: DownloadStatus::Completed,
+                total_bytes: None,
+                downloaded_bytes: 0,
+                content_type: None,
+                sha256: None,
+                error: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        let installation = Installation {
+            id: 0,
+            mod_id: trees.id,
+            download_id: Some(download.id),
+            version: None,
+            install_dir: "/games/mods".to_string(),
+            installed_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        db.save_installation(&installation, &[("trees/tree.dat".to_string(), 10)]).await.unwrap();
+        let profile = db.add_profile("Forest", None, None).await.unwrap();
+        let profile_mod = |mod_id| ProfileMod { mod_id, pinned_version: None, enabled: true, position: 0 };
+        db.set_profile_mods(profile.id, &[profile_mod(trees.id), ProfileMod { position: 1, ..profile_mod(rocks.id) }])
+            .await
+            .unwrap();
+        db.add_notification(&Notification {
+            id: 0,
+            kind: NotificationKind::ModUpdate,
+            mod_id: Some(trees.id),
+            site_id: site.id,
+            title: "Trees".to_string(),
+            message: "1.1".to_string(),
+            read: false,
+            created_at: Utc::now(),
+            saved_page_id: None,
+        })
+        .await
+        .unwrap();
+        let page_id = db.save_page_for_site(site.id, "https://example.com", "pages/1", "1").await.unwrap();
+        let mut schedule = db.get_site_schedule(site.id).await.unwrap();
+        schedule.failure_count = 2;
+        db.save_site_schedule(&schedule).await.unwrap();
+        db.add_check_run(&CheckRun {
+            id: 0,
+            site_id: site.id,
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            http_status: Some(200),
+            mods_found: 1,
+            new_mods: 1,
+            updated_mods: 0,
+            error: None,
+            null_fraction: None,
+            drift: None,
+            saved_page_id: Some(page_id),
+        })
+        .await
+        .unwrap();
+
+        db.delete_site(site.id).await.unwrap();
+        assert!(db.get_site(site.id).await.is_err());
+        assert!(db.get_mod(trees.id).await.is_err());
+        let profile_mods = db.get_profile_mods(profile.id).await.unwrap();
+        assert_eq!(profile_mods.iter().map(|m| m.mod_id).collect::<Vec<_>>(), vec![rocks.id]);
+        assert_eq!(db.get_mods(None).await.unwrap().len(), 1);
+        assert_eq!(db.get_mod_history(rocks.id).await.unwrap().len(), 1);
+        assert!(db.get_check_runs(None, 10).await.unwrap().is_empty());
+        let schedules: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM site_schedules").fetch_one(&db.pool).await.unwrap();
+        assert_eq!(schedules, 0);
+    }
+}
@@ -0,0 +1,242 @@
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use log::{info, warn};
+
+/// Имя файла базы данных
+pub const DB_FILE_NAME: &str = "mod_aggregator.db";
+
+/// Переменная окружения с путем к базе данных
+pub const DB_PATH_ENV: &str = "MOD_AGGREGATOR_DB";
+
+/// Флаг командной строки с путем к базе данных (`--db-path <путь>` или `--db-path=<путь>`)
+pub const DB_PATH_FLAG: &str = "--db-path";
+
+/// Идентификатор приложения (должен совпадать с `identifier` в tauri.conf.json)
+pub const APP_IDENTIFIER: &str = "com.modaggregator.app";
+
+/// Суффиксы файлов SQLite, которые переносятся вместе с базой (WAL режим)
+const SQLITE_SIDE_FILES: [&str; 2] = ["-wal", "-shm"];
+
+/// Откуда взят путь к базе данных
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbPathSource {
+    /// Флаг командной строки `--db-path`
+    CliFlag,
+    /// Переменная окружения `MOD_AGGREGATOR_DB`
+    EnvVar,
+    /// Директория данных приложения (по умолчанию)
+    AppData,
+}
+
+/// Расположение файла базы данных
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DbLocation {
+    /// Путь к файлу базы данных
+    pub path: PathBuf,
+    /// Источник пути
+    pub source: DbPathSource,
+}
+
+/// Найти путь к базе данных в аргументах командной строки
+///
+/// # Параметры
+/// * `args` - аргументы командной строки (первый элемент - имя программы)
+///
+/// # Возвращает
+/// Путь из флага `--db-path` или None, если флаг не задан
+pub fn path_from_args<I>(args: I) -> Option<PathBuf>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        let arg = arg.to_string_lossy().into_owned();
+        if arg == DB_PATH_FLAG {
+            return args.next().map(PathBuf::from);
+        }
+        if let Some(value) = arg.strip_prefix(DB_PATH_FLAG).and_then(|rest| rest.strip_prefix('=')) {
+            return Some(PathBuf::from(value));
+        }
+    }
+    None
+}
+
+/// Определить расположение базы данных
+///
+/// Приоритет: флаг командной строки, затем переменная окружения, затем
+/// директория данных приложения (там же, где `saved_pages`).
+///
+/// # Параметры
+/// * `app_data_dir` - директория данных приложения
+/// * `cli_path` - путь из флага `--db-path`
+/// * `env_path` - значение переменной `MOD_AGGREGATOR_DB`
+///
+/// # Возвращает
+/// Расположение базы данных
+pub fn resolve(app_data_dir: &Path, cli_path: Option<PathBuf>, env_path: Option<OsString>) -> DbLocation {
+    if let Some(path) = cli_path.filter(|p| !p.as_os_str().is_empty()) {
+        return DbLocation { path, source: DbPathSource::CliFlag };
+    }
+    if let Some(path) = env_path.filter(|p| !p.is_empty()) {
+        return DbLocation { path: PathBuf::from(path), source: DbPathSource::EnvVar };
+    }
+    DbLocation {
+        path: app_data_dir.join(DB_FILE_NAME),
+        source: DbPathSource::AppData,
+    }
+}
+
+/// Получить директорию данных приложения без Tauri
+///
+/// Совпадает с `app_data_dir()` Tauri (`<data_dir>/<identifier>`), поэтому
+/// CLI по умолчанию работает с той же базой, что и десктопное приложение.
+///
+/// # Возвращает
+/// Путь к директории данных или None, если системная директория неизвестна
+pub fn default_app_data_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join(APP_IDENTIFIER))
+}
+
+/// Определить расположение базы данных по аргументам и окружению процесса
+///
+/// # Параметры
+/// * `app_data_dir` - директория данных приложения
+///
+/// # Возвращает
+/// Расположение базы данных
+pub fn resolve_from_env(app_data_dir: &Path) -> DbLocation {
+    resolve(
+        app_data_dir,
+        path_from_args(std::env::args_os()),
+        std::env::var_os(DB_PATH_ENV),
+    )
+}
+
+/// Перенести базу данных из старого расположения
+///
+/// Раньше база создавалась в текущей директории процесса. Если в новом
+/// расположении базы еще нет, а в старом она есть, файл базы (вместе с
+/// `-wal`/`-shm`) переносится. Существующая база в новом расположении
+/// никогда не перезаписывается.
+///
+/// # Параметры
+/// * `legacy_dir` - старая директория базы данных
+/// * `target` - новый путь к файлу базы данных
+///
+/// # Возвращает
+/// true, если база была перенесена, или ошибку ввода-вывода
+pub fn migrate_legacy(legacy_dir: &Path, target: &Path) -> io::Result<bool> {
+    let legacy = legacy_dir.join(DB_FILE_NAME);
+    // Если пути совпадают, target уже существует и переносить нечего
+    if target.exists() || !legacy.is_file() {
+        return Ok(false);
+    }
+
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    // Сначала копируем все файлы, затем удаляем старые: при ошибке копирования
+    // старая база остается нетронутой, а частичная копия удаляется
+    let files: Vec<(PathBuf, PathBuf)> = std::iter::once((legacy.clone(), target.to_path_buf()))
+        .chain(SQLITE_SIDE_FILES.iter().map(|suffix| (with_suffix(&legacy, suffix), with_suffix(target, suffix))))
+        .filter(|(from, _)| from.is_file())
+        .collect();
+
+    for (index, (from, to)) in files.iter().enumerate() {
+        if let Err(e) = fs::copy(from, to) {
+            for (_, copied) in &files[..=index] {
+                let _ = fs::remove_file(copied);
+            }
+            return Err(e);
+        }
+    }
+
+    for (from, _) in &files {
+        if let Err(e) = fs::remove_file(from) {
+            warn!("Failed to remove legacy database file {:?}: {}", from, e);
+        }
+    }
+
+    info!("Migrated legacy database from {:?} to {:?}", legacy, target);
+    Ok(true)
+}
+
+/// Добавить суффикс к имени файла (`db` -> `db-wal`)
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<OsString> {
+        list.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn cli_flag_is_parsed_in_both_forms() {
+        assert_eq!(
+            path_from_args(args(&["app", "--db-path", "/tmp/a.db"])),
+            Some(PathBuf::from("/tmp/a.db"))
+        );
+        assert_eq!(
+            path_from_args(args(&["app", "--verbose", "--db-path=/tmp/b.db"])),
+            Some(PathBuf::from("/tmp/b.db"))
+        );
+        assert_eq!(path_from_args(args(&["app", "--db-path"])), None);
+        assert_eq!(path_from_args(args(&["--db-path=/tmp/program-name.db"])), None);
+    }
+
+    #[test]
+    fn resolve_prefers_cli_then_env_then_app_data() {
+        let app_data = Path::new("/data/app");
+
+        let location = resolve(app_data, Some("/cli.db".into()), Some("/env.db".into()));
+        assert_eq!(location, DbLocation { path: "/cli.db".into(), source: DbPathSource::CliFlag });
+
+        let location = resolve(app_data, None, Some("/env.db".into()));
+        assert_eq!(location, DbLocation { path: "/env.db".into(), source: DbPathSource::EnvVar });
+
+        let location = resolve(app_data, None, Some(OsString::new()));
+        assert_eq!(location, DbLocation { path: app_data.join(DB_FILE_NAME), source: DbPathSource::AppData });
+    }
+
+    #[test]
+    fn legacy_database_is_moved_with_wal_files() {
+        let legacy_dir = tempfile::tempdir().unwrap();
+        let app_data = tempfile::tempdir().unwrap();
+        let legacy = legacy_dir.path().join(DB_FILE_NAME);
+        fs::write(&legacy, b"db").unwrap();
+        fs::write(with_suffix(&legacy, "-wal"), b"wal").unwrap();
+
+        let target = app_data.path().join("nested").join(DB_FILE_NAME);
+        assert!(migrate_legacy(legacy_dir.path(), &target).unwrap());
+
+        assert_eq!(fs::read(&target).unwrap(), b"db");
+        assert_eq!(fs::read(with_suffix(&target, "-wal")).unwrap(), b"wal");
+        assert!(!legacy.exists());
+        assert!(!with_suffix(&legacy, "-wal").exists());
+    }
+
+    #[test]
+    fn existing_database_is_never_overwritten() {
+        let legacy_dir = tempfile::tempdir().unwrap();
+        let app_data = tempfile::tempdir().unwrap();
+        fs::write(legacy_dir.path().join(DB_FILE_NAME), b"old").unwrap();
+        let target = app_data.path().join(DB_FILE_NAME);
+        fs::write(&target, b"new").unwrap();
+
+        assert!(!migrate_legacy(legacy_dir.path(), &target).unwrap());
+        assert_eq!(fs::read(&target).unwrap(), b"new");
+        assert!(legacy_dir.path().join(DB_FILE_NAME).exists());
+
+        // Старая и новая директории совпадают - переносить нечего
+        assert!(!migrate_legacy(app_data.path(), &target).unwrap());
+    }
+}
@@ -0,0 +1,335 @@
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use scraper::{Html, Selector};
+use tokio::sync::{Mutex, Semaphore};
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+use log::{debug, warn};
+use crate::models::Mod;
+use crate::parser::resolve_url;
+
+/// Количество одновременных запросов к страницам модов по умолчанию
+fn default_concurrency() -> usize {
+    4
+}
+
+/// Пауза между запросами к одному сайту по умолчанию (мс)
+fn default_delay_ms() -> u64 {
+    500
+}
+
+/// Конфигурация второго этапа парсинга - обхода страниц модов
+///
+/// Задается ключом `detail` в конфигурации парсера сайта. Все селекторы
+/// применяются к странице мода (`Mod::url`) и необязательны.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DetailConfig {
+    /// Селектор описания мода
+    pub description_selector: Option<String>,
+    /// Селектор списка изменений (changelog)
+    pub changes_selector: Option<String>,
+    /// Селектор версии
+    pub version_selector: Option<String>,
+    /// Селектор автора
+    pub author_selector: Option<String>,
+    /// Селектор ссылки на скачивание (берется атрибут href)
+    pub download_selector: Option<String>,
+    /// Максимум одновременных запросов
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize,
+    /// Минимальный интервал между запросами к сайту (мс)
+    #[serde(default = "default_delay_ms")]
+    pub delay_ms: u64,
+}
+
+/// Разобранные селекторы страницы мода
+struct DetailSelectors {
+    description: Option<Selector>,
+    changes: Option<Selector>,
+    version: Option<Selector>,
+    author: Option<Selector>,
+    download: Option<Selector>,
+}
+
+impl DetailConfig {
+    /// Прочитать настройки обхода страниц модов из конфигурации парсера
+    ///
+    /// # Параметры
+    /// * `config` - конфигурация парсера сайта
+    ///
+    /// # Возвращает
+    /// Настройки, None если ключ `detail` не задан, или ошибку разбора/селектора
+    pub fn from_config(config: &serde_json::Value) -> Result<Option<Self>, String> {
+        match config.get("detail") {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(value) => {
+                let detail: DetailConfig = serde_json::from_value(value.clone())
+                    .map_err(|e| format!("Invalid detail config: {}", e))?;
+                // Проверяем селекторы заранее, чтобы ошибка была видна сразу, а не на каждой странице
+                detail.selectors()?;
+                Ok(Some(detail))
+            }
+        }
+    }
+
+    /// Разобрать CSS селекторы конфигурации
+    ///
+    /// # Возвращает
+    /// Набор селекторов или ошибку с именем некорректного поля
+    fn selectors(&self) -> Result<DetailSelectors, String> {
+        let parse = |name: &str, value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| Selector::parse(s).map_err(|e| format!("Invalid CSS selector in detail.{}: {}", name, e)))
+                .transpose()
+        };
+        Ok(DetailSelectors {
+            description: parse("description_selector", &self.description_selector)?,
+            changes: parse("changes_selector", &self.changes_selector)?,
+            version: parse("version_selector", &self.version_selector)?,
+            author: parse("author_selector", &self.author_selector)?,
+            download: parse("download_selector", &self.download_selector)?,
+        })
+    }
+}
+
+/// Обходчик страниц модов
+///
+/// Загружает страницу каждого мода с ограничением параллельности и
+/// минимальным интервалом между запросами к сайту, затем дополняет мод
+/// данными со страницы. Если страница не загрузилась, мод остается с
+/// данными из списка.
+pub struct DetailCrawler {
+    client: reqwest::Client,
+    selectors: Arc<DetailSelectors>,
+    concurrency: usize,
+    delay: Duration,
+}
+
+impl DetailCrawler {
+    /// Создать обходчик по конфигурации
+    ///
+    /// # Параметры
+    /// * `client` - HTTP клиент для загрузки страниц
+    /// * `config` - настройки обхода страниц модов
+    ///
+    /// # Возвращает
+    /// Обходчик или ошибку селектора
+    pub fn new(client: reqwest::Client, config: &DetailConfig) -> Result<Self, String> {
+        Ok(DetailCrawler {
+            client,
+            selectors: Arc::new(config.selectors()?),
+            concurrency: config.concurrency.max(1),
+            delay: Duration::from_millis(config.delay_ms),
+        })
+    }
+
+    /// Дополнить моды данными со страниц модов
+    ///
+    /// # Параметры
+    /// * `mods` - моды, найденные на страницах списка
+    /// * `base_url` - базовый URL для относительных ссылок
+    ///
+    /// # Возвращает
+    /// Моды в исходном порядке, дополненные данными со страниц
+    pub async fn enrich(&self, mods: Vec<Mod>, base_url: &str) -> Vec<Mod> {
+        let semaphore = Arc::new(Semaphore::new(self.concurrency));
+        // Время, раньше которого нельзя отправить следующий запрос к сайту
+        let next_slot = Arc::new(Mutex::new(Instant::now()));
+        let mut tasks = JoinSet::new();
+
+        for (index, mod_item) in mods.iter().cloned().enumerate() {
+            let client = self.client.clone();
+            let selectors = self.selectors.clone();
+            let semaphore = semaphore.clone();
+            let next_slot = next_slot.clone();
+            let delay = self.delay;
+            let base_url = base_url.to_string();
+
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await.ok();
+                let start_at = {
+                    let mut slot = next_slot.lock().await;
+                    let start_at = (*slot).max(Instant::now());
+                    *slot = start_at + delay;
+                    start_at
+                };
+                tokio::time::sleep_until(start_at).await;
+
+                let enriched = match Self::fetch(&client, &mod_item.url).await {
+                    Ok(html) => Self::apply(&selectors, mod_item, &html, &base_url),
+                    Err(e) => {
+                        warn!("Failed to load detail page {}: {}", mod_item.url, e);
+                        mod_item
+                    }
+                };
+                (index, enriched)
+            });
+        }
+
+        let mut result = mods;
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok((index, enriched)) => result[index] = enriched,
+                Err(e) => warn!("Detail page task failed: {}", e),
+            }
+        }
+        result
+    }
+
+    /// Загрузить страницу мода
+    ///
+    /// # Параметры
+    /// * `client` - HTTP клиент
+    /// * `url` - URL страницы мода
+    ///
+    /// # Возвращает
+    /// HTML страницы или ошибку (включая HTTP статусы 4xx/5xx)
+    async fn fetch(client: &reqwest::Client, url: &str) -> Result<String, reqwest::Error> {
+        debug!("Loading detail page: {}", url);
+        client.get(url).send().await?.error_for_status()?.text().await
+    }
+
+    /// Применить селекторы к странице мода
+    ///
+    /// Значения со страницы мода имеют приоритет; поля, которые не удалось
+    /// найти, остаются из списка.
+    ///
+    /// # Параметры
+    /// * `selectors` - селекторы страницы мода
+    /// * `mod_item` - мод с данными из списка
+    /// * `html` - HTML страницы мода
+    /// * `base_url` - базовый URL для относительных ссылок
+    ///
+    /// # Возвращает
+    /// Дополненный мод
+    fn apply(selectors: &DetailSelectors, mod_item: Mod, html: &str, base_url: &str) -> Mod {
+        let document = Html::parse_document(html);
+        let text = |selector: &Option<Selector>| {
+            selector
+                .as_ref()
+                .and_then(|sel| document.select(sel).next())
+                .map(|e| e.text().collect::<Vec<_>>().join(" ").split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|s| !s.is_empty())
+        };
+
+        let download_url = selectors.download
+            .as_ref()
+            .and_then(|sel| document.select(sel).next())
+            .and_then(|e| e.value().attr("href"))
+            .map(|href| {
+                // Относительные ссылки разрешаем относительно страницы мода
+                url::Url::parse(&mod_item.url)
+                    .and_then(|page| page.join(href))
+                    .map(|u| u.to_string())
+                    .unwrap_or_else(|_| resolve_url(href, base_url))
+            });
+
+        Mod {
+            description: text(&selectors.description).or(mod_item.description),
+            changes: text(&selectors.changes).or(mod_item.changes),
+            version: text(&selectors.version).or(mod_item.version),
+            author: text(&selectors.author).or(mod_item.author),
+            download_url: download_url.or(mod_item.download_url),
+            ..mod_item
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn list_mod(url: String) -> Mod {
+        Mod {
+            id: 0,
+            site_id: 1,
+            title: "From list".to_string(),
+            url,
+            version: Some("1.0".to_string()),
+            author: None,
+            description: Some("List description".to_string()),
+            image_url: None,
+            changes: None,
+            download_url: None,
+            files: Vec::new(),
+            followed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn crawler(concurrency: usize, delay_ms: u64) -> DetailCrawler {
+        let config = DetailConfig {
+            description_selector: Some(".description".to_string()),
+            changes_selector: Some(".changes".to_string()),
+            version_selector: Some(".version".to_string()),
+            author_selector: Some(".author".to_string()),
+            download_selector: Some("a.download".to_string()),
+            concurrency,
+            delay_ms,
+        };
+        DetailCrawler::new(reqwest::Client::new(), &config).unwrap()
+    }
+
+    #[tokio::test]
+    async fn failed_detail_page_keeps_list_data() {
+        let server = MockServer::start(|request| match request.path.as_str() {
+            "/mods/broken" => MockResponse::new(500, "error"),
+            path => MockResponse::ok(format!(
+                "<p class=\"description\">Full {}</p><ul class=\"changes\"><li>Fixed</li>\n<li>Added</li></ul>\
+                 <span class=\"version\">2.0</span><span class=\"author\">alice</span><a class=\"download\" href=\"files/mod.zip\">Get</a>",
+                path
+            )),
+        })
+        .await;
+        let mods = vec![
+            list_mod(server.url("/mods/trees")),
+            list_mod(server.url("/mods/broken")),
+            list_mod(server.url("/mods/rocks")),
+        ];
+
+        let enriched = crawler(2, 0).enrich(mods.clone(), "https://example.com").await;
+
+        assert_eq!(enriched.len(), 3);
+        assert_eq!(enriched[1].url, mods[1].url);
+        assert_eq!(
+            (enriched[1].description.as_deref(), enriched[1].version.as_deref(), enriched[1].download_url.as_deref()),
+            (Some("List description"), Some("1.0"), None)
+        );
+        for (enriched, name) in [(&enriched[0], "trees"), (&enriched[2], "rocks")] {
+            assert_eq!(enriched.description.as_deref(), Some(format!("Full /mods/{}", name).as_str()));
+            assert_eq!(enriched.changes.as_deref(), Some("Fixed Added"));
+            assert_eq!((enriched.version.as_deref(), enriched.author.as_deref()), (Some("2.0"), Some("alice")));
+            // Ссылка разрешается относительно страницы мода, а не base_url
+            assert_eq!(enriched.download_url, Some(server.url("/mods/files/mod.zip")));
+            assert_eq!(enriched.title, "From list");
+        }
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn requests_are_limited_and_spaced() {
+        let server = MockServer::respond_with(MockResponse::ok("<p>mod</p>").delay(Duration::from_millis(100))).await;
+        let mods: Vec<Mod> = (0..6).map(|i| list_mod(server.url(&format!("/mods/{}", i)))).collect();
+        let enriched = crawler(2, 0).enrich(mods, "").await;
+        assert_eq!(enriched.len(), 6);
+        assert_eq!(server.max_in_flight(), 2);
+
+        // Интервал между запросами соблюдается и при свободных местах
+        let server = MockServer::respond_with(MockResponse::ok("<p>mod</p>")).await;
+        let mods: Vec<Mod> = (0..4).map(|i| list_mod(server.url(&format!("/mods/{}", i)))).collect();
+        crawler(4, 80).enrich(mods, "").await;
+        let mut received: Vec<_> = server.requests().iter().map(|request| request.received_at).collect();
+        received.sort();
+        assert_eq!(received.len(), 4);
+        for pair in received.windows(2) {
+            assert!(pair[1] - pair[0] >= Duration::from_millis(60), "{:?}", pair[1] - pair[0]);
+        }
+    }
+}
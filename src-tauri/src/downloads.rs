@@ -0,0 +1,670 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use chrono::Utc;
+use reqwest::header::{CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use reqwest::StatusCode;
+use sha2::{Digest, Sha256};
+use tokio::fs;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::{broadcast, watch};
+use tokio::time::Instant;
+use log::{debug, info, warn};
+use crate::database::Database;
+use crate::models::{Download, DownloadStatus, Mod};
+use crate::state::USER_AGENT;
+
+/// Имя события Tauri с состоянием загрузки (payload - `Download`)
+pub const DOWNLOAD_EVENT: &str = "download-progress";
+
+/// Поддиректория данных приложения для загруженных файлов
+pub const DOWNLOADS_DIR: &str = "downloads";
+
+/// Минимальный интервал между событиями прогресса (и записями в базу)
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Максимальное время ожидания очередной части файла
+const STALL_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Расширение частично загруженного файла
+const PART_EXTENSION: &str = "part";
+
+/// Ошибки менеджера загрузок
+#[derive(Debug, thiserror::Error)]
+pub enum DownloadError {
+    #[error("Мод {0} не найден")]
+    ModNotFound(i64),
+    #[error("Загрузка {0} не найдена")]
+    NotFound(i64),
+    #[error("У мода нет ссылки на скачивание")]
+    NoDownloadUrl,
+    #[error("Ссылка {0} не относится к файлам мода")]
+    UnknownUrl(String),
+    #[error("Действие недоступно для загрузки в статусе {0}")]
+    InvalidState(&'static str),
+    #[error("Ошибка базы данных: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Ошибка файла: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Ошибка HTTP: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Сервер вернул статус {0}")]
+    Status(StatusCode),
+    #[error("Сервер не отвечает дольше {} секунд", STALL_TIMEOUT.as_secs())]
+    Stalled,
+}
+
+/// Команда для выполняющейся загрузки
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Control {
+    Run,
+    Pause,
+    Cancel,
+}
+
+/// Чем закончилась передача файла
+enum Outcome {
+    Completed,
+    Paused,
+    Cancelled,
+}
+
+/// Менеджер загрузок файлов модов
+///
+/// Файлы сохраняются в `<данные приложения>/downloads/<mod_id>/<версия>/` через
+/// временный `.part` файл; продолжение загрузки использует HTTP Range.
+/// Состояние хранится в таблице `downloads`, изменения рассылаются подписчикам
+/// (`subscribe`), а десктопное приложение пересылает их событием `DOWNLOAD_EVENT`.
+/// Клонирование дешевое: все клоны работают с одним набором загрузок.
+#[derive(Clone)]
+pub struct DownloadManager {
+    db: Database,
+    client: reqwest::Client,
+    dir: PathBuf,
+    /// Выполняющиеся загрузки (ID -> канал команд)
+    active: Arc<Mutex<HashMap<i64, watch::Sender<Control>>>>,
+    events: broadcast::Sender<Download>,
+}
+
+impl DownloadManager {
+    /// Создать менеджер загрузок
+    ///
+    /// # Параметры
+    /// * `db` - база данных приложения
+    /// * `data_dir` - директория данных приложения (файлы попадают в `downloads/`)
+    ///
+    /// # Возвращает
+    /// Менеджер загрузок или ошибку создания HTTP клиента
+    pub fn new(db: Database, data_dir: &Path) -> Result<Self, reqwest::Error> {
+        // У общего клиента таймаут на весь запрос, а большие файлы загружаются дольше
+        let client = reqwest::Client::builder()
+            .user_agent(USER_AGENT)
+            .connect_timeout(Duration::from_secs(30))
+            .build()?;
+        let (events, _) = broadcast::channel(64);
+
+        Ok(DownloadManager {
+            db,
+            client,
+            dir: data_dir.join(DOWNLOADS_DIR),
+            active: Arc::new(Mutex::new(HashMap::new())),
+            events,
+        })
+    }
+
+    /// Подписаться на изменения загрузок
+    pub fn subscribe(&self) -> broadcast::Receiver<Download> {
+        self.events.subscribe()
+    }
+
+    /// Перевести в паузу загрузки, прерванные закрытием приложения
+    ///
+    /// # Возвращает
+    /// Количество таких загрузок или ошибку
+    pub async fn recover_interrupted(&self) -> Result<u64, DownloadError> {
+        let count = self.db.pause_interrupted_downloads().await?;
+        if count > 0 {
+            info!("Paused {} interrupted downloads", count);
+        }
+        Ok(count)
+    }
+
+    /// Начать загрузку файла мода
+    ///
+    /// Если такой файл этой версии уже загружается или загрузка приостановлена,
+    /// она продолжается вместо создания новой.
+    ///
+    /// # Параметры
+    /// * `mod_id` - ID мода
+    /// * `url` - ссылка на файл из `download_url` или `files` мода (None = `download_url`
+    ///   или первый файл)
+    ///
+    /// # Возвращает
+    /// Загрузку или ошибку
+    pub async fn start(&self, mod_id: i64, url: Option<String>) -> Result<Download, DownloadError> {
+        let mod_item = self.db.get_mod(mod_id).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => DownloadError::ModNotFound(mod_id),
+            e => DownloadError::Database(e),
+        })?;
+        let (url, file_name) = select_file(&mod_item, url)?;
+
+        let existing = self
+            .db
+            .get_downloads(Some(mod_id))
+            .await?
+            .into_iter()
+            .find(|d| d.url == url && d.version == mod_item.version && !d.status.is_finished());
+        if let Some(download) = existing {
+            return match download.status {
+                DownloadStatus::Queued | DownloadStatus::Downloading if self.is_active(download.id) => Ok(download),
+                _ => self.resume(download.id).await,
+            };
+        }
+
+        let mod_version_id = self.db.get_mod_history(mod_id).await?.first().map(|v| v.id);
+        let version_dir = mod_item.version.as_deref().map(sanitize_file_name).unwrap_or_else(|| "unversioned".to_string());
+        let path = self.dir.join(mod_id.to_string()).join(version_dir).join(&file_name);
+
+        let download = self
+            .db
+            .add_download(&Download {
+                id: 0,
+                mod_id,
+                mod_version_id,
+                version: mod_item.version.clone(),
+                url,
+                file_name,
+                path: path.to_string_lossy().into_owned(),
+                status: DownloadStatus::Queued,
+                total_bytes: None,
+                downloaded_bytes: 0,
+                content_type: None,
+                sha256: None,
+                error: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await?;
+        info!("Starting download {} of {} for mod {}", download.id, download.url, mod_id);
+        self.spawn(download.clone());
+        Ok(download)
+    }
+
+    /// Приостановить загрузку
+    ///
+    /// # Параметры
+    /// * `id` - ID загрузки
+    ///
+    /// # Возвращает
+    /// Пустой результат или ошибку, если загрузка не выполняется
+    pub fn pause(&self, id: i64) -> Result<(), DownloadError> {
+        self.send(id, Control::Pause)
+    }
+
+    /// Продолжить приостановленную или завершившуюся ошибкой загрузку
+    ///
+    /// # Параметры
+    /// * `id` - ID загрузки
+    ///
+    /// # Возвращает
+    /// Загрузку или ошибку
+    pub async fn resume(&self, id: i64) -> Result<Download, DownloadError> {
+        let mut download = self.get(id).await?;
+        if self.is_active(id) {
+            return Ok(download);
+        }
+        match download.status {
+            DownloadStatus::Paused | DownloadStatus::Failed | DownloadStatus::Queued | DownloadStatus::Downloading => {}
+            status => return Err(DownloadError::InvalidState(status.as_str())),
+        }
+
+        download.status = DownloadStatus::Queued;
+        download.error = None;
+        self.save(&mut download).await;
+        self.spawn(download.clone());
+        Ok(download)
+    }
+
+    /// Отменить загрузку и удалить частично загруженный файл
+    ///
+    /// # Параметры
+    /// * `id` - ID загрузки
+    ///
+    /// # Возвращает
+    /// Пустой результат или ошибку
+    pub async fn cancel(&self, id: i64) -> Result<(), DownloadError> {
+        if self.send(id, Control::Cancel).is_ok() {
+            return Ok(());
+        }
+
+        let mut download = self.get(id).await?;
+        if download.status.is_finished() {
+            return Err(DownloadError::InvalidState(download.status.as_str()));
+        }
+        remove_if_exists(&part_path(Path::new(&download.path))).await;
+        download.status = DownloadStatus::Cancelled;
+        self.save(&mut download).await;
+        Ok(())
+    }
+
+    /// Получить загрузку по ID
+    async fn get(&self, id: i64) -> Result<Download, DownloadError> {
+        self.db.get_download(id).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => DownloadError::NotFound(id),
+            e => DownloadError::Database(e),
+        })
+    }
+
+    /// Проверить, выполняется ли загрузка сейчас
+    fn is_active(&self, id: i64) -> bool {
+        self.active.lock().unwrap().contains_key(&id)
+    }
+
+    /// Отправить команду выполняющейся загрузке
+    fn send(&self, id: i64, control: Control) -> Result<(), DownloadError> {
+        match self.active.lock().unwrap().get(&id) {
+            Some(sender) => {
+                sender.send_replace(control);
+                Ok(())
+            }
+            None => Err(DownloadError::InvalidState("not_running")),
+        }
+    }
+
+    /// Запустить передачу файла в фоновой задаче
+    fn spawn(&self, download: Download) {
+        let (sender, receiver) = watch::channel(Control::Run);
+        self.active.lock().unwrap().insert(download.id, sender);
+        let manager = self.clone();
+        tokio::spawn(async move { manager.run(download, receiver).await });
+    }
+
+    /// Выполнить загрузку и сохранить итоговое состояние
+    async fn run(self, mut download: Download, mut control: watch::Receiver<Control>) {
+        let id = download.id;
+        download.status = DownloadStatus::Downloading;
+        self.save(&mut download).await;
+
+        match self.transfer(&mut download, &mut control).await {
+            Ok(Outcome::Completed) => {
+                info!("Download {} completed ({} bytes)", id, download.downloaded_bytes);
+                download.status = DownloadStatus::Completed;
+            }
+            Ok(Outcome::Paused) => {
+                debug!("Download {} paused at {} bytes", id, download.downloaded_bytes);
+                download.status = DownloadStatus::Paused;
+            }
+            Ok(Outcome::Cancelled) => {
+                remove_if_exists(&part_path(Path::new(&download.path))).await;
+                download.status = DownloadStatus::Cancelled;
+                download.downloaded_bytes = 0;
+            }
+            Err(e) => {
+                warn!("Download {} failed: {}", id, e);
+                download.status = DownloadStatus::Failed;
+                download.error = Some(e.to_string());
+            }
+        }
+
+        self.active.lock().unwrap().remove(&id);
+        self.save(&mut download).await;
+    }
+
+    /// Передать файл с сервера в `.part` файл и проверить его
+    ///
+    /// Если `.part` файл уже есть, запрашивается только оставшаяся часть
+    /// (`Range: bytes=<размер>-`). Сервер без поддержки Range отдает файл
+    /// целиком, и загрузка начинается заново.
+    async fn transfer(&self, download: &mut Download, control: &mut watch::Receiver<Control>) -> Result<Outcome, DownloadError> {
+        let path = PathBuf::from(&download.path);
+        let part = part_path(&path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+
+        let mut offset = fs::metadata(&part).await.map(|m| m.len()).unwrap_or(0);
+        let mut response = self.request(&download.url, offset).await?;
+        if offset > 0 && response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+            // Файл на сервере изменился или уже загружен целиком - начинаем заново
+            debug!("Range not satisfiable for download {}, restarting", download.id);
+            offset = 0;
+            response = self.request(&download.url, 0).await?;
+        }
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(DownloadError::Status(status));
+        }
+        if status != StatusCode::PARTIAL_CONTENT {
+            offset = 0;
+        }
+
+        download.content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.split(';').next().unwrap_or(v).trim().to_string())
+            .or(download.content_type.take());
+        download.total_bytes = match status {
+            StatusCode::PARTIAL_CONTENT => response
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|v| v.to_str().ok())
+                .and_then(content_range_total)
+                .or_else(|| response.content_length().map(|len| len + offset)),
+            _ => response.content_length(),
+        }
+        .and_then(|total| i64::try_from(total).ok());
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(offset > 0)
+            .truncate(offset == 0)
+            .open(&part)
+            .await?;
+        download.downloaded_bytes = offset as i64;
+        let mut last_progress = Instant::now();
+
+        loop {
+            let chunk = tokio::select! {
+                biased;
+                Ok(()) = control.changed() => {
+                    let command = *control.borrow_and_update();
+                    match command {
+                        Control::Run => continue,
+                        Control::Pause => {
+                            file.flush().await?;
+                            return Ok(Outcome::Paused);
+                        }
+                        Control::Cancel => return Ok(Outcome::Cancelled),
+                    }
+                }
+                chunk = tokio::time::timeout(STALL_TIMEOUT, response.chunk()) => {
+                    chunk.map_err(|_| DownloadError::Stalled)??
+                }
+            };
+            let Some(chunk) = chunk else { break };
+
+            file.write_all(&chunk).await?;
+            download.downloaded_bytes += chunk.len() as i64;
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                last_progress = Instant::now();
+                self.save(download).await;
+            }
+        }
+        file.flush().await?;
+        drop(file);
+
+        let (size, sha256) = hash_file(&part).await?;
+        download.downloaded_bytes = size as i64;
+        download.total_bytes = Some(size as i64);
+        download.sha256 = Some(sha256);
+        remove_if_exists(&path).await;
+        fs::rename(&part, &path).await?;
+        Ok(Outcome::Completed)
+    }
+
+    /// Отправить запрос файла (с Range, если часть уже загружена)
+    async fn request(&self, url: &str, offset: u64) -> Result<reqwest::Response, DownloadError> {
+        let mut request = self.client.get(url);
+        if offset > 0 {
+            request = request.header(RANGE, format!("bytes={}-", offset));
+        }
+        Ok(request.send().await?)
+    }
+
+    /// Сохранить состояние загрузки и разослать его подписчикам
+    async fn save(&self, download: &mut Download) {
+        download.updated_at = Utc::now();
+        if let Err(e) = self.db.update_download(download).await {
+            warn!("Failed to save download {}: {}", download.id, e);
+        }
+        // Ошибка отправки означает только отсутствие подписчиков
+        let _ = self.events.send(download.clone());
+    }
+}
+
+/// Выбрать файл мода для загрузки
+///
+/// # Возвращает
+/// Ссылку и имя файла или ошибку, если ссылки нет или она чужая
+fn select_file(mod_item: &Mod, url: Option<String>) -> Result<(String, String), DownloadError> {
+    let url = match url {
+        Some(url) => {
+            let known = mod_item.download_url.as_deref() == Some(url.as_str())
+                || mod_item.files.iter().any(|file| file.url == url);
+            if !known {
+                return Err(DownloadError::UnknownUrl(url));
+            }
+            url
+        }
+        None => mod_item
+            .download_url
+            .clone()
+            .or_else(|| mod_item.files.first().map(|file| file.url.clone()))
+            .ok_or(DownloadError::NoDownloadUrl)?,
+    };
+
+    let name = mod_item
+        .files
+        .iter()
+        .find(|file| file.url == url)
+        .map(|file| file.name.clone())
+        .or_else(|| {
+            url::Url::parse(&url)
+                .ok()
+                .and_then(|u| u.path_segments()?.next_back().map(str::to_string))
+        })
+        .unwrap_or_default();
+    Ok((url, sanitize_file_name(&name)))
+}
+
+/// Заменить символы, недопустимые в имени файла
+fn sanitize_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || matches!(c, '.' | '-' | '_' | '+') { c } else { '_' })
+        .collect();
+    let name = name.trim_matches('.');
+    if name.is_empty() {
+        "download".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Путь временного файла загрузки (`archive.zip` -> `archive.zip.part`)
+fn part_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(".");
+    name.push(PART_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Общий размер из заголовка `Content-Range: bytes 100-199/1000`
+fn content_range_total(value: &str) -> Option<u64> {
+    value.rsplit('/').next()?.trim().parse().ok()
+}
+
+/// Посчитать размер и SHA-256 файла
+async fn hash_file(path: &Path) -> Result<(u64, String), std::io::Error> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let mut file = std::fs::File::open(path)?;
+        let mut hasher = Sha256::new();
+        let size = std::io::copy(&mut file, &mut hasher)?;
+        Ok((size, hex::encode(hasher.finalize())))
+    })
+    .await
+    .map_err(std::io::Error::other)?
+}
+
+/// Удалить файл, если он существует
+async fn remove_if_exists(path: &Path) {
+    if let Err(e) = fs::remove_file(path).await {
+        if e.kind() != std::io::ErrorKind::NotFound {
+            warn!("Failed to remove {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ModFile, Site};
+    use serde_json::json;
+    use crate::test_support::{MockResponse, MockServer};
+
+    /// Содержимое тестового файла
+    fn payload() -> Vec<u8> {
+        (0..50_000u32).map(|i| (i % 251) as u8).collect()
+    }
+
+    /// Локальный HTTP сервер файла с поддержкой Range
+    async fn file_server(body: Vec<u8>) -> MockServer {
+        MockServer::start(move |request| {
+            let start = request
+                .header("Range")
+                .and_then(|range| range.strip_prefix("bytes="))
+                .and_then(|range| range.trim_end_matches('-').parse::<usize>().ok());
+            let response = match start {
+                Some(start) => MockResponse::new(206, &body[start..])
+                    .header("Content-Range", format!("bytes {}-{}/{}", start, body.len() - 1, body.len())),
+                None => MockResponse::ok(body.clone()),
+            };
+            response.header("Content-Type", "application/zip")
+        })
+        .await
+    }
+
+    /// Заголовки Range полученных запросов
+    fn ranges(server: &MockServer) -> Vec<Option<String>> {
+        server
+            .requests()
+            .iter()
+            .map(|request| request.header("Range").map(str::to_string))
+            .collect()
+    }
+
+    async fn setup(file_url: &str) -> (tempfile::TempDir, DownloadManager, Mod) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).await.unwrap();
+        let site: Site = db.add_site("Test", "https://example.com", &json!({})).await.unwrap();
+        let mod_item = db
+            .add_mod(&Mod {
+                id: 0,
+                site_id: site.id,
+                title: "Trees".to_string(),
+                url: "https://example.com/trees".to_string(),
+                version: Some("1.2".to_string()),
+                author: None,
+                description: None,
+                image_url: None,
+                changes: None,
+                download_url: None,
+                files: vec![ModFile {
+                    name: "trees 1.2.zip".to_string(),
+                    url: file_url.to_string(),
+                    size: None,
+                    content_type: None,
+                }],
+                followed: false,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        let manager = DownloadManager::new(db, dir.path()).unwrap();
+        (dir, manager, mod_item)
+    }
+
+    async fn wait_finished(events: &mut broadcast::Receiver<Download>) -> Download {
+        loop {
+            let download = tokio::time::timeout(Duration::from_secs(10), events.recv()).await.unwrap().unwrap();
+            if matches!(download.status, DownloadStatus::Completed | DownloadStatus::Failed | DownloadStatus::Cancelled) {
+                return download;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn file_is_downloaded_and_hashed() {
+        let body = payload();
+        let server = file_server(body.clone()).await;
+        let (_dir, manager, mod_item) = setup(&server.url("/files/trees.zip")).await;
+        let mut events = manager.subscribe();
+
+        let download = manager.start(mod_item.id, None).await.unwrap();
+        assert_eq!(download.file_name, "trees_1.2.zip");
+        assert!(download.mod_version_id.is_some());
+
+        let done = wait_finished(&mut events).await;
+        assert_eq!(done.status, DownloadStatus::Completed);
+        assert_eq!(done.total_bytes, Some(body.len() as i64));
+        assert_eq!(done.content_type.as_deref(), Some("application/zip"));
+        assert_eq!(done.sha256.as_deref(), Some(hex::encode(Sha256::digest(&body)).as_str()));
+        assert_eq!(std::fs::read(&done.path).unwrap(), body);
+        assert!(done.path.contains("1.2"));
+        assert_eq!(ranges(&server), vec![None]);
+
+        let stored = manager.db.get_download(done.id).await.unwrap();
+        assert_eq!(stored.status, DownloadStatus::Completed);
+        assert_eq!(stored.sha256, done.sha256);
+    }
+
+    #[tokio::test]
+    async fn paused_download_resumes_with_range() {
+        let body = payload();
+        let server = file_server(body.clone()).await;
+        let url = server.url("/trees.zip");
+        let (_dir, manager, mod_item) = setup(&url).await;
+
+        // Загрузка, прерванная закрытием приложения после 1000 байт
+        let path = manager.dir.join("trees.zip");
+        std::fs::create_dir_all(&manager.dir).unwrap();
+        std::fs::write(part_path(&path), &body[..1000]).unwrap();
+        let interrupted = manager
+            .db
+            .add_download(&Download {
+                id: 0,
+                mod_id: mod_item.id,
+                mod_version_id: None,
+                version: mod_item.version.clone(),
+                url: url.clone(),
+                file_name: "trees.zip".to_string(),
+                path: path.to_string_lossy().into_owned(),
+                status: DownloadStatus::Downloading,
+                total_bytes: None,
+                downloaded_bytes: 1000,
+                content_type: None,
+                sha256: None,
+                error: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(manager.recover_interrupted().await.unwrap(), 1);
+        assert_eq!(manager.db.get_download(interrupted.id).await.unwrap().status, DownloadStatus::Paused);
+
+        let mut events = manager.subscribe();
+        let resumed = manager.start(mod_item.id, Some(url)).await.unwrap();
+        assert_eq!(resumed.id, interrupted.id);
+
+        let done = wait_finished(&mut events).await;
+        assert_eq!(done.status, DownloadStatus::Completed);
+        assert_eq!(ranges(&server), vec![Some("bytes=1000-".to_string())]);
+        assert_eq!(std::fs::read(&path).unwrap(), body);
+        assert!(!part_path(&path).exists());
+    }
+
+    #[test]
+    fn file_names_are_sanitized() {
+        assert_eq!(sanitize_file_name("../../etc/passwd"), "_.._etc_passwd");
+        assert_eq!(sanitize_file_name("мод v1.zip"), "мод_v1.zip");
+        assert_eq!(sanitize_file_name(".."), "download");
+        assert_eq!(content_range_total("bytes 100-199/1000"), Some(1000));
+    }
+}
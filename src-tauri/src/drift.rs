@@ -0,0 +1,291 @@
+use std::path::{Path, PathBuf};
+use chrono::Utc;
+use log::{info, warn};
+use tokio::sync::broadcast;
+use crate::check_runs;
+use crate::database::Database;
+use crate::models::{CheckRun, Mod, ParserDrift, Site};
+
+/// Сколько последних успешных проверок сайта составляют его историю
+pub const DRIFT_HISTORY: usize = 10;
+
+/// Минимальное количество успешных проверок для сравнения с историей
+pub const MIN_HISTORY: usize = 3;
+
+/// Сбой, если доля пустых полей выросла больше чем на эту величину
+const NULL_FRACTION_RISE: f64 = 0.25;
+
+/// Поддиректория снимков страниц (общая с сохраненными страницами)
+const SAVED_PAGES_DIR: &str = "saved_pages";
+
+/// Доля пустых полей найденных модов
+///
+/// Учитываются название ("Unknown" - значение по умолчанию, если селектор
+/// ничего не нашел), версия, автор, описание, изображение и ссылка на
+/// скачивание. Поля, которые конфигурация сайта никогда не извлекает,
+/// пустые во всех проверках и не влияют на сравнение с историей.
+///
+/// # Параметры
+/// * `mods` - моды, найденные парсером
+///
+/// # Возвращает
+/// Доля от 0.0 до 1.0 или None, если модов нет
+pub fn null_fraction(mods: &[Mod]) -> Option<f64> {
+    if mods.is_empty() {
+        return None;
+    }
+    let empty = |value: &Option<String>| value.as_deref().is_none_or(|v| v.trim().is_empty());
+    let nulls: usize = mods
+        .iter()
+        .map(|mod_item| {
+            let title = mod_item.title.trim();
+            [
+                title.is_empty() || title == "Unknown",
+                empty(&mod_item.version),
+                empty(&mod_item.author),
+                empty(&mod_item.description),
+                empty(&mod_item.image_url),
+                empty(&mod_item.download_url),
+            ]
+            .iter()
+            .filter(|null| **null)
+            .count()
+        })
+        .sum();
+    Some(nulls as f64 / (mods.len() * 6) as f64)
+}
+
+/// Сравнить успешную проверку с историей сайта
+///
+/// Сбой парсера подозревается, если модов найдено меньше половины от
+/// обычного количества (медианы истории) или доля пустых полей выросла
+/// на 25 процентных пунктов. В историю входят и проверки с подозрением на
+/// сбой, поэтому новое стабильное состояние сайта со временем становится нормой.
+///
+/// # Параметры
+/// * `run` - текущая проверка
+/// * `history` - предыдущие успешные проверки от новых к старым
+///
+/// # Возвращает
+/// Описание отличий или None, если проверка похожа на историю
+pub fn detect(run: &CheckRun, history: &[CheckRun]) -> Option<String> {
+    if run.error.is_some() || history.len() < MIN_HISTORY {
+        return None;
+    }
+    let mut reasons = Vec::new();
+
+    if let Some(usual) = check_runs::count_drop(run.mods_found, history) {
+        reasons.push(format!("найдено модов: {} (обычно около {})", run.mods_found, usual.round()));
+    }
+
+    let usual_nulls: Vec<f64> = history.iter().filter_map(|previous| previous.null_fraction).collect();
+    if let (Some(current), true) = (run.null_fraction, usual_nulls.len() >= MIN_HISTORY) {
+        let usual = check_runs::median(usual_nulls).unwrap_or(0.0);
+        if current - usual >= NULL_FRACTION_RISE {
+            reasons.push(format!(
+                "доля пустых полей выросла с {:.0}% до {:.0}%",
+                usual * 100.0,
+                current * 100.0
+            ));
+        }
+    }
+
+    (!reasons.is_empty()).then(|| reasons.join("; "))
+}
+
+/// Обнаружение сбоев парсеров сайтов
+///
+/// Сравнивает каждую успешную проверку с историей сайта (см. `detect`).
+/// При первой подозрительной проверке сохраняет загруженную страницу как
+/// снимок в `saved_pages` и отправляет `ParserDrift` подписчикам - в
+/// приложении они превращаются в уведомление `parser_drift`.
+#[derive(Clone)]
+pub struct DriftMonitor {
+    db: Database,
+    dir: PathBuf,
+    events: broadcast::Sender<ParserDrift>,
+}
+
+impl DriftMonitor {
+    /// Создать монитор сбоев парсеров
+    ///
+    /// # Параметры
+    /// * `db` - общая база данных приложения
+    /// * `data_dir` - директория данных приложения (снимки в `saved_pages`)
+    pub fn new(db: Database, data_dir: &Path) -> Self {
+        let (events, _) = broadcast::channel(16);
+        DriftMonitor {
+            db,
+            dir: data_dir.join(SAVED_PAGES_DIR),
+            events,
+        }
+    }
+
+    /// Подписаться на подозрения на сбой парсеров
+    pub fn subscribe(&self) -> broadcast::Receiver<ParserDrift> {
+        self.events.subscribe()
+    }
+
+    /// Проверить результат проверки сайта на сбой парсера
+    ///
+    /// Заполняет `run.drift`. Если предыдущая успешная проверка была в
+    /// порядке, сохраняет снимок страницы в `run.saved_page_id` - о такой
+    /// проверке нужно сообщить через `notify` после ее записи.
+    ///
+    /// # Параметры
+    /// * `site` - проверенный сайт
+    /// * `run` - еще не записанная проверка
+    /// * `page` - URL и содержимое загруженной страницы (если было)
+    ///
+    /// # Возвращает
+    /// true, если сбой обнаружен впервые, или ошибку базы данных
+    pub async fn inspect(&self, site: &Site, run: &mut CheckRun, page: Option<&(String, String)>) -> Result<bool, sqlx::Error> {
+        if run.error.is_some() {
+            return Ok(false);
+        }
+        let history: Vec<CheckRun> = self
+            .db
+            .get_check_runs(Some(site.id), DRIFT_HISTORY as i64 * 3)
+            .await?
+            .into_iter()
+            .filter(|previous| previous.error.is_none())
+            .take(DRIFT_HISTORY)
+            .collect();
+        run.drift = detect(run, &history);
+        let Some(reason) = &run.drift else {
+            return Ok(false);
+        };
+        if history.first().is_some_and(|previous| previous.drift.is_some()) {
+            return Ok(false);
+        }
+
+        warn!("Parser of site {} is likely broken: {}", site.name, reason);
+        if let Some((url, body)) = page {
+            match self.save_snapshot(site.id, url, body).await {
+                Ok(page_id) => run.saved_page_id = Some(page_id),
+                Err(e) => warn!("Failed to save page snapshot of site {}: {}", site.name, e),
+            }
+        }
+        Ok(true)
+    }
+
+    /// Сообщить подписчикам о сбое парсера
+    ///
+    /// # Параметры
+    /// * `site` - сайт
+    /// * `run` - записанная проверка со сбоем (после `inspect`)
+    pub fn notify(&self, site: &Site, run: &CheckRun) {
+        let drift = ParserDrift {
+            site_id: site.id,
+            site_name: site.name.clone(),
+            check_run_id: run.id,
+            reason: run.drift.clone().unwrap_or_default(),
+            saved_page_id: run.saved_page_id,
+        };
+        // Ошибка означает только отсутствие подписчиков
+        let _ = self.events.send(drift);
+    }
+
+    /// Сохранить страницу как новую версию сохраненной страницы сайта
+    async fn save_snapshot(&self, site_id: i64, url: &str, body: &str) -> Result<i64, String> {
+        let now = Utc::now();
+        let host = url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|host| host.replace('.', "_")))
+            .unwrap_or_else(|| "unknown".to_string());
+        let folder = format!("page_{}_{}", now.timestamp_millis(), host);
+        let dir = self.dir.join(&folder);
+        tokio::fs::create_dir_all(&dir).await.map_err(|e| e.to_string())?;
+        tokio::fs::write(dir.join("index.html"), body).await.map_err(|e| e.to_string())?;
+
+        let page_id = self
+            .db
+            .save_page_for_site(site_id, url, &folder, &now.to_rfc3339())
+            .await
+            .map_err(|e| e.to_string())?;
+        info!("Saved page snapshot of {} to {:?}", url, dir);
+        Ok(page_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use serde_json::json;
+    use crate::test_support::{test_state, MockResponse, MockServer};
+    use crate::updates;
+
+    fn run(mods_found: i64, null_fraction: Option<f64>) -> CheckRun {
+        CheckRun {
+            id: 0,
+            site_id: 1,
+            started_at: Utc::now(),
+            finished_at: Utc::now(),
+            http_status: Some(200),
+            mods_found,
+            new_mods: 0,
+            updated_mods: 0,
+            error: None,
+            null_fraction,
+            drift: None,
+            saved_page_id: None,
+        }
+    }
+
+    #[test]
+    fn runs_are_compared_with_history() {
+        let history = vec![run(20, Some(0.1)), run(22, Some(0.1)), run(2, Some(0.2))];
+        assert_eq!(detect(&run(18, Some(0.15)), &history), None);
+        assert!(detect(&run(0, None), &history).unwrap().contains("обычно около 20"));
+        assert!(detect(&run(20, Some(0.5)), &history).unwrap().contains("с 10% до 50%"));
+        // Мало истории или сайт обычно почти пустой
+        assert_eq!(detect(&run(0, None), &history[..2]), None);
+        assert_eq!(detect(&run(0, None), &[run(2, None), run(1, None), run(2, None)]), None);
+    }
+
+    #[tokio::test]
+    async fn drift_is_reported_once_with_snapshot() {
+        let (dir, state) = test_state().await;
+        let items: String = (0..5)
+            .map(|i| format!("<item><title>Mod {} v1.0</title><link>/mod{}</link></item>", i, i))
+            .collect();
+        let feed = format!("<rss version=\"2.0\"><channel>{}</channel></rss>", items);
+        let body = Arc::new(Mutex::new(feed));
+        let server = MockServer::start({
+            let body = body.clone();
+            move |_| MockResponse::ok(body.lock().unwrap().clone()).header("Content-Type", "application/rss+xml")
+        })
+        .await;
+        let site = state
+            .db
+            .add_site("Feed", server.address(), &json!({"source": {"type": "feed"}}))
+            .await
+            .unwrap();
+        let mut events = state.drift.subscribe();
+        for _ in 0..MIN_HISTORY {
+            updates::check_site(&state, &site).await.unwrap();
+        }
+        assert!(events.try_recv().is_err());
+
+        // Разметка сменилась: элементы ленты больше не находятся
+        let broken = "<rss version=\"2.0\"><channel></channel></rss>".to_string();
+        *body.lock().unwrap() = broken.clone();
+        updates::check_site(&state, &site).await.unwrap();
+        let drift = events.try_recv().unwrap();
+        assert_eq!(drift.site_id, site.id);
+        assert!(drift.reason.contains("найдено модов: 0"));
+        let page_id = drift.saved_page_id.unwrap();
+        let snapshots: Vec<_> = std::fs::read_dir(dir.path().join(SAVED_PAGES_DIR)).unwrap().collect();
+        assert_eq!(snapshots.len(), 1);
+        let snapshot = snapshots[0].as_ref().unwrap().path().join("index.html");
+        assert_eq!(std::fs::read_to_string(snapshot).unwrap(), broken);
+
+        // Сбой продолжается: проверка помечена, но повторного сообщения нет
+        updates::check_site(&state, &site).await.unwrap();
+        assert!(events.try_recv().is_err());
+        let runs = state.db.get_check_runs(Some(site.id), 2).await.unwrap();
+        assert!(runs.iter().all(|run| run.drift.is_some()));
+        assert_eq!((runs[0].saved_page_id, runs[1].saved_page_id), (None, Some(page_id)));
+    }
+}
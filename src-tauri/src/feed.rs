@@ -0,0 +1,373 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use quick_xml::escape::{resolve_html5_entity, resolve_predefined_entity};
+use quick_xml::events::{BytesRef, BytesStart, Event};
+use quick_xml::reader::Reader;
+use quick_xml::XmlVersion;
+use regex::Regex;
+use scraper::Html;
+use log::debug;
+use crate::models::Mod;
+
+/// Регулярное выражение версии по умолчанию: `Mod v1.2.3`, `Mod 2.0-beta`
+pub const DEFAULT_VERSION_REGEX: &str = r"(?i)\bv?(\d+(?:\.\d+)+(?:-[0-9a-z.]+)?)";
+
+/// Конфигурация источника RSS/Atom
+///
+/// Задается как `{"type": "feed", ...}` в ключе `source` конфигурации
+/// парсера. Лента загружается с `list_url` (по умолчанию URL сайта).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FeedConfig {
+    /// Регулярное выражение для извлечения версии из заголовка записи.
+    /// Берется первая группа захвата, а если групп нет - все совпадение.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_regex: Option<String>,
+}
+
+impl FeedConfig {
+    /// Скомпилировать регулярное выражение версии
+    ///
+    /// # Возвращает
+    /// Регулярное выражение (по умолчанию `DEFAULT_VERSION_REGEX`) или ошибку
+    pub fn version_regex(&self) -> Result<Regex, String> {
+        let pattern = self.version_regex.as_deref().unwrap_or(DEFAULT_VERSION_REGEX);
+        Regex::new(pattern).map_err(|e| format!("Invalid version_regex: {}", e))
+    }
+}
+
+/// Поля записи ленты, собранные при чтении XML
+#[derive(Default)]
+struct FeedEntry {
+    title: Option<String>,
+    link: Option<String>,
+    /// `guid`/`id` - запасная ссылка, если у записи нет `link`
+    id: Option<String>,
+    author: Option<String>,
+    summary: Option<String>,
+    /// Полный текст (`content`, `content:encoded`), если нет краткого описания
+    content: Option<String>,
+    image_url: Option<String>,
+    download_url: Option<String>,
+    published: Option<DateTime<Utc>>,
+    updated: Option<DateTime<Utc>>,
+}
+
+/// Распарсить ленту RSS 2.0, RSS 1.0 (RDF) или Atom
+///
+/// # Параметры
+/// * `xml` - содержимое ленты
+/// * `feed_url` - URL ленты (для относительных ссылок)
+/// * `version_regex` - регулярное выражение версии (см. `FeedConfig::version_regex`)
+///
+/// # Возвращает
+/// Вектор модов (site_id проставляет вызывающая сторона) или ошибку разбора
+pub fn parse_feed(xml: &str, feed_url: &str, version_regex: &Regex) -> Result<Vec<Mod>, String> {
+    let base = url::Url::parse(feed_url).ok();
+    // Текст не обрезается: ссылки на сущности (`&amp;`) приходят отдельными событиями
+    let mut reader = Reader::from_str(xml);
+
+    let mut root: Option<String> = None;
+    let mut entries = Vec::new();
+    // Запись, которая читается сейчас, и путь элементов внутри нее
+    let mut entry: Option<FeedEntry> = None;
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Invalid feed XML at position {}: {}", reader.error_position(), e))?;
+        match event {
+            Event::Start(element) => {
+                let name = element.local_name().as_ref().to_string();
+                if root.is_none() {
+                    root = Some(name.clone());
+                }
+                match entry.as_mut() {
+                    Some(current) => {
+                        if path.is_empty() {
+                            text.clear();
+                            read_link_attributes(current, &element);
+                        }
+                        path.push(name);
+                    }
+                    None if name == "item" || name == "entry" => entry = Some(FeedEntry::default()),
+                    None => {}
+                }
+            }
+            Event::Empty(element) => {
+                if let Some(current) = entry.as_mut().filter(|_| path.is_empty()) {
+                    read_link_attributes(current, &element);
+                }
+            }
+            // Текст вне записей (заголовок канала и т.п.) не нужен
+            Event::Text(content) if entry.is_some() => text.push_str(&content.xml10_content()),
+            Event::CData(content) if entry.is_some() => text.push_str(&content.xml10_content()),
+            Event::GeneralRef(reference) if entry.is_some() => push_reference(&mut text, &reference),
+            Event::End(_) => {
+                let Some(current) = entry.as_mut() else { continue };
+                match path.pop() {
+                    // Конец самой записи
+                    None => entries.push(entry.take().unwrap_or_default()),
+                    Some(name) => {
+                        if path.is_empty() {
+                            set_field(current, &name, text.trim());
+                        } else if name == "name" && path.len() == 1 && path[0] == "author" {
+                            // Atom: <author><name>...</name><email>...</email></author>
+                            current.author = non_empty(text.trim());
+                            text.clear();
+                        }
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !matches!(root.as_deref(), Some("rss" | "feed" | "RDF")) {
+        return Err(format!(
+            "Not an RSS/Atom feed (root element: {})",
+            root.as_deref().unwrap_or("none")
+        ));
+    }
+
+    let mods: Vec<Mod> = entries
+        .into_iter()
+        .filter_map(|entry| entry_to_mod(entry, base.as_ref(), version_regex))
+        .collect();
+    debug!("Feed {} has {} entries with links", feed_url, mods.len());
+    Ok(mods)
+}
+
+/// Сохранить текст дочернего элемента записи в соответствующее поле
+fn set_field(entry: &mut FeedEntry, name: &str, text: &str) {
+    match name {
+        "title" => entry.title = non_empty(text),
+        // RSS: ссылка текстом; у Atom текста нет, href читается из атрибутов
+        "link" if entry.link.is_none() => entry.link = non_empty(text),
+        "guid" | "id" => entry.id = non_empty(text),
+        // RSS `author`, Dublin Core `dc:creator`; Atom `author` уже заполнен из `name`
+        "author" | "creator" if entry.author.is_none() => entry.author = non_empty(text),
+        "description" | "summary" => entry.summary = non_empty(text),
+        "content" | "encoded" => entry.content = non_empty(text),
+        "pubDate" | "published" | "issued" => entry.published = parse_date(text),
+        "updated" | "modified" | "date" => entry.updated = parse_date(text),
+        _ => {}
+    }
+}
+
+/// Прочитать ссылки из атрибутов элемента записи
+///
+/// Atom `<link href="..." rel="alternate|enclosure"/>`, RSS
+/// `<enclosure url="..." type="..."/>` и `<media:thumbnail url="..."/>`.
+fn read_link_attributes(entry: &mut FeedEntry, element: &BytesStart) {
+    let attr = |name: &str| {
+        element
+            .attributes()
+            .flatten()
+            .find(|a| a.key.local_name().as_ref() == name)
+            .and_then(|a| a.normalized_value(XmlVersion::default()).ok().map(|v| v.trim().to_string()))
+            .filter(|v| !v.is_empty())
+    };
+    let is_image = || attr("type").is_some_and(|t| t.starts_with("image/"));
+
+    match element.local_name().as_ref() {
+        "link" => {
+            let Some(href) = attr("href") else { return };
+            match attr("rel").as_deref() {
+                None | Some("alternate") if entry.link.is_none() => entry.link = Some(href),
+                Some("enclosure") if is_image() => entry.image_url = entry.image_url.take().or(Some(href)),
+                Some("enclosure") => entry.download_url = entry.download_url.take().or(Some(href)),
+                _ => {}
+            }
+        }
+        "enclosure" => {
+            let Some(url) = attr("url") else { return };
+            if is_image() {
+                entry.image_url = entry.image_url.take().or(Some(url));
+            } else {
+                entry.download_url = entry.download_url.take().or(Some(url));
+            }
+        }
+        "thumbnail" => {
+            if let Some(url) = attr("url") {
+                entry.image_url = Some(url);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Добавить в текст раскрытую ссылку на сущность (`&amp;`, `&#8212;`, `&nbsp;`)
+fn push_reference(text: &mut String, reference: &BytesRef) {
+    if let Ok(Some(ch)) = reference.resolve_char_ref() {
+        text.push(ch);
+        return;
+    }
+    let name = reference.xml10_content();
+    match resolve_predefined_entity(&name).or_else(|| resolve_html5_entity(&name)) {
+        Some(value) => text.push_str(value),
+        None => {
+            text.push('&');
+            text.push_str(&name);
+            text.push(';');
+        }
+    }
+}
+
+/// Преобразовать запись ленты в мод
+///
+/// # Возвращает
+/// Мод или None, если у записи нет ни ссылки, ни `guid`/`id` в виде URL
+fn entry_to_mod(entry: FeedEntry, base: Option<&url::Url>, version_regex: &Regex) -> Option<Mod> {
+    let resolve = |href: String| match base {
+        Some(base) => base.join(&href).map(String::from).unwrap_or(href),
+        None => href,
+    };
+    let url = entry
+        .link
+        .map(&resolve)
+        .or_else(|| entry.id.filter(|id| id.starts_with("http://") || id.starts_with("https://")))?;
+
+    let title = entry.title.map(|t| collapse_whitespace(&t)).unwrap_or_else(|| "Unknown".to_string());
+    let version = extract_version(&title, version_regex);
+    let description = entry.summary.or(entry.content).map(|html| html_to_text(&html)).filter(|s| !s.is_empty());
+    let now = Utc::now();
+
+    Some(Mod {
+        id: 0,
+        site_id: 0,
+        title,
+        url,
+        version,
+        author: entry.author,
+        description,
+        image_url: entry.image_url.map(&resolve),
+        changes: None,
+        download_url: entry.download_url.map(&resolve),
+        files: Vec::new(),
+        followed: false,
+        created_at: entry.published.or(entry.updated).unwrap_or(now),
+        updated_at: entry.updated.or(entry.published).unwrap_or(now),
+    })
+}
+
+/// Извлечь версию из заголовка записи
+///
+/// # Параметры
+/// * `title` - заголовок записи
+/// * `version_regex` - регулярное выражение версии
+///
+/// # Возвращает
+/// Первую группу захвата (или все совпадение, если групп нет) или None
+pub fn extract_version(title: &str, version_regex: &Regex) -> Option<String> {
+    let captures = version_regex.captures(title)?;
+    captures
+        .get(1)
+        .or_else(|| captures.get(0))
+        .map(|m| m.as_str().trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// Разобрать дату RFC 2822 (RSS) или RFC 3339 (Atom)
+fn parse_date(text: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc2822(text)
+        .or_else(|_| DateTime::parse_from_rfc3339(text))
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+}
+
+/// Получить текст описания без HTML разметки
+fn html_to_text(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    collapse_whitespace(&fragment.root_element().text().collect::<String>())
+}
+
+/// Заменить последовательности пробельных символов одним пробелом
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Непустая строка или None
+fn non_empty(text: &str) -> Option<String> {
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn default_regex() -> Regex {
+        FeedConfig::default().version_regex().unwrap()
+    }
+
+    #[test]
+    fn rss_items_are_mapped_to_mods() {
+        let xml = r#"<?xml version="1.0"?>
+            <rss version="2.0" xmlns:dc="http://purl.org/dc/elements/1.1/">
+              <channel>
+                <title>Mods</title>
+                <link>https://example.com/</link>
+                <item>
+                  <title>Better Trees v1.4.2</title>
+                  <link>/mods/better-trees</link>
+                  <dc:creator>alice</dc:creator>
+                  <description><![CDATA[<p>New <b>trees</b> &amp; bushes</p>]]></description>
+                  <pubDate>Tue, 10 Jun 2025 04:00:00 GMT</pubDate>
+                  <enclosure url="https://cdn.example.com/trees.zip" type="application/zip" length="1"/>
+                </item>
+                <item>
+                  <title>No link &#8212; skipped</title>
+                  <guid isPermaLink="false">tag:123</guid>
+                </item>
+              </channel>
+            </rss>"#;
+
+        let mods = parse_feed(xml, "https://example.com/feed.xml", &default_regex()).unwrap();
+        assert_eq!(mods.len(), 1);
+        let m = &mods[0];
+        assert_eq!(m.title, "Better Trees v1.4.2");
+        assert_eq!(m.url, "https://example.com/mods/better-trees");
+        assert_eq!(m.version.as_deref(), Some("1.4.2"));
+        assert_eq!(m.author.as_deref(), Some("alice"));
+        assert_eq!(m.description.as_deref(), Some("New trees & bushes"));
+        assert_eq!(m.download_url.as_deref(), Some("https://cdn.example.com/trees.zip"));
+        assert_eq!(m.created_at.to_rfc3339(), "2025-06-10T04:00:00+00:00");
+    }
+
+    #[test]
+    fn atom_entries_are_mapped_to_mods() {
+        let xml = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+              <title>Releases</title>
+              <entry>
+                <title type="text">Release build-77 &amp; fixes</title>
+                <link rel="self" href="https://example.com/api/77"/>
+                <link rel="alternate" href="https://example.com/releases/77"/>
+                <id>urn:uuid:77</id>
+                <author><name>bob</name><email>bob@example.com</email></author>
+                <summary>Fixes crash</summary>
+                <published>2025-01-02T10:00:00+03:00</published>
+                <updated>2025-01-03T12:00:00Z</updated>
+              </entry>
+            </feed>"#;
+
+        let regex = FeedConfig { version_regex: Some(r"build-(\d+)".to_string()) }.version_regex().unwrap();
+        let mods = parse_feed(xml, "https://example.com/atom", &regex).unwrap();
+        assert_eq!(mods.len(), 1);
+        let m = &mods[0];
+        assert_eq!(m.title, "Release build-77 & fixes");
+        assert_eq!(m.url, "https://example.com/releases/77");
+        assert_eq!(m.version.as_deref(), Some("77"));
+        assert_eq!(m.author.as_deref(), Some("bob"));
+        assert_eq!(m.description.as_deref(), Some("Fixes crash"));
+        assert_eq!(m.created_at.to_rfc3339(), "2025-01-02T07:00:00+00:00");
+        assert_eq!(m.updated_at.to_rfc3339(), "2025-01-03T12:00:00+00:00");
+    }
+
+    #[test]
+    fn html_page_is_not_a_feed() {
+        let err = parse_feed("<html><body></body></html>", "https://example.com/", &default_regex()).unwrap_err();
+        assert!(err.contains("Not an RSS/Atom feed"));
+    }
+}
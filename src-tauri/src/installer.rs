@@ -0,0 +1,672 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use log::{info, warn};
+use tokio::sync::Mutex;
+use crate::database::Database;
+use crate::models::{DownloadStatus, Installation, InstalledFile};
+
+/// Префикс служебных директорий установщика внутри директории установки
+const WORK_DIR_PREFIX: &str = ".mod-aggregator-";
+
+/// Ошибки установки модов
+#[derive(Debug, thiserror::Error)]
+pub enum InstallError {
+    #[error("Загрузка {0} не найдена")]
+    DownloadNotFound(i64),
+    #[error("Загрузка {0} еще не завершена")]
+    DownloadNotCompleted(i64),
+    #[error("Установка {0} не найдена")]
+    NotFound(i64),
+    #[error("Не задана директория установки: укажите ее для сайта или профиля")]
+    NoInstallDir,
+    #[error("Директория установки {0} не существует")]
+    MissingInstallDir(String),
+    #[error("Небезопасный путь в архиве: {0}")]
+    UnsafePath(String),
+    #[error("Ошибка архива: {0}")]
+    Archive(String),
+    #[error("Ошибка базы данных: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Ошибка файла: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Формат загруженного файла
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    SevenZip,
+    TarGz,
+    Tar,
+    /// Не архив: файл устанавливается как есть
+    File,
+}
+
+/// Установщик модов
+///
+/// Распаковывает завершенные загрузки (zip, 7z, tar, tar.gz; остальные файлы
+/// копируются как есть) в директорию игры/модов и записывает каждый файл в
+/// таблицу `installed_files`. Архив сначала распаковывается во временную
+/// директорию, затем файлы переносятся на место; замененные файлы хранятся в
+/// резервной директории, пока перенос не закончится, и возвращаются при ошибке.
+/// Установки выполняются по одной.
+#[derive(Clone)]
+pub struct Installer {
+    db: Database,
+    lock: Arc<Mutex<()>>,
+}
+
+impl Installer {
+    /// Создать установщик
+    ///
+    /// # Параметры
+    /// * `db` - база данных приложения
+    pub fn new(db: Database) -> Self {
+        Installer {
+            db,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    /// Установить или обновить мод из загрузки
+    ///
+    /// Если мод уже установлен в эту директорию, установка обновляется на месте:
+    /// файлы старой версии, которых нет в новой, удаляются. При любой ошибке
+    /// директория остается в прежнем состоянии.
+    ///
+    /// # Параметры
+    /// * `download_id` - ID завершенной загрузки
+    /// * `install_dir` - директория установки (None = директория сайта мода)
+    ///
+    /// # Возвращает
+    /// Установку или ошибку
+    pub async fn install(&self, download_id: i64, install_dir: Option<String>) -> Result<Installation, InstallError> {
+        let _guard = self.lock.lock().await;
+
+        let download = self.db.get_download(download_id).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => InstallError::DownloadNotFound(download_id),
+            e => InstallError::Database(e),
+        })?;
+        if download.status != DownloadStatus::Completed {
+            return Err(InstallError::DownloadNotCompleted(download_id));
+        }
+
+        let install_dir = match install_dir.filter(|dir| !dir.trim().is_empty()) {
+            Some(dir) => dir,
+            None => {
+                let mod_item = self.db.get_mod(download.mod_id).await?;
+                let site = self.db.get_site(mod_item.site_id).await?;
+                site.install_dir.filter(|dir| !dir.trim().is_empty()).ok_or(InstallError::NoInstallDir)?
+            }
+        };
+
+        let existing = self.db.find_installation(download.mod_id, &install_dir).await?;
+        let old_files = match &existing {
+            Some(installation) => self.db.get_installed_files(installation.id).await?,
+            None => Vec::new(),
+        };
+
+        let archive = PathBuf::from(&download.path);
+        let file_name = download.file_name.clone();
+        let target = PathBuf::from(&install_dir);
+        let old_paths: Vec<String> = old_files.into_iter().map(|file| file.path).collect();
+        let files = tokio::task::spawn_blocking(move || install_files(&archive, &file_name, &target, download_id, &old_paths))
+            .await
+            .map_err(io::Error::other)??;
+
+        let installation = match existing {
+            Some(installation) => Installation {
+                download_id: Some(download_id),
+                version: download.version.clone(),
+                ..installation
+            },
+            None => Installation {
+                id: 0,
+                mod_id: download.mod_id,
+                download_id: Some(download_id),
+                version: download.version.clone(),
+                install_dir,
+                installed_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            },
+        };
+        let installation = self.db.save_installation(&installation, &files).await?;
+        info!(
+            "Installed mod {} ({} files) into {}",
+            installation.mod_id,
+            files.len(),
+            installation.install_dir
+        );
+        Ok(installation)
+    }
+
+    /// Удалить установленный мод
+    ///
+    /// Удаляются файлы из `installed_files` и опустевшие после этого директории.
+    ///
+    /// # Параметры
+    /// * `installation_id` - ID установки
+    ///
+    /// # Возвращает
+    /// Пустой результат или ошибку
+    pub async fn uninstall(&self, installation_id: i64) -> Result<(), InstallError> {
+        let _guard = self.lock.lock().await;
+
+        let installation = self.db.get_installation(installation_id).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => InstallError::NotFound(installation_id),
+            e => InstallError::Database(e),
+        })?;
+        let files: Vec<InstalledFile> = self.db.get_installed_files(installation_id).await?;
+
+        let target = PathBuf::from(&installation.install_dir);
+        tokio::task::spawn_blocking(move || remove_files(&target, files.iter().map(|file| file.path.as_str())))
+            .await
+            .map_err(io::Error::other)??;
+
+        self.db.delete_installation(installation_id).await?;
+        info!("Uninstalled mod {} from {}", installation.mod_id, installation.install_dir);
+        Ok(())
+    }
+}
+
+/// Распаковать загрузку и перенести файлы в директорию установки
+///
+/// # Параметры
+/// * `archive` - загруженный файл
+/// * `file_name` - имя файла (для установки не-архивов)
+/// * `target` - директория установки
+/// * `download_id` - ID загрузки (для имен служебных директорий)
+/// * `old_files` - файлы предыдущей установки этого мода
+///
+/// # Возвращает
+/// Установленные файлы (путь, размер) или ошибку
+fn install_files(
+    archive: &Path,
+    file_name: &str,
+    target: &Path,
+    download_id: i64,
+    old_files: &[String],
+) -> Result<Vec<(String, i64)>, InstallError> {
+    if !target.is_dir() {
+        return Err(InstallError::MissingInstallDir(target.display().to_string()));
+    }
+
+    let suffix = format!("{}-{}", download_id, chrono::Utc::now().timestamp_millis());
+    let staging = target.join(format!("{}staging-{}", WORK_DIR_PREFIX, suffix));
+    let backup = target.join(format!("{}backup-{}", WORK_DIR_PREFIX, suffix));
+
+    let result = extract(archive, file_name, &staging).and_then(|files| {
+        let paths: Vec<String> = files.iter().map(|(path, _)| path.clone()).collect();
+        commit(target, &staging, &backup, &paths, old_files)?;
+        Ok(files)
+    });
+
+    if let Err(e) = fs::remove_dir_all(&staging) {
+        if e.kind() != io::ErrorKind::NotFound {
+            warn!("Failed to remove staging directory {:?}: {}", staging, e);
+        }
+    }
+    result
+}
+
+/// Определить формат файла по сигнатуре
+fn detect_format(path: &Path) -> io::Result<ArchiveFormat> {
+    let mut header = Vec::with_capacity(512);
+    File::open(path)?.take(512).read_to_end(&mut header)?;
+
+    Ok(if header.starts_with(b"PK\x03\x04") || header.starts_with(b"PK\x05\x06") {
+        ArchiveFormat::Zip
+    } else if header.starts_with(b"7z\xBC\xAF\x27\x1C") {
+        ArchiveFormat::SevenZip
+    } else if header.starts_with(&[0x1f, 0x8b]) {
+        ArchiveFormat::TarGz
+    } else if header.get(257..262) == Some(b"ustar".as_slice()) {
+        ArchiveFormat::Tar
+    } else {
+        ArchiveFormat::File
+    })
+}
+
+/// Распаковать файл во временную директорию
+///
+/// # Возвращает
+/// Файлы (путь через `/`, размер), отсортированные по пути, или ошибку
+fn extract(archive: &Path, file_name: &str, staging: &Path) -> Result<Vec<(String, i64)>, InstallError> {
+    let mut files = BTreeMap::new();
+    fs::create_dir_all(staging)?;
+
+    match detect_format(archive)? {
+        ArchiveFormat::Zip => {
+            let mut zip = zip::ZipArchive::new(File::open(archive)?).map_err(|e| InstallError::Archive(e.to_string()))?;
+            for index in 0..zip.len() {
+                let mut entry = zip.by_index(index).map_err(|e| InstallError::Archive(e.to_string()))?;
+                if entry.is_dir() {
+                    continue;
+                }
+                if entry.is_symlink() {
+                    warn!("Skipping symlink {} in {:?}", entry.name(), archive);
+                    continue;
+                }
+                let name = entry.name().to_string();
+                write_entry(staging, &name, &mut entry, &mut files)?;
+            }
+        }
+        ArchiveFormat::SevenZip => {
+            let mut reader = sevenz_rust::SevenZReader::open(archive, sevenz_rust::Password::empty())
+                .map_err(|e| InstallError::Archive(e.to_string()))?;
+            // Ошибки записи сохраняются здесь: обработчик может вернуть только ошибку sevenz_rust
+            let mut failure = None;
+            reader
+                .for_each_entries(|entry, data| {
+                    if entry.is_directory() {
+                        return Ok(true);
+                    }
+                    match write_entry(staging, entry.name(), data, &mut files) {
+                        Ok(()) => Ok(true),
+                        Err(e) => {
+                            failure = Some(e);
+                            Ok(false)
+                        }
+                    }
+                })
+                .map_err(|e| InstallError::Archive(e.to_string()))?;
+            if let Some(e) = failure {
+                return Err(e);
+            }
+        }
+        ArchiveFormat::TarGz => extract_tar(flate2::read::GzDecoder::new(File::open(archive)?), staging, &mut files)?,
+        ArchiveFormat::Tar => extract_tar(File::open(archive)?, staging, &mut files)?,
+        ArchiveFormat::File => {
+            write_entry(staging, file_name, &mut File::open(archive)?, &mut files)?;
+        }
+    }
+
+    if files.is_empty() {
+        return Err(InstallError::Archive("archive contains no files".to_string()));
+    }
+    Ok(files.into_iter().collect())
+}
+
+/// Распаковать tar архив (символические ссылки и специальные файлы пропускаются)
+fn extract_tar<R: Read>(reader: R, staging: &Path, files: &mut BTreeMap<String, i64>) -> Result<(), InstallError> {
+    let mut archive = tar::Archive::new(reader);
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let kind = entry.header().entry_type();
+        if kind.is_dir() {
+            continue;
+        }
+        let name = entry.path()?.to_string_lossy().into_owned();
+        if !kind.is_file() {
+            warn!("Skipping non-regular tar entry {}", name);
+            continue;
+        }
+        write_entry(staging, &name, &mut entry, files)?;
+    }
+    Ok(())
+}
+
+/// Записать файл из архива во временную директорию
+fn write_entry(
+    staging: &Path,
+    name: &str,
+    data: &mut dyn Read,
+    files: &mut BTreeMap<String, i64>,
+) -> Result<(), InstallError> {
+    let path = safe_relative_path(name)?;
+    let destination = staging.join(&path);
+    if let Some(parent) = destination.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let size = io::copy(data, &mut File::create(&destination)?)?;
+    files.insert(path, size as i64);
+    Ok(())
+}
+
+/// Проверить путь из архива и привести его к виду `dir/file`
+///
+/// Ведущие `/` и `./` отбрасываются; `..`, пути с диском (`C:`) и служебные
+/// директории установщика запрещены.
+fn safe_relative_path(name: &str) -> Result<String, InstallError> {
+    let mut parts = Vec::new();
+    for part in name.split(['/', '\\']) {
+        match part {
+            "" | "." => continue,
+            ".." => return Err(InstallError::UnsafePath(name.to_string())),
+            part if part.contains(':') || part.starts_with(WORK_DIR_PREFIX) => {
+                return Err(InstallError::UnsafePath(name.to_string()))
+            }
+            part => parts.push(part),
+        }
+    }
+    if parts.is_empty() {
+        return Err(InstallError::UnsafePath(name.to_string()));
+    }
+    Ok(parts.join("/"))
+}
+
+/// Перенести файлы из временной директории в директорию установки
+///
+/// Текущие версии заменяемых файлов и файлы старой установки переносятся в
+/// резервную директорию. Если перенос прервался ошибкой, уже перенесенные
+/// новые файлы удаляются, а резервные возвращаются на место.
+fn commit(
+    target: &Path,
+    staging: &Path,
+    backup: &Path,
+    new_files: &[String],
+    old_files: &[String],
+) -> Result<(), InstallError> {
+    let mut backed_up = Vec::new();
+    let mut placed = Vec::new();
+
+    if let Err(e) = apply(target, staging, backup, new_files, old_files, &mut backed_up, &mut placed) {
+        warn!("Installation into {:?} failed, rolling back: {}", target, e);
+        rollback(target, backup, &backed_up, &placed);
+        remove_restored_backup(backup);
+        return Err(e.into());
+    }
+
+    // Замененные файлы больше не нужны
+    if let Err(e) = fs::remove_dir_all(backup) {
+        if e.kind() != io::ErrorKind::NotFound {
+            warn!("Failed to remove backup directory {:?}: {}", backup, e);
+        }
+    }
+    let new_set: BTreeSet<&str> = new_files.iter().map(String::as_str).collect();
+    for path in old_files.iter().filter(|path| !new_set.contains(path.as_str())) {
+        remove_empty_parents(target, path);
+    }
+    Ok(())
+}
+
+/// Выполнить перенос файлов, записывая выполненные шаги для отката
+fn apply<'a>(
+    target: &Path,
+    staging: &Path,
+    backup: &Path,
+    new_files: &'a [String],
+    old_files: &'a [String],
+    backed_up: &mut Vec<&'a str>,
+    placed: &mut Vec<&'a str>,
+) -> io::Result<()> {
+    let replaced: BTreeSet<&str> = old_files.iter().chain(new_files).map(String::as_str).collect();
+    for path in replaced {
+        let current = target.join(path);
+        if fs::symlink_metadata(&current).is_ok_and(|meta| meta.is_file()) {
+            move_file(&current, &backup.join(path))?;
+            backed_up.push(path);
+        }
+    }
+    for path in new_files {
+        move_file(&staging.join(path), &target.join(path))?;
+        placed.push(path);
+    }
+    Ok(())
+}
+
+/// Откатить перенос: удалить новые файлы и вернуть резервные
+fn rollback(target: &Path, backup: &Path, backed_up: &[&str], placed: &[&str]) {
+    for path in placed.iter().rev() {
+        if let Err(e) = fs::remove_file(target.join(path)) {
+            warn!("Rollback: failed to remove {}: {}", path, e);
+        }
+        remove_empty_parents(target, path);
+    }
+    for path in backed_up.iter().rev() {
+        if let Err(e) = move_file(&backup.join(path), &target.join(path)) {
+            warn!("Rollback: failed to restore {} from {:?}: {}", path, backup, e);
+        }
+    }
+}
+
+/// Удалить файлы установки и опустевшие директории
+fn remove_files<'a>(target: &Path, paths: impl Iterator<Item = &'a str>) -> io::Result<()> {
+    for path in paths {
+        match fs::remove_file(target.join(path)) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => warn!("Installed file {} is already missing", path),
+            Err(e) => return Err(e),
+        }
+        remove_empty_parents(target, path);
+    }
+    Ok(())
+}
+
+/// Переместить файл, создав родительские директории
+fn move_file(from: &Path, to: &Path) -> io::Result<()> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(from, to)
+}
+
+/// Удалить пустые директории на пути файла (не выше директории установки)
+fn remove_empty_parents(target: &Path, path: &str) {
+    let mut dir = target.join(path);
+    while dir.pop() && dir.starts_with(target) && dir != target {
+        if fs::remove_dir(&dir).is_err() {
+            break;
+        }
+    }
+}
+
+/// Удалить резервную директорию после отката
+///
+/// Если какие-то файлы не удалось вернуть, директория остается: эти файлы
+/// нужны пользователю.
+fn remove_restored_backup(backup: &Path) {
+    if !backup.exists() {
+        return;
+    }
+    let has_files = fs::read_dir(backup).is_ok_and(|entries| entries.flatten().any(|entry| !has_only_dirs(&entry.path())));
+    if has_files {
+        warn!("Keeping backup directory {:?}: some files were not restored", backup);
+    } else if let Err(e) = fs::remove_dir_all(backup) {
+        warn!("Failed to remove backup directory {:?}: {}", backup, e);
+    }
+}
+
+/// Проверить, что путь - директория без файлов (возможно, с пустыми поддиректориями)
+fn has_only_dirs(path: &Path) -> bool {
+    path.is_dir()
+        && fs::read_dir(path).is_ok_and(|entries| entries.flatten().all(|entry| has_only_dirs(&entry.path())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use chrono::Utc;
+    use serde_json::json;
+    use crate::models::{Download, Mod};
+
+    /// Создать zip архив с файлами (имя, содержимое)
+    fn write_zip(path: &Path, files: &[(&str, &str)]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in files {
+            zip.start_file(*name, zip::write::SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    /// Создать tar.gz архив с файлами (имя, содержимое)
+    fn write_tar_gz(path: &Path, files: &[(&str, &str)]) {
+        let encoder = flate2::write::GzEncoder::new(File::create(path).unwrap(), flate2::Compression::default());
+        let mut tar = tar::Builder::new(encoder);
+        for (name, content) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            tar.append_data(&mut header, name, content.as_bytes()).unwrap();
+        }
+        tar.into_inner().unwrap().finish().unwrap();
+    }
+
+    /// База с сайтом (директория установки `game/mods`) и модом
+    async fn setup() -> (tempfile::TempDir, Database, Installer, Mod, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).await.unwrap();
+        let game = dir.path().join("game").join("mods");
+        fs::create_dir_all(&game).unwrap();
+        let site = db.add_site("Test", "https://example.com", &json!({})).await.unwrap();
+        db.set_site_install_dir(site.id, Some(game.to_str().unwrap())).await.unwrap();
+        let mod_item = db
+            .add_mod(&Mod {
+                id: 0,
+                site_id: site.id,
+                title: "Trees".to_string(),
+                url: "https://example.com/trees".to_string(),
+                version: Some("1.0".to_string()),
+                author: None,
+                description: None,
+                image_url: None,
+                changes: None,
+                download_url: None,
+                files: Vec::new(),
+                followed: false,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .await
+            .unwrap();
+        let installer = Installer::new(db.clone());
+        (dir, db, installer, mod_item, game)
+    }
+
+    /// Добавить завершенную загрузку файла
+    async fn completed_download(db: &Database, mod_id: i64, path: &Path, version: &str) -> i64 {
+        db.add_download(&Download {
+            id: 0,
+            mod_id,
+            mod_version_id: None,
+            version: Some(version.to_string()),
+            url: format!("https://example.com/{}", version),
+            file_name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            path: path.to_string_lossy().into_owned(),
+            status: DownloadStatus::Completed,
+            total_bytes: None,
+            downloaded_bytes: 0,
+            content_type: None,
+            sha256: None,
+            error: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .await
+        .unwrap()
+        .id
+    }
+
+    /// Файлы директории (относительные пути через `/`) с содержимым
+    fn tree(dir: &Path) -> Vec<(String, String)> {
+        let mut files = Vec::new();
+        let mut stack = vec![dir.to_path_buf()];
+        while let Some(current) = stack.pop() {
+            for entry in fs::read_dir(&current).unwrap().flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    stack.push(path);
+                } else {
+                    let name = path.strip_prefix(dir).unwrap().to_string_lossy().replace('\\', "/");
+                    files.push((name, fs::read_to_string(&path).unwrap()));
+                }
+            }
+        }
+        files.sort();
+        files
+    }
+
+    fn entries(files: &[(&str, &str)]) -> Vec<(String, String)> {
+        files.iter().map(|(name, content)| (name.to_string(), content.to_string())).collect()
+    }
+
+    #[tokio::test]
+    async fn mod_is_installed_upgraded_and_uninstalled() {
+        let (dir, db, installer, mod_item, game) = setup().await;
+        fs::write(game.join("user.cfg"), "keep").unwrap();
+
+        let v1 = dir.path().join("trees-1.0.zip");
+        write_zip(&v1, &[("trees/a.txt", "one"), ("trees/old/b.txt", "old")]);
+        let v1_id = completed_download(&db, mod_item.id, &v1, "1.0").await;
+        let installed = installer.install(v1_id, None).await.unwrap();
+        assert_eq!(installed.version.as_deref(), Some("1.0"));
+        assert_eq!(
+            tree(&game),
+            entries(&[("trees/a.txt", "one"), ("trees/old/b.txt", "old"), ("user.cfg", "keep")])
+        );
+
+        let v2 = dir.path().join("trees-2.0.tar.gz");
+        write_tar_gz(&v2, &[("trees/a.txt", "two"), ("trees/c.txt", "new")]);
+        let v2_id = completed_download(&db, mod_item.id, &v2, "2.0").await;
+        let upgraded = installer.install(v2_id, None).await.unwrap();
+        assert_eq!(upgraded.id, installed.id);
+        assert_eq!(upgraded.version.as_deref(), Some("2.0"));
+        assert_eq!(
+            tree(&game),
+            entries(&[("trees/a.txt", "two"), ("trees/c.txt", "new"), ("user.cfg", "keep")])
+        );
+        assert!(!game.join("trees").join("old").exists());
+        let files: Vec<String> = db.get_installed_files(upgraded.id).await.unwrap().into_iter().map(|f| f.path).collect();
+        assert_eq!(files, vec!["trees/a.txt", "trees/c.txt"]);
+
+        installer.uninstall(upgraded.id).await.unwrap();
+        assert_eq!(tree(&game), entries(&[("user.cfg", "keep")]));
+        assert!(!game.join("trees").exists());
+        assert!(db.get_installations(Some(mod_item.id)).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_upgrade_is_rolled_back() {
+        let (dir, db, installer, mod_item, game) = setup().await;
+        let v1 = dir.path().join("trees-1.0.zip");
+        write_zip(&v1, &[("a.txt", "one"), ("b.txt", "old")]);
+        let v1_id = completed_download(&db, mod_item.id, &v1, "1.0").await;
+        let installed = installer.install(v1_id, None).await.unwrap();
+        let before = tree(&game);
+
+        // Архив обрывается посередине: распаковка падает до изменения директории
+        let broken = dir.path().join("trees-2.0.tar.gz");
+        write_tar_gz(&broken, &[("a.txt", &"x".repeat(100_000))]);
+        let data = fs::read(&broken).unwrap();
+        fs::write(&broken, &data[..data.len() / 2]).unwrap();
+        let broken_id = completed_download(&db, mod_item.id, &broken, "2.0").await;
+        assert!(installer.install(broken_id, None).await.is_err());
+        assert_eq!(tree(&game), before);
+
+        // Перенос падает посередине: файл пользователя мешает создать директорию
+        fs::write(game.join("blocker"), "user").unwrap();
+        let v3 = dir.path().join("trees-3.0.zip");
+        write_zip(&v3, &[("a.txt", "three"), ("blocker/c.txt", "new")]);
+        let v3_id = completed_download(&db, mod_item.id, &v3, "3.0").await;
+        assert!(installer.install(v3_id, None).await.is_err());
+
+        assert_eq!(tree(&game), entries(&[("a.txt", "one"), ("b.txt", "old"), ("blocker", "user")]));
+        let leftovers = fs::read_dir(&game)
+            .unwrap()
+            .flatten()
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with(WORK_DIR_PREFIX))
+            .count();
+        assert_eq!(leftovers, 0);
+        let stored = db.get_installation(installed.id).await.unwrap();
+        assert_eq!(stored.version.as_deref(), Some("1.0"));
+        assert_eq!(db.get_installed_files(installed.id).await.unwrap().len(), 2);
+    }
+
+    #[test]
+    fn archive_paths_are_checked() {
+        assert_eq!(safe_relative_path("./mods//a.txt").unwrap(), "mods/a.txt");
+        assert_eq!(safe_relative_path("/abs/a.txt").unwrap(), "abs/a.txt");
+        assert_eq!(safe_relative_path("dir\\b.txt").unwrap(), "dir/b.txt");
+        assert!(safe_relative_path("../a.txt").is_err());
+        assert!(safe_relative_path("mods/../../a.txt").is_err());
+        assert!(safe_relative_path("C:/Windows/a.txt").is_err());
+        assert!(safe_relative_path(".mod-aggregator-backup-1/a.txt").is_err());
+        assert!(safe_relative_path("./").is_err());
+    }
+}
@@ -0,0 +1,378 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use chrono::Utc;
+use log::{info, warn};
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio_util::sync::CancellationToken;
+use crate::models::{Download, DownloadStatus, Job, JobKind, JobStatus};
+use crate::state::AppState;
+
+/// Имя события Tauri с изменением фоновой задачи
+pub const JOB_EVENT: &str = "job-progress";
+
+/// Сколько завершенных задач хранится для `list_jobs`
+const MAX_FINISHED_JOBS: usize = 50;
+
+/// Ошибки работы с фоновыми задачами
+#[derive(Debug, thiserror::Error)]
+pub enum JobError {
+    #[error("Задача {0} не найдена")]
+    NotFound(i64),
+    #[error("Задача {0} уже завершена")]
+    Finished(i64),
+    #[error("Задача отменена")]
+    Cancelled,
+}
+
+tokio::task_local! {
+    /// Задача, в которой выполняется текущий код (см. `report_progress`)
+    static CURRENT_JOB: JobHandle;
+}
+
+/// Сообщить о ходе текущей задачи
+///
+/// Вне `JobManager::run` (например, в CLI) ничего не делает.
+///
+/// # Параметры
+/// * `done` - сколько частей работы выполнено
+/// * `total` - сколько частей всего
+/// * `message` - текущий этап
+pub fn report_progress(done: usize, total: usize, message: impl Into<String>) {
+    let _ = CURRENT_JOB.try_with(|job| job.progress(Some(done as f64 / total.max(1) as f64), Some(message.into())));
+}
+
+struct Entry {
+    job: Job,
+    token: CancellationToken,
+}
+
+/// Менеджер фоновых задач
+///
+/// Долгие операции (проверка обновлений, загрузка страницы, загрузка файла)
+/// выполняются как задачи с ID и токеном отмены. Задачи хранятся только в
+/// памяти; изменения рассылаются подписчикам (`subscribe`), десктопное
+/// приложение пересылает их событием `JOB_EVENT`. Клонирование дешевое: все
+/// клоны работают с одним списком задач.
+#[derive(Clone)]
+pub struct JobManager {
+    jobs: Arc<Mutex<HashMap<i64, Entry>>>,
+    next_id: Arc<AtomicI64>,
+    events: broadcast::Sender<Job>,
+}
+
+impl Default for JobManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JobManager {
+    /// Создать менеджер задач
+    pub fn new() -> Self {
+        let (events, _) = broadcast::channel(256);
+        JobManager {
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            next_id: Arc::new(AtomicI64::new(1)),
+            events,
+        }
+    }
+
+    /// Подписаться на изменения задач
+    pub fn subscribe(&self) -> broadcast::Receiver<Job> {
+        self.events.subscribe()
+    }
+
+    /// Получить задачи: выполняющиеся и последние завершенные
+    ///
+    /// # Возвращает
+    /// Задачи от новых к старым
+    pub fn list(&self) -> Vec<Job> {
+        let mut jobs: Vec<Job> = self.jobs.lock().unwrap().values().map(|entry| entry.job.clone()).collect();
+        jobs.sort_by_key(|job| std::cmp::Reverse(job.id));
+        jobs
+    }
+
+    /// Отменить выполняющуюся задачу
+    ///
+    /// # Параметры
+    /// * `id` - ID задачи
+    ///
+    /// # Возвращает
+    /// Пустой результат или ошибку, если задачи нет или она уже завершена
+    pub fn cancel(&self, id: i64) -> Result<(), JobError> {
+        let jobs = self.jobs.lock().unwrap();
+        let entry = jobs.get(&id).ok_or(JobError::NotFound(id))?;
+        if entry.job.status != JobStatus::Running {
+            return Err(JobError::Finished(id));
+        }
+        info!("Cancelling job {} ({})", id, entry.job.title);
+        entry.token.cancel();
+        Ok(())
+    }
+
+    /// Зарегистрировать выполняющуюся задачу
+    ///
+    /// Задачу нужно завершить через `JobHandle::finish`; для операций внутри
+    /// текущей задачи удобнее `run`.
+    ///
+    /// # Параметры
+    /// * `kind` - тип задачи
+    /// * `title` - описание задачи
+    /// * `download_id` - ID загрузки для задач `download`
+    ///
+    /// # Возвращает
+    /// Handle задачи
+    pub fn start(&self, kind: JobKind, title: impl Into<String>, download_id: Option<i64>) -> JobHandle {
+        let job = Job {
+            id: self.next_id.fetch_add(1, Ordering::SeqCst),
+            kind,
+            title: title.into(),
+            status: JobStatus::Running,
+            progress: None,
+            message: None,
+            error: None,
+            download_id,
+            started_at: Utc::now(),
+            finished_at: None,
+        };
+        let token = CancellationToken::new();
+        let handle = JobHandle {
+            id: job.id,
+            manager: self.clone(),
+            token: token.clone(),
+        };
+        let _ = self.events.send(job.clone());
+        self.jobs.lock().unwrap().insert(job.id, Entry { job, token });
+        handle
+    }
+
+    /// Выполнить операцию как задачу
+    ///
+    /// Операция прерывается (future удаляется) при отмене задачи. Внутри
+    /// операции ход задачи сообщается через `report_progress`.
+    ///
+    /// # Параметры
+    /// * `kind` - тип задачи
+    /// * `title` - описание задачи
+    /// * `task` - операция
+    ///
+    /// # Возвращает
+    /// Результат операции или ошибку `JobError::Cancelled` при отмене
+    pub async fn run<T, F>(&self, kind: JobKind, title: impl Into<String>, task: F) -> Result<T, String>
+    where
+        F: Future<Output = Result<T, String>>,
+    {
+        let handle = self.start(kind, title, None);
+        let token = handle.token.clone();
+        let result = tokio::select! {
+            result = CURRENT_JOB.scope(handle.clone(), task) => result,
+            _ = token.cancelled() => {
+                handle.finish(JobStatus::Cancelled, None);
+                return Err(JobError::Cancelled.to_string());
+            }
+        };
+        match &result {
+            Ok(_) => handle.finish(JobStatus::Completed, None),
+            Err(e) => handle.finish(JobStatus::Failed, Some(e.clone())),
+        }
+        result
+    }
+
+    /// Выполняется ли задача загрузки
+    fn is_tracking_download(&self, download_id: i64) -> bool {
+        self.jobs
+            .lock()
+            .unwrap()
+            .values()
+            .any(|entry| entry.job.download_id == Some(download_id) && entry.job.status == JobStatus::Running)
+    }
+
+    /// Изменить задачу и разослать ее подписчикам
+    fn update(&self, id: i64, change: impl FnOnce(&mut Job)) {
+        let mut jobs = self.jobs.lock().unwrap();
+        let Some(entry) = jobs.get_mut(&id) else {
+            return;
+        };
+        if entry.job.status != JobStatus::Running {
+            return;
+        }
+        change(&mut entry.job);
+        let _ = self.events.send(entry.job.clone());
+
+        // Старые завершенные задачи удаляются из списка
+        let mut finished: Vec<i64> = jobs
+            .values()
+            .filter(|entry| entry.job.status != JobStatus::Running)
+            .map(|entry| entry.job.id)
+            .collect();
+        if finished.len() > MAX_FINISHED_JOBS {
+            finished.sort_unstable();
+            for id in &finished[..finished.len() - MAX_FINISHED_JOBS] {
+                jobs.remove(id);
+            }
+        }
+    }
+}
+
+/// Handle выполняющейся задачи
+#[derive(Clone)]
+pub struct JobHandle {
+    id: i64,
+    manager: JobManager,
+    token: CancellationToken,
+}
+
+impl JobHandle {
+    /// ID задачи
+    pub fn id(&self) -> i64 {
+        self.id
+    }
+
+    /// Запрошена ли отмена задачи
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Дождаться отмены задачи
+    pub async fn cancelled(&self) {
+        self.token.cancelled().await
+    }
+
+    /// Сообщить о ходе задачи
+    ///
+    /// # Параметры
+    /// * `progress` - доля выполненной работы (0.0 - 1.0)
+    /// * `message` - текущий этап (None = без изменений)
+    pub fn progress(&self, progress: Option<f64>, message: Option<String>) {
+        self.manager.update(self.id, |job| {
+            job.progress = progress.map(|p| p.clamp(0.0, 1.0));
+            if message.is_some() {
+                job.message = message;
+            }
+        });
+    }
+
+    /// Завершить задачу (повторное завершение ничего не делает)
+    ///
+    /// # Параметры
+    /// * `status` - итоговое состояние
+    /// * `error` - текст ошибки для `failed`
+    pub fn finish(&self, status: JobStatus, error: Option<String>) {
+        self.manager.update(self.id, |job| {
+            if status == JobStatus::Completed {
+                job.progress = Some(1.0);
+            }
+            job.status = status;
+            job.error = error;
+            job.finished_at = Some(Utc::now());
+        });
+    }
+}
+
+/// Отслеживать загрузку файла как задачу
+///
+/// Задача повторяет прогресс загрузки и завершается вместе с ней; отмена
+/// задачи отменяет загрузку. Для уже отслеживаемой загрузки ничего не делает.
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+/// * `download` - начатая или продолженная загрузка
+pub fn track_download(state: &AppState, download: &Download) {
+    if state.jobs.is_tracking_download(download.id) {
+        return;
+    }
+    let job = state.jobs.start(JobKind::Download, format!("Загрузка {}", download.file_name), Some(download.id));
+    // Подписка до чтения состояния, чтобы не пропустить завершение
+    let mut events = state.downloads.subscribe();
+    let (downloads, db, id) = (state.downloads.clone(), state.db.clone(), download.id);
+
+    tokio::spawn(async move {
+        let mut current = db.get_download(id).await.ok();
+        loop {
+            if let Some(download) = current.take() {
+                if let Some(status) = download_job_status(download.status) {
+                    job.finish(status, download.error);
+                    return;
+                }
+                let progress = download
+                    .total_bytes
+                    .filter(|total| *total > 0)
+                    .map(|total| download.downloaded_bytes as f64 / total as f64);
+                job.progress(progress, None);
+            }
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(download) if download.id == id => current = Some(download),
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(_)) => current = db.get_download(id).await.ok(),
+                    Err(RecvError::Closed) => return,
+                },
+                _ = job.cancelled() => {
+                    if let Err(e) = downloads.cancel(id).await {
+                        warn!("Failed to cancel download {} of job {}: {}", id, job.id(), e);
+                    }
+                    job.finish(JobStatus::Cancelled, None);
+                    return;
+                }
+            }
+        }
+    });
+}
+
+/// Состояние задачи для завершившейся или приостановленной загрузки
+fn download_job_status(status: DownloadStatus) -> Option<JobStatus> {
+    match status {
+        DownloadStatus::Queued | DownloadStatus::Downloading => None,
+        DownloadStatus::Paused => Some(JobStatus::Paused),
+        DownloadStatus::Completed => Some(JobStatus::Completed),
+        DownloadStatus::Failed => Some(JobStatus::Failed),
+        DownloadStatus::Cancelled => Some(JobStatus::Cancelled),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn jobs_report_progress_and_can_be_cancelled() {
+        let jobs = JobManager::new();
+        let mut events = jobs.subscribe();
+
+        let result = jobs
+            .run(JobKind::CheckUpdates, "Проверка", async {
+                report_progress(1, 2, "Сайт 1");
+                Ok(5)
+            })
+            .await;
+        assert_eq!(result, Ok(5));
+        assert_eq!(events.recv().await.unwrap().status, JobStatus::Running);
+        let progress = events.recv().await.unwrap();
+        assert_eq!((progress.progress, progress.message.as_deref()), (Some(0.5), Some("Сайт 1")));
+        assert_eq!(events.try_recv().unwrap().status, JobStatus::Completed);
+
+        let running = tokio::spawn({
+            let jobs = jobs.clone();
+            async move {
+                jobs.run(JobKind::FetchPage, "Страница", async {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                    Ok(())
+                })
+                .await
+            }
+        });
+        let started = events.recv().await.unwrap();
+        assert_eq!(jobs.list()[0].status, JobStatus::Running);
+        jobs.cancel(started.id).unwrap();
+
+        let result = tokio::time::timeout(Duration::from_secs(5), running).await.unwrap().unwrap();
+        assert_eq!(result, Err("Задача отменена".to_string()));
+        assert_eq!(jobs.list()[0].status, JobStatus::Cancelled);
+        assert!(matches!(jobs.cancel(started.id), Err(JobError::Finished(_))));
+        assert!(matches!(jobs.cancel(100), Err(JobError::NotFound(100))));
+    }
+}
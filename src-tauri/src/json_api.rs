@@ -0,0 +1,547 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use chrono::Utc;
+use crate::models::Mod;
+
+/// Максимальное количество страниц по умолчанию
+fn default_max_pages() -> usize {
+    10
+}
+
+/// Номер первой страницы по умолчанию
+fn default_start_page() -> u64 {
+    1
+}
+
+/// HTTP метод запроса к API
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum HttpMethod {
+    #[default]
+    #[serde(alias = "get")]
+    Get,
+    #[serde(alias = "post")]
+    Post,
+}
+
+/// Пути к полям мода внутри элемента списка
+///
+/// Пути задаются относительно элемента (`name`, `$.links.html`,
+/// `files[0].version`). Обязателен только `url`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsonFields {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub changes: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+}
+
+/// Постраничная загрузка JSON API
+///
+/// Значение курсора или номера страницы передается в query параметре
+/// `param`, а для POST запросов с JSON объектом в `body` - в поле тела
+/// с тем же именем. Обход ограничен `max_pages` и останавливается, если
+/// страница не дала ни одного нового URL мода.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum JsonPagination {
+    /// Курсор следующей страницы из ответа (`cursor_path`), конец - пустой курсор
+    Cursor {
+        cursor_path: String,
+        param: String,
+        #[serde(default = "default_max_pages")]
+        max_pages: usize,
+    },
+    /// Номер страницы; `total_pages_path` - необязательный путь к числу страниц в ответе
+    Page {
+        param: String,
+        #[serde(default = "default_start_page")]
+        start_page: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        total_pages_path: Option<String>,
+        #[serde(default = "default_max_pages")]
+        max_pages: usize,
+    },
+}
+
+/// Конфигурация источника JSON API
+///
+/// Задается как `{"type": "json_api", ...}` в ключе `source` конфигурации
+/// парсера. Запрос отправляется на `url` (по умолчанию `list_url` или URL
+/// сайта), список модов выбирается путем `items`, поля мода - путями `fields`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonApiConfig {
+    /// URL запроса
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// HTTP метод (GET или POST)
+    #[serde(default)]
+    pub method: HttpMethod,
+    /// Дополнительные заголовки запроса
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// JSON тело запроса (только для POST)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Value>,
+    /// Путь к списку модов в ответе, например `$.data.mods[*]`
+    pub items: String,
+    /// Пути к полям мода внутри элемента списка
+    pub fields: JsonFields,
+    /// Постраничная загрузка
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<JsonPagination>,
+}
+
+/// Сегмент пути к значению JSON
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    /// Ключ объекта: `.name` или `['name']`
+    Key(String),
+    /// Индекс массива: `[0]`, `[-1]` (с конца)
+    Index(i64),
+    /// Все элементы массива или значения объекта: `[*]`, `.*`
+    Wildcard,
+}
+
+/// Путь к значениям JSON в стиле JSONPath
+///
+/// Поддерживается подмножество: необязательный корень `$`, ключи через точку,
+/// `['ключ']`, индексы (`[0]`, `[-1]`) и `*`/`[*]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath {
+    segments: Vec<PathSegment>,
+}
+
+impl JsonPath {
+    /// Разобрать путь
+    ///
+    /// # Параметры
+    /// * `path` - путь, например `$.data.items[*].name`
+    ///
+    /// # Возвращает
+    /// Разобранный путь или ошибку с описанием позиции
+    pub fn parse(path: &str) -> Result<Self, String> {
+        let path = path.trim();
+        let rest = path.strip_prefix('$').unwrap_or(path);
+        let chars: Vec<char> = rest.chars().collect();
+        let mut segments = Vec::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            match chars[i] {
+                '.' => {
+                    i += 1;
+                    let start = i;
+                    while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                        i += 1;
+                    }
+                    segments.push(key_segment(&chars[start..i], path)?);
+                }
+                '[' => {
+                    let end = chars[i..]
+                        .iter()
+                        .position(|c| *c == ']')
+                        .map(|offset| i + offset)
+                        .ok_or_else(|| format!("Unclosed '[' in path '{}'", path))?;
+                    let inner: String = chars[i + 1..end].iter().collect();
+                    let inner = inner.trim();
+                    let segment = if inner == "*" {
+                        PathSegment::Wildcard
+                    } else if let Some(key) = quoted(inner) {
+                        PathSegment::Key(key.to_string())
+                    } else {
+                        inner
+                            .parse()
+                            .map(PathSegment::Index)
+                            .map_err(|_| format!("Invalid index '{}' in path '{}'", inner, path))?
+                    };
+                    segments.push(segment);
+                    i = end + 1;
+                }
+                // Путь без `$` начинается сразу с ключа: `data.items`
+                _ if i == 0 => {
+                    while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                        i += 1;
+                    }
+                    segments.push(key_segment(&chars[..i], path)?);
+                }
+                c => return Err(format!("Unexpected '{}' in path '{}'", c, path)),
+            }
+        }
+
+        Ok(JsonPath { segments })
+    }
+
+    /// Выбрать все значения по пути
+    ///
+    /// # Параметры
+    /// * `value` - корневое значение
+    ///
+    /// # Возвращает
+    /// Найденные значения (пустой вектор, если путь не найден)
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut current = vec![value];
+        for segment in &self.segments {
+            current = current
+                .into_iter()
+                .flat_map(|value| -> Vec<&Value> {
+                    match (segment, value) {
+                        (PathSegment::Key(key), Value::Object(map)) => map.get(key).into_iter().collect(),
+                        (PathSegment::Index(index), Value::Array(items)) => {
+                            let index = if *index < 0 { items.len() as i64 + index } else { *index };
+                            usize::try_from(index).ok().and_then(|i| items.get(i)).into_iter().collect()
+                        }
+                        (PathSegment::Wildcard, Value::Array(items)) => items.iter().collect(),
+                        (PathSegment::Wildcard, Value::Object(map)) => map.values().collect(),
+                        _ => Vec::new(),
+                    }
+                })
+                .collect();
+        }
+        current
+    }
+
+    /// Получить первое значение по пути в виде текста
+    ///
+    /// # Параметры
+    /// * `value` - корневое значение
+    ///
+    /// # Возвращает
+    /// Строку, число или логическое значение как текст; None для null, пустых
+    /// строк, объектов и массивов
+    pub fn select_text(&self, value: &Value) -> Option<String> {
+        match self.select(value).into_iter().next()? {
+            Value::String(text) => Some(text.trim().to_string()).filter(|t| !t.is_empty()),
+            Value::Number(number) => Some(number.to_string()),
+            Value::Bool(flag) => Some(flag.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Сегмент-ключ после точки (`*` - все значения)
+fn key_segment(chars: &[char], path: &str) -> Result<PathSegment, String> {
+    let key: String = chars.iter().collect();
+    match key.as_str() {
+        "" => Err(format!("Empty key in path '{}'", path)),
+        "*" => Ok(PathSegment::Wildcard),
+        _ => Ok(PathSegment::Key(key)),
+    }
+}
+
+/// Строка в одинарных или двойных кавычках без кавычек
+fn quoted(text: &str) -> Option<&str> {
+    text.strip_prefix('\'')
+        .and_then(|t| t.strip_suffix('\''))
+        .or_else(|| text.strip_prefix('"').and_then(|t| t.strip_suffix('"')))
+}
+
+/// Разобранные пути полей мода
+struct FieldPaths {
+    title: Option<JsonPath>,
+    url: JsonPath,
+    version: Option<JsonPath>,
+    author: Option<JsonPath>,
+    image: Option<JsonPath>,
+    description: Option<JsonPath>,
+    changes: Option<JsonPath>,
+    download_url: Option<JsonPath>,
+}
+
+/// Подготовленный источник JSON API (пути разобраны один раз на сайт)
+pub struct JsonApiSource {
+    config: JsonApiConfig,
+    items: JsonPath,
+    fields: FieldPaths,
+    next_cursor: Option<JsonPath>,
+    total_pages: Option<JsonPath>,
+}
+
+impl JsonApiConfig {
+    /// Проверить конфигурацию и разобрать пути
+    ///
+    /// # Возвращает
+    /// Подготовленный источник или ошибку с именем некорректного поля
+    pub fn prepare(&self) -> Result<JsonApiSource, String> {
+        let path = |name: &str, path: &str| JsonPath::parse(path).map_err(|e| format!("{}: {}", name, e));
+        let optional = |name: &str, value: &Option<String>| value.as_deref().map(|p| path(name, p)).transpose();
+
+        if let Some(url) = &self.url {
+            url::Url::parse(url).map_err(|e| format!("url: invalid URL: {}", e))?;
+        }
+        if self.body.is_some() && self.method == HttpMethod::Get {
+            return Err("body: request body requires method POST".to_string());
+        }
+
+        let fields = &self.fields;
+        let (next_cursor, total_pages) = match &self.pagination {
+            Some(JsonPagination::Cursor { cursor_path, .. }) => (Some(path("cursor_path", cursor_path)?), None),
+            Some(JsonPagination::Page { total_pages_path, .. }) => (None, optional("total_pages_path", total_pages_path)?),
+            None => (None, None),
+        };
+
+        Ok(JsonApiSource {
+            config: self.clone(),
+            items: path("items", &self.items)?,
+            fields: FieldPaths {
+                title: optional("fields.title", &fields.title)?,
+                url: path("fields.url", &fields.url)?,
+                version: optional("fields.version", &fields.version)?,
+                author: optional("fields.author", &fields.author)?,
+                image: optional("fields.image", &fields.image)?,
+                description: optional("fields.description", &fields.description)?,
+                changes: optional("fields.changes", &fields.changes)?,
+                download_url: optional("fields.download_url", &fields.download_url)?,
+            },
+            next_cursor,
+            total_pages,
+        })
+    }
+}
+
+/// Позиция постраничной загрузки
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PageParam {
+    /// Первая страница без параметра (курсор еще неизвестен)
+    First,
+    /// Значение параметра пагинации
+    Value(String),
+}
+
+impl JsonApiSource {
+    /// URL запроса
+    ///
+    /// # Параметры
+    /// * `list_url` - URL списка из конфигурации сайта (если `url` не задан)
+    pub fn request_url<'a>(&'a self, list_url: &'a str) -> &'a str {
+        self.config.url.as_deref().unwrap_or(list_url)
+    }
+
+    /// Максимальное количество загружаемых страниц
+    pub fn max_pages(&self) -> usize {
+        match &self.config.pagination {
+            Some(JsonPagination::Cursor { max_pages, .. }) | Some(JsonPagination::Page { max_pages, .. }) => {
+                (*max_pages).max(1)
+            }
+            None => 1,
+        }
+    }
+
+    /// Параметр первой страницы
+    pub fn first_page(&self) -> PageParam {
+        match &self.config.pagination {
+            Some(JsonPagination::Page { start_page, .. }) => PageParam::Value(start_page.to_string()),
+            _ => PageParam::First,
+        }
+    }
+
+    /// Параметр следующей страницы
+    ///
+    /// # Параметры
+    /// * `response` - ответ API на текущую страницу
+    /// * `current` - параметр текущей страницы
+    ///
+    /// # Возвращает
+    /// Параметр следующей страницы или None, если страниц больше нет
+    pub fn next_page(&self, response: &Value, current: &PageParam) -> Option<PageParam> {
+        match &self.config.pagination {
+            Some(JsonPagination::Cursor { .. }) => {
+                let cursor = self.next_cursor.as_ref()?.select_text(response)?;
+                // Курсор не изменился - API вернул последнюю страницу повторно
+                (PageParam::Value(cursor.clone()) != *current).then_some(PageParam::Value(cursor))
+            }
+            Some(JsonPagination::Page { .. }) => {
+                let PageParam::Value(page) = current else { return None };
+                let page: u64 = page.parse().ok()?;
+                let total = self
+                    .total_pages
+                    .as_ref()
+                    .and_then(|path| path.select_text(response))
+                    .and_then(|total| total.parse::<u64>().ok());
+                match total {
+                    Some(total) if page >= total => None,
+                    _ => Some(PageParam::Value((page + 1).to_string())),
+                }
+            }
+            None => None,
+        }
+    }
+
+    /// Построить HTTP запрос страницы
+    ///
+    /// # Параметры
+    /// * `client` - HTTP клиент
+    /// * `url` - URL запроса
+    /// * `page` - параметр страницы
+    ///
+    /// # Возвращает
+    /// Запрос, готовый к отправке
+    pub fn request(&self, client: &reqwest::Client, url: &str, page: &PageParam) -> reqwest::RequestBuilder {
+        let param = match (&self.config.pagination, page) {
+            (Some(JsonPagination::Cursor { param, .. }), PageParam::Value(value))
+            | (Some(JsonPagination::Page { param, .. }), PageParam::Value(value)) => Some((param, value)),
+            _ => None,
+        };
+
+        let mut request = match self.config.method {
+            HttpMethod::Get => client.get(url),
+            HttpMethod::Post => client.post(url),
+        };
+        for (name, value) in &self.config.headers {
+            request = request.header(name, value);
+        }
+
+        match (&self.config.body, param) {
+            (Some(Value::Object(body)), Some((param, value))) => {
+                let mut body = body.clone();
+                body.insert(param.clone(), Value::String(value.clone()));
+                request.json(&body)
+            }
+            (body, param) => {
+                if let Some((param, value)) = param {
+                    request = request.query(&[(param, value)]);
+                }
+                match body {
+                    Some(body) => request.json(body),
+                    None => request,
+                }
+            }
+        }
+    }
+
+    /// Извлечь моды из ответа API
+    ///
+    /// # Параметры
+    /// * `response` - JSON ответ
+    /// * `base_url` - URL для относительных ссылок
+    ///
+    /// # Возвращает
+    /// Вектор модов (site_id проставляет вызывающая сторона); элементы без URL пропускаются
+    pub fn extract(&self, response: &Value, base_url: &str) -> Vec<Mod> {
+        let base = url::Url::parse(base_url).ok();
+        let resolve = |href: String| match &base {
+            Some(base) => base.join(&href).map(String::from).unwrap_or(href),
+            None => href,
+        };
+        let fields = &self.fields;
+        let text = |path: &Option<JsonPath>, item: &Value| path.as_ref().and_then(|p| p.select_text(item));
+
+        self.items
+            .select(response)
+            .into_iter()
+            .filter_map(|item| {
+                let url = resolve(fields.url.select_text(item)?);
+                Some(Mod {
+                    id: 0,
+                    site_id: 0,
+                    title: text(&fields.title, item).unwrap_or_else(|| "Unknown".to_string()),
+                    url,
+                    version: text(&fields.version, item),
+                    author: text(&fields.author, item),
+                    description: text(&fields.description, item),
+                    image_url: text(&fields.image, item).map(resolve),
+                    changes: text(&fields.changes, item),
+                    download_url: text(&fields.download_url, item).map(resolve),
+                    files: Vec::new(),
+                    followed: false,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn paths_select_nested_values() {
+        let value = json!({
+            "data": {"mods": [
+                {"name": "A", "files": [{"v": "1.0"}, {"v": "1.1"}], "id": 7},
+                {"name": "B", "files": [], "id": 8}
+            ]},
+            "meta": {"next-cursor": "abc"}
+        });
+
+        let names: Vec<_> = JsonPath::parse("$.data.mods[*].name").unwrap().select(&value);
+        assert_eq!(names, vec![&json!("A"), &json!("B")]);
+        assert_eq!(JsonPath::parse("data.mods[0].files[-1].v").unwrap().select_text(&value).as_deref(), Some("1.1"));
+        assert_eq!(JsonPath::parse("$.meta['next-cursor']").unwrap().select_text(&value).as_deref(), Some("abc"));
+        assert_eq!(JsonPath::parse("$.data.mods[1].id").unwrap().select_text(&value).as_deref(), Some("8"));
+        assert!(JsonPath::parse("$.data.mods[5].name").unwrap().select(&value).is_empty());
+
+        assert!(JsonPath::parse("$.data[").is_err());
+        assert!(JsonPath::parse("$.data..mods").is_err());
+        assert!(JsonPath::parse("$.data[x]").is_err());
+    }
+
+    #[test]
+    fn items_are_mapped_to_mods() {
+        let config: JsonApiConfig = serde_json::from_value(json!({
+            "items": "$.results[*]",
+            "fields": {
+                "title": "name",
+                "url": "links.html",
+                "version": "$.latest.version",
+                "author": "owner.login",
+                "image": "icon"
+            },
+            "pagination": {"mode": "cursor", "cursor_path": "$.next", "param": "after"}
+        }))
+        .unwrap();
+        let source = config.prepare().unwrap();
+        let response = json!({
+            "results": [
+                {"name": "Trees", "links": {"html": "/mods/trees"}, "latest": {"version": 3}, "owner": {"login": "alice"}, "icon": "/i/t.png"},
+                {"name": "No URL"}
+            ],
+            "next": "c2"
+        });
+
+        let mods = source.extract(&response, "https://api.example.com/v1/mods");
+        assert_eq!(mods.len(), 1);
+        assert_eq!(mods[0].title, "Trees");
+        assert_eq!(mods[0].url, "https://api.example.com/mods/trees");
+        assert_eq!(mods[0].version.as_deref(), Some("3"));
+        assert_eq!(mods[0].author.as_deref(), Some("alice"));
+        assert_eq!(mods[0].image_url.as_deref(), Some("https://api.example.com/i/t.png"));
+
+        assert_eq!(source.first_page(), PageParam::First);
+        let next = source.next_page(&response, &PageParam::First);
+        assert_eq!(next, Some(PageParam::Value("c2".to_string())));
+        assert_eq!(source.next_page(&response, &next.unwrap()), None);
+        assert_eq!(source.next_page(&json!({"next": null}), &PageParam::First), None);
+    }
+
+    #[test]
+    fn page_pagination_stops_at_total() {
+        let config: JsonApiConfig = serde_json::from_value(json!({
+            "items": "items",
+            "fields": {"url": "url"},
+            "pagination": {"mode": "page", "param": "page", "total_pages_path": "pages"}
+        }))
+        .unwrap();
+        let source = config.prepare().unwrap();
+
+        let first = source.first_page();
+        assert_eq!(first, PageParam::Value("1".to_string()));
+        assert_eq!(source.next_page(&json!({"pages": 2}), &first), Some(PageParam::Value("2".to_string())));
+        assert_eq!(source.next_page(&json!({"pages": 2}), &PageParam::Value("2".to_string())), None);
+    }
+}
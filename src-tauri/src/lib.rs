@@ -0,0 +1,33 @@
+// Библиотека backend: база данных, парсинг и проверка обновлений.
+// Используется десктопным приложением (main.rs) и консольной утилитой
+// (bin/mod-aggregator-cli.rs). Не зависит от Tauri.
+
+pub mod changes;
+pub mod check_queue;
+pub mod check_runs;
+pub mod database;
+pub mod db_path;
+pub mod detail;
+pub mod downloads;
+pub mod drift;
+pub mod feed;
+pub mod installer;
+pub mod jobs;
+pub mod json_api;
+pub mod migrations;
+pub mod models;
+pub mod pagination;
+pub mod parser;
+pub mod parser_builder;
+pub mod parser_graph;
+pub mod parsers;
+pub mod profiles;
+pub mod releases;
+pub mod scheduler;
+pub mod search;
+pub mod site_config;
+pub mod source;
+pub mod state;
+pub mod updates;
+#[cfg(test)]
+mod test_support;
@@ -1,21 +1,55 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-mod database;
-mod parser;
-mod parser_builder;
 mod notification;
-mod models;
 
-// mod parsers;
-
-use database::Database;
-use parser::ParserEngine;
-use parser_builder::ParserBuilder;
+use mod_aggregator::{changes, check_queue, check_runs, db_path, downloads, jobs, migrations, models, profiles, scheduler, site_config, updates};
+use mod_aggregator::parser_builder::ParserBuilder;
+use mod_aggregator::search::{SearchFilters, SearchResults, SearchSort, DEFAULT_SEARCH_LIMIT};
+use mod_aggregator::state::AppState;
 use notification::NotificationService;
 use log::{info, warn, error, debug};
 use chrono::Utc;
 
+/// Пересылает события канала во frontend событием Tauri
+///
+/// # Параметры
+/// * `app_handle` - приложение
+/// * `events` - подписка на канал событий
+/// * `event` - имя события Tauri
+fn forward_events<T: serde::Serialize + Clone + Send + 'static>(
+    app_handle: tauri::AppHandle,
+    mut events: tokio::sync::broadcast::Receiver<T>,
+    event: &'static str,
+) {
+    tauri::async_runtime::spawn(async move {
+        use tauri::Emitter;
+        while let Some(payload) = next_event(&mut events, event).await {
+            if let Err(e) = app_handle.emit(event, &payload) {
+                warn!("Failed to emit {} event: {}", event, e);
+            }
+        }
+    });
+}
+
+/// Следующее событие канала
+///
+/// Пропущенные из-за переполнения канала события не останавливают пересылку:
+/// они только записываются в лог.
+///
+/// # Возвращает
+/// Событие или None, если канал закрыт
+async fn next_event<T: Clone>(events: &mut tokio::sync::broadcast::Receiver<T>, name: &str) -> Option<T> {
+    use tokio::sync::broadcast::error::RecvError;
+    loop {
+        match events.recv().await {
+            Ok(event) => return Some(event),
+            Err(RecvError::Lagged(skipped)) => warn!("Skipped {} {} events", skipped, name),
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
+
 /// Извлекает data-base-url из HTML
 /// 
 /// Ищет атрибут data-base-url в тегах html или body
@@ -58,12 +92,14 @@ fn normalize_url_for_comparison(url: &str) -> String {
 
 /// Получить список всех сайтов из базы данных
 /// 
+/// Секреты конфигурации парсера (токен источника релизов) скрыты.
+/// 
 /// # Возвращает
 /// Вектор всех сайтов, отсортированных по имени, или ошибку
 #[tauri::command]
-async fn get_sites() -> Result<Vec<models::Site>, String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    db.get_sites().await.map_err(|e| e.to_string())
+async fn get_sites(state: tauri::State<'_, AppState>) -> Result<Vec<models::Site>, String> {
+    let sites = state.db.get_sites().await.map_err(|e| e.to_string())?;
+    Ok(sites.into_iter().map(site_config::redact_site).collect())
 }
 
 /// Добавить новый сайт в базу данных
@@ -74,11 +110,14 @@ async fn get_sites() -> Result<Vec<models::Site>, String> {
 /// * `parser_config` - конфигурация парсера в формате JSON
 /// 
 /// # Возвращает
-/// Созданный сайт или ошибку
+/// Созданный сайт (со скрытыми секретами) или ошибку (для конфигурации -
+/// список ошибок по полям)
 #[tauri::command]
-async fn add_site(name: String, url: String, parser_config: serde_json::Value) -> Result<models::Site, String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    db.add_site(&name, &url, &parser_config).await.map_err(|e| e.to_string())
+async fn add_site(state: tauri::State<'_, AppState>, name: String, url: String, parser_config: serde_json::Value) -> Result<models::Site, String> {
+    let parser_config = site_config::validate_for_site(&state.parsers, &url, &parser_config)
+        .map_err(|e| e.to_string())?;
+    let site = state.db.add_site(&name, &url, &parser_config).await.map_err(|e| e.to_string())?;
+    Ok(site_config::redact_site(site))
 }
 
 /// Обновить существующий сайт в базе данных
@@ -87,14 +126,40 @@ async fn add_site(name: String, url: String, parser_config: serde_json::Value) -
 /// * `id` - идентификатор сайта для обновления
 /// * `name` - новое название сайта
 /// * `url` - новый URL сайта
-/// * `parser_config` - новая конфигурация парсера в формате JSON
+/// * `parser_config` - новая конфигурация парсера в формате JSON (скрытые
+///   секреты из `get_sites` заменяются сохраненными)
 /// 
 /// # Возвращает
-/// Пустой результат при успехе или ошибку
+/// Пустой результат при успехе или ошибку (для конфигурации - список ошибок по полям)
+#[tauri::command]
+async fn update_site(state: tauri::State<'_, AppState>, id: i64, name: String, url: String, parser_config: serde_json::Value) -> Result<(), String> {
+    let stored = state.db.get_site(id).await.map_err(|e| e.to_string())?;
+    let parser_config = site_config::restore_secrets(&parser_config, &stored.parser_config);
+    let parser_config = site_config::validate_for_site(&state.parsers, &url, &parser_config)
+        .map_err(|e| e.to_string())?;
+    state.db.update_site(id, &name, &url, &parser_config).await.map_err(|e| e.to_string())
+}
+
+/// Проверить конфигурацию парсера без сохранения
+/// 
+/// Используется формой сайта, чтобы подсветить ошибочные поля.
+/// 
+/// # Параметры
+/// * `url` - URL сайта (для выбора нативного парсера)
+/// * `parser_config` - конфигурация парсера в формате JSON
+/// 
+/// # Возвращает
+/// Список ошибок по полям (пустой, если конфигурация корректна)
 #[tauri::command]
-async fn update_site(id: i64, name: String, url: String, parser_config: serde_json::Value) -> Result<(), String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    db.update_site(id, &name, &url, &parser_config).await.map_err(|e| e.to_string())
+async fn validate_parser_config(
+    state: tauri::State<'_, AppState>,
+    url: String,
+    parser_config: serde_json::Value,
+) -> Result<Vec<site_config::FieldError>, String> {
+    Ok(site_config::validate_for_site(&state.parsers, &url, &parser_config)
+        .err()
+        .map(|e| e.errors)
+        .unwrap_or_default())
 }
 
 /// Удалить сайт из базы данных
@@ -105,9 +170,8 @@ async fn update_site(id: i64, name: String, url: String, parser_config: serde_js
 /// # Возвращает
 /// Пустой результат при успехе или ошибку
 #[tauri::command]
-async fn delete_site(id: i64) -> Result<(), String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    db.delete_site(id).await.map_err(|e| e.to_string())
+async fn delete_site(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    state.db.delete_site(id).await.map_err(|e| e.to_string())
 }
 
 /// Получить список модов из базы данных
@@ -118,15 +182,420 @@ async fn delete_site(id: i64) -> Result<(), String> {
 /// # Возвращает
 /// Вектор модов, отсортированных по дате обновления, или ошибку
 #[tauri::command]
-async fn get_mods(site_id: Option<i64>) -> Result<Vec<models::Mod>, String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    db.get_mods(site_id).await.map_err(|e| e.to_string())
+async fn get_mods(state: tauri::State<'_, AppState>, site_id: Option<i64>) -> Result<Vec<models::Mod>, String> {
+    state.db.get_mods(site_id).await.map_err(|e| e.to_string())
+}
+
+/// Получить страницу списка модов
+/// 
+/// # Параметры
+/// * `site_id` - ID сайта для фильтрации (None = все сайты)
+/// * `sort` - поле сортировки: `title`, `author`, `created`, `updated` (по умолчанию)
+/// * `direction` - `asc` или `desc` (по умолчанию по алфавиту для текста, сначала новые для дат)
+/// * `limit` - размер страницы (по умолчанию 50, не больше 500)
+/// * `offset` - смещение от начала списка
+/// 
+/// # Возвращает
+/// Моды страницы и общее количество модов или ошибку
+#[tauri::command]
+async fn get_mods_page(
+    state: tauri::State<'_, AppState>,
+    site_id: Option<i64>,
+    sort: Option<models::ModSort>,
+    direction: Option<models::SortDirection>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<models::ModPage, String> {
+    let sort = sort.unwrap_or_default();
+    state
+        .db
+        .get_mods_page(
+            site_id,
+            sort,
+            direction.unwrap_or_else(|| sort.default_direction()),
+            limit.unwrap_or(models::ModPage::DEFAULT_PAGE_SIZE),
+            offset.unwrap_or(0),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Полнотекстовый поиск модов
+/// 
+/// # Параметры
+/// * `query` - строка поиска (пустая - все моды по фильтрам)
+/// * `filters` - фильтры по сайту, автору, дате обновления и подписке
+/// * `sort` - порядок: `relevance` (по умолчанию), `updated`, `created`, `title`
+/// * `limit` - размер страницы (по умолчанию 50, не больше 200)
+/// * `offset` - смещение от начала результатов
+/// 
+/// # Возвращает
+/// Найденные моды со сниппетами совпадений и общее количество или ошибку
+#[tauri::command]
+async fn search_mods(
+    state: tauri::State<'_, AppState>,
+    query: Option<String>,
+    filters: Option<SearchFilters>,
+    sort: Option<SearchSort>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<SearchResults, String> {
+    state
+        .db
+        .search_mods(
+            query.as_deref().unwrap_or_default(),
+            &filters.unwrap_or_default(),
+            sort.unwrap_or_default(),
+            limit.unwrap_or(DEFAULT_SEARCH_LIMIT),
+            offset.unwrap_or(0),
+        )
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Получить историю версий мода
+/// 
+/// # Параметры
+/// * `mod_id` - ID мода
+/// 
+/// # Возвращает
+/// Записи истории версий (новые первыми) или ошибку
+#[tauri::command]
+async fn get_mod_history(state: tauri::State<'_, AppState>, mod_id: i64) -> Result<Vec<models::ModVersion>, String> {
+    state.db.get_mod_history(mod_id).await.map_err(|e| e.to_string())
+}
+
+/// Сравнить две записи истории версий мода
+/// 
+/// # Параметры
+/// * `from_version_id` - ID более ранней записи истории
+/// * `to_version_id` - ID более поздней записи истории
+/// 
+/// # Возвращает
+/// Измененные поля и характер изменения версии или ошибку
+#[tauri::command]
+async fn get_mod_version_diff(
+    state: tauri::State<'_, AppState>,
+    from_version_id: i64,
+    to_version_id: i64,
+) -> Result<models::ModVersionDiff, String> {
+    let from = state.db.get_mod_version(from_version_id).await.map_err(|e| e.to_string())?;
+    let to = state.db.get_mod_version(to_version_id).await.map_err(|e| e.to_string())?;
+    if from.mod_id != to.mod_id {
+        return Err("Записи истории относятся к разным модам".to_string());
+    }
+    Ok(changes::diff_versions(from, to))
+}
+
+/// Начать загрузку файла мода
+/// 
+/// Прогресс сообщается событием `download-progress` с объектом загрузки;
+/// загрузка также отслеживается задачей `download` (см. `list_jobs`).
+/// 
+/// # Параметры
+/// * `mod_id` - ID мода
+/// * `url` - ссылка на файл мода (None = основная ссылка на скачивание)
+/// 
+/// # Возвращает
+/// Созданную или продолженную загрузку или ошибку
+#[tauri::command]
+async fn start_download(
+    state: tauri::State<'_, AppState>,
+    mod_id: i64,
+    url: Option<String>,
+) -> Result<models::Download, String> {
+    let download = state.downloads.start(mod_id, url).await.map_err(|e| e.to_string())?;
+    jobs::track_download(&state, &download);
+    Ok(download)
+}
+
+/// Приостановить загрузку
+/// 
+/// # Параметры
+/// * `id` - ID загрузки
+#[tauri::command]
+async fn pause_download(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    state.downloads.pause(id).map_err(|e| e.to_string())
+}
+
+/// Продолжить приостановленную или завершившуюся ошибкой загрузку
+/// 
+/// # Параметры
+/// * `id` - ID загрузки
+/// 
+/// # Возвращает
+/// Загрузку или ошибку
+#[tauri::command]
+async fn resume_download(state: tauri::State<'_, AppState>, id: i64) -> Result<models::Download, String> {
+    let download = state.downloads.resume(id).await.map_err(|e| e.to_string())?;
+    jobs::track_download(&state, &download);
+    Ok(download)
+}
+
+/// Отменить загрузку и удалить частично загруженный файл
+/// 
+/// # Параметры
+/// * `id` - ID загрузки
+#[tauri::command]
+async fn cancel_download(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    state.downloads.cancel(id).await.map_err(|e| e.to_string())
+}
+
+/// Получить загрузки
+/// 
+/// # Параметры
+/// * `mod_id` - ID мода (None = все загрузки)
+/// 
+/// # Возвращает
+/// Загрузки (новые первыми) или ошибку
+#[tauri::command]
+async fn get_downloads(state: tauri::State<'_, AppState>, mod_id: Option<i64>) -> Result<Vec<models::Download>, String> {
+    state.db.get_downloads(mod_id).await.map_err(|e| e.to_string())
+}
+
+/// Установить мод из завершенной загрузки или обновить установленный
+/// 
+/// # Параметры
+/// * `download_id` - ID загрузки
+/// * `install_dir` - директория установки (None = директория сайта мода)
+/// 
+/// # Возвращает
+/// Установку или ошибку (при ошибке директория не меняется)
+#[tauri::command]
+async fn install_mod(
+    state: tauri::State<'_, AppState>,
+    download_id: i64,
+    install_dir: Option<String>,
+) -> Result<models::Installation, String> {
+    state.installer.install(download_id, install_dir).await.map_err(|e| e.to_string())
+}
+
+/// Удалить установленный мод (файлы и запись установки)
+/// 
+/// # Параметры
+/// * `installation_id` - ID установки
+#[tauri::command]
+async fn uninstall_mod(state: tauri::State<'_, AppState>, installation_id: i64) -> Result<(), String> {
+    state.installer.uninstall(installation_id).await.map_err(|e| e.to_string())
+}
+
+/// Получить установки модов
+/// 
+/// # Параметры
+/// * `mod_id` - ID мода (None = все установки)
+#[tauri::command]
+async fn get_installations(
+    state: tauri::State<'_, AppState>,
+    mod_id: Option<i64>,
+) -> Result<Vec<models::Installation>, String> {
+    state.db.get_installations(mod_id).await.map_err(|e| e.to_string())
+}
+
+/// Получить файлы, записанные при установке мода
+/// 
+/// # Параметры
+/// * `installation_id` - ID установки
+#[tauri::command]
+async fn get_installed_files(
+    state: tauri::State<'_, AppState>,
+    installation_id: i64,
+) -> Result<Vec<models::InstalledFile>, String> {
+    state.db.get_installed_files(installation_id).await.map_err(|e| e.to_string())
+}
+
+/// Задать директорию игры/модов для установки модов сайта
+/// 
+/// # Параметры
+/// * `site_id` - ID сайта
+/// * `install_dir` - директория (None или пустая строка = не задана)
+#[tauri::command]
+async fn set_site_install_dir(
+    state: tauri::State<'_, AppState>,
+    site_id: i64,
+    install_dir: Option<String>,
+) -> Result<(), String> {
+    let install_dir = install_dir.filter(|dir| !dir.trim().is_empty());
+    if let Some(dir) = &install_dir {
+        if !std::path::Path::new(dir).is_dir() {
+            return Err(format!("Директория {} не существует", dir));
+        }
+    }
+    state.db.set_site_install_dir(site_id, install_dir.as_deref()).await.map_err(|e| e.to_string())
+}
+
+/// Следить за модом (получать уведомления об его обновлениях)
+/// 
+/// # Параметры
+/// * `mod_id` - ID мода
+#[tauri::command]
+async fn follow_mod(state: tauri::State<'_, AppState>, mod_id: i64) -> Result<(), String> {
+    state.db.set_mod_followed(mod_id, true).await.map_err(|e| e.to_string())
+}
+
+/// Перестать следить за модом
+/// 
+/// # Параметры
+/// * `mod_id` - ID мода
+#[tauri::command]
+async fn unfollow_mod(state: tauri::State<'_, AppState>, mod_id: i64) -> Result<(), String> {
+    state.db.set_mod_followed(mod_id, false).await.map_err(|e| e.to_string())
+}
+
+/// Получить моды, за которыми следит пользователь
+#[tauri::command]
+async fn get_followed_mods(state: tauri::State<'_, AppState>) -> Result<Vec<models::Mod>, String> {
+    state.db.get_followed_mods().await.map_err(|e| e.to_string())
+}
+
+/// Задать, уведомлять ли о новых модах сайта
+/// 
+/// # Параметры
+/// * `site_id` - ID сайта
+/// * `notify` - true - уведомлять, false - сохранять новые моды молча
+#[tauri::command]
+async fn set_site_notify_new_mods(state: tauri::State<'_, AppState>, site_id: i64, notify: bool) -> Result<(), String> {
+    state.db.set_site_notify_new_mods(site_id, notify).await.map_err(|e| e.to_string())
+}
+
+/// Получить все профили (сборки модов)
+#[tauri::command]
+async fn get_profiles(state: tauri::State<'_, AppState>) -> Result<Vec<models::Profile>, String> {
+    state.db.get_profiles().await.map_err(|e| e.to_string())
+}
+
+/// Создать профиль
+/// 
+/// # Параметры
+/// * `name` - уникальное имя профиля
+/// * `description` - описание
+/// * `install_dir` - директория игры/модов профиля
+/// 
+/// # Возвращает
+/// Созданный профиль или ошибку
+#[tauri::command]
+async fn create_profile(
+    state: tauri::State<'_, AppState>,
+    name: String,
+    description: Option<String>,
+    install_dir: Option<String>,
+) -> Result<models::Profile, String> {
+    profiles::create_profile(&state.db, &name, description.as_deref(), install_dir.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Изменить профиль
+/// 
+/// # Параметры
+/// * `id` - ID профиля
+/// * `name` - новое имя
+/// * `description` - новое описание
+/// * `install_dir` - новая директория игры/модов
+#[tauri::command]
+async fn update_profile(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    name: String,
+    description: Option<String>,
+    install_dir: Option<String>,
+) -> Result<(), String> {
+    profiles::update_profile(&state.db, id, &name, description.as_deref(), install_dir.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Удалить профиль (моды остаются в базе)
+/// 
+/// # Параметры
+/// * `id` - ID профиля
+#[tauri::command]
+async fn delete_profile(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    state.db.delete_profile(id).await.map_err(|e| e.to_string())
+}
+
+/// Скопировать профиль вместе со списком модов
+/// 
+/// # Параметры
+/// * `id` - ID исходного профиля
+/// * `name` - имя копии
+/// 
+/// # Возвращает
+/// Новый профиль или ошибку
+#[tauri::command]
+async fn clone_profile(state: tauri::State<'_, AppState>, id: i64, name: String) -> Result<models::Profile, String> {
+    profiles::clone_profile(&state.db, id, &name).await.map_err(|e| e.to_string())
+}
+
+/// Получить моды профиля в порядке загрузки
+/// 
+/// # Параметры
+/// * `id` - ID профиля
+#[tauri::command]
+async fn get_profile_mods(state: tauri::State<'_, AppState>, id: i64) -> Result<Vec<models::ProfileMod>, String> {
+    state.db.get_profile_mods(id).await.map_err(|e| e.to_string())
+}
+
+/// Заменить список модов профиля (порядок списка - порядок загрузки)
+/// 
+/// # Параметры
+/// * `id` - ID профиля
+/// * `mods` - моды с закрепленными версиями и флагами включения
+/// 
+/// # Возвращает
+/// Сохраненный список или ошибку
+#[tauri::command]
+async fn set_profile_mods(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    mods: Vec<models::ProfileMod>,
+) -> Result<Vec<models::ProfileMod>, String> {
+    profiles::set_profile_mods(&state.db, id, &mods).await.map_err(|e| e.to_string())
+}
+
+/// Экспортировать профиль в манифест
+/// 
+/// # Параметры
+/// * `id` - ID профиля
+/// * `format` - `json` или `toml`
+/// 
+/// # Возвращает
+/// Текст манифеста или ошибку
+#[tauri::command]
+async fn export_profile(
+    state: tauri::State<'_, AppState>,
+    id: i64,
+    format: profiles::ManifestFormat,
+) -> Result<String, String> {
+    profiles::export_profile(&state.db, id, format).await.map_err(|e| e.to_string())
+}
+
+/// Импортировать профиль из манифеста
+/// 
+/// # Параметры
+/// * `manifest` - текст манифеста
+/// * `format` - `json` или `toml`
+/// * `name` - имя нового профиля (None = из манифеста)
+/// 
+/// # Возвращает
+/// Созданный профиль и моды, которые не удалось найти, или ошибку
+#[tauri::command]
+async fn import_profile(
+    state: tauri::State<'_, AppState>,
+    manifest: String,
+    format: profiles::ManifestFormat,
+    name: Option<String>,
+) -> Result<profiles::ProfileImport, String> {
+    profiles::import_profile(&state.db, &manifest, format, name.as_deref())
+        .await
+        .map_err(|e| e.to_string())
 }
 
 /// Проверить обновления модов для указанного сайта или всех сайтов
 /// 
 /// Загружает страницы сайтов, парсит моды и сравнивает с существующими в базе данных.
-/// Создает записи о новых модах и обновлениях существующих.
+/// Создает записи о новых модах и обновлениях существующих. Сайты проверяются
+/// одновременно, ход проверки каждого сайта приходит событием `site-check-progress`.
+/// Проверка выполняется как задача `check_updates` (можно отменить через `cancel_job`).
 /// 
 /// # Параметры
 /// * `site_id` - ID сайта для проверки (None = все сайты)
@@ -134,43 +603,120 @@ async fn get_mods(site_id: Option<i64>) -> Result<Vec<models::Mod>, String> {
 /// # Возвращает
 /// Вектор обновлений модов (ModUpdate) или ошибку
 #[tauri::command]
-async fn check_updates(site_id: Option<i64>) -> Result<Vec<models::ModUpdate>, String> {
-    let engine = ParserEngine::new();
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    
-    let sites = if let Some(id) = site_id {
-        vec![db.get_site(id).await.map_err(|e| e.to_string())?]
-    } else {
-        db.get_sites().await.map_err(|e| e.to_string())?
+async fn check_updates(state: tauri::State<'_, AppState>, site_id: Option<i64>) -> Result<Vec<models::ModUpdate>, String> {
+    let title = match site_id {
+        Some(id) => format!("Проверка обновлений сайта {}", id),
+        None => "Проверка обновлений всех сайтов".to_string(),
     };
+    state
+        .jobs
+        .run(models::JobKind::CheckUpdates, title, updates::check_updates(&state, site_id))
+        .await
+}
 
-    let mut updates = Vec::new();
-    
-    for site in sites {
-        match engine.parse_site(&site).await {
-            Ok(mods) => {
-                for mod_item in mods {
-                    if let Some(existing) = db.get_mod_by_url(&mod_item.url).await.ok().flatten() {
-                        if existing.updated_at < mod_item.updated_at {
-                            updates.push(models::ModUpdate {
-                                mod_id: existing.id,
-                                site_id: site.id,
-                                old_version: existing.version.clone(),
-                                new_version: mod_item.version.clone(),
-                                changes: mod_item.changes.clone(),
-                            });
-                            db.update_mod(existing.id, &mod_item).await.ok();
-                        }
-                    } else {
-                        db.add_mod(&mod_item).await.ok();
-                    }
-                }
-            }
-            Err(e) => eprintln!("Error parsing site {}: {}", site.name, e),
-        }
-    }
-    
-    Ok(updates)
+/// Получить расписания фоновой проверки всех сайтов
+/// 
+/// # Возвращает
+/// Расписания сайтов (по умолчанию - раз в час) или ошибку
+#[tauri::command]
+async fn get_site_schedules(state: tauri::State<'_, AppState>) -> Result<Vec<models::SiteSchedule>, String> {
+    state.db.get_site_schedules().await.map_err(|e| e.to_string())
+}
+
+/// Получить расписание фоновой проверки сайта
+/// 
+/// # Параметры
+/// * `site_id` - ID сайта
+/// 
+/// # Возвращает
+/// Расписание сайта или ошибку
+#[tauri::command]
+async fn get_site_schedule(state: tauri::State<'_, AppState>, site_id: i64) -> Result<models::SiteSchedule, String> {
+    scheduler::get_schedule(&state.db, site_id).await.map_err(|e| e.to_string())
+}
+
+/// Изменить расписание фоновой проверки сайта
+/// 
+/// # Параметры
+/// * `site_id` - ID сайта
+/// * `schedule` - `{"type": "interval", "seconds": 3600}` или `{"type": "cron", "expression": "0 */6 * * *"}`
+/// * `enabled` - включена ли фоновая проверка сайта
+/// 
+/// # Возвращает
+/// Сохраненное расписание со временем следующей проверки или ошибку проверки
+#[tauri::command]
+async fn set_site_schedule(
+    state: tauri::State<'_, AppState>,
+    site_id: i64,
+    schedule: models::Schedule,
+    enabled: bool,
+) -> Result<models::SiteSchedule, String> {
+    scheduler::set_schedule(&state.db, site_id, schedule, enabled)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Проверить сайт немедленно, не дожидаясь расписания
+/// 
+/// Результат записывается в расписание сайта (время проверки, ошибка),
+/// следующая проверка считается от текущего момента. Проверка выполняется
+/// как задача `check_updates`.
+/// 
+/// # Параметры
+/// * `site_id` - ID сайта
+/// 
+/// # Возвращает
+/// Вектор обновлений модов сайта или ошибку проверки
+#[tauri::command]
+async fn run_site_check_now(state: tauri::State<'_, AppState>, site_id: i64) -> Result<Vec<models::ModUpdate>, String> {
+    let site = scheduler::get_site(&state.db, site_id).await.map_err(|e| e.to_string())?;
+    let title = format!("Проверка сайта {}", site.name);
+    state
+        .jobs
+        .run(models::JobKind::CheckUpdates, title, scheduler::run_site(&state, &site))
+        .await
+}
+
+/// Получить историю проверок сайтов
+/// 
+/// # Параметры
+/// * `site_id` - ID сайта для фильтрации (None = все сайты)
+/// * `limit` - максимальное количество записей (по умолчанию 100)
+/// 
+/// # Возвращает
+/// Проверки от новых к старым: время, HTTP статус, найденные/новые/обновленные моды, ошибка
+#[tauri::command]
+async fn get_check_runs(
+    state: tauri::State<'_, AppState>,
+    site_id: Option<i64>,
+    limit: Option<i64>,
+) -> Result<Vec<models::CheckRun>, String> {
+    state
+        .db
+        .get_check_runs(site_id, limit.unwrap_or(100).clamp(1, 1000))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Получить здоровье сайта по последним проверкам
+/// 
+/// # Параметры
+/// * `site_id` - ID сайта
+/// 
+/// # Возвращает
+/// Статус `ok`, `degraded`, `failing` или `unknown` с пояснением или ошибку
+#[tauri::command]
+async fn get_site_health(state: tauri::State<'_, AppState>, site_id: i64) -> Result<models::SiteHealth, String> {
+    check_runs::site_health(&state.db, site_id).await.map_err(|e| e.to_string())
+}
+
+/// Получить здоровье всех сайтов
+/// 
+/// # Возвращает
+/// Здоровье каждого сайта или ошибку
+#[tauri::command]
+async fn get_sites_health(state: tauri::State<'_, AppState>) -> Result<Vec<models::SiteHealth>, String> {
+    check_runs::sites_health(&state.db).await.map_err(|e| e.to_string())
 }
 
 /// Построить конфигурацию парсера из HTML и CSS селектора
@@ -189,6 +735,29 @@ async fn build_parser(html: String, selector: String) -> Result<serde_json::Valu
     builder.build_from_selector(&html, &selector).await.map_err(|e| e.to_string())
 }
 
+/// Получить фоновые задачи
+/// 
+/// # Возвращает
+/// Выполняющиеся и последние завершенные задачи (новые первыми)
+#[tauri::command]
+async fn list_jobs(state: tauri::State<'_, AppState>) -> Result<Vec<models::Job>, String> {
+    Ok(state.jobs.list())
+}
+
+/// Отменить фоновую задачу
+/// 
+/// Изменения задач приходят событием `job-progress`.
+/// 
+/// # Параметры
+/// * `id` - ID задачи
+/// 
+/// # Возвращает
+/// Пустой результат или ошибку, если задачи нет или она уже завершена
+#[tauri::command]
+async fn cancel_job(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    state.jobs.cancel(id).map_err(|e| e.to_string())
+}
+
 /// Загрузить HTML страницу с указанного URL или из кеша
 /// 
 /// Сначала проверяет кеш (если `force_refresh` = false), затем загружает с сервера.
@@ -204,6 +773,18 @@ async fn build_parser(html: String, selector: String) -> Result<serde_json::Valu
 /// HTML содержимое страницы или ошибку
 #[tauri::command]
 async fn fetch_page(app_handle: tauri::AppHandle, url: String, force_refresh: bool, site_id: Option<i64>) -> Result<String, String> {
+    use tauri::Manager;
+
+    // Загрузка выполняется как задача fetch_page (можно отменить через cancel_job)
+    let jobs = app_handle.state::<AppState>().jobs.clone();
+    let title = format!("Загрузка страницы {}", url);
+    jobs.run(models::JobKind::FetchPage, title, load_page(app_handle, url, force_refresh, site_id)).await
+}
+
+/// Загрузить страницу для `fetch_page`: из кеша или с сервера с сохранением в кеш
+async fn load_page(app_handle: tauri::AppHandle, url: String, force_refresh: bool, site_id: Option<i64>) -> Result<String, String> {
+    use tauri::Manager;
+    
     info!("fetch_page called with URL: {}, force_refresh: {}, site_id: {:?}", url, force_refresh, site_id);
     
     // Validate URL format
@@ -272,18 +853,9 @@ async fn fetch_page(app_handle: tauri::AppHandle, url: String, force_refresh: bo
     // Limit response size to prevent memory issues (10MB max)
     const MAX_SIZE: u64 = 10 * 1024 * 1024;
     
-    debug!("Creating HTTP client...");
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| {
-            error!("Failed to create HTTP client: {}", e);
-            format!("Не удалось создать HTTP клиент: {}", e)
-        })?;
-    
     debug!("Sending GET request to: {}", url);
-    let response = client
+    let response = app_handle.state::<AppState>()
+        .http
         .get(&url)
         .send()
         .await
@@ -415,7 +987,7 @@ async fn save_resource(app_handle: tauri::AppHandle, url: String, data: Vec<u8>,
     
     // Генерируем безопасное имя файла из URL
     let url_path = url.split('?').next().unwrap_or(&url); // Убираем query параметры
-    let file_name = url_path.split('/').last().unwrap_or("resource");
+    let file_name = url_path.split('/').next_back().unwrap_or("resource");
     let safe_filename = file_name
         .chars()
         .filter(|c| c.is_alphanumeric() || *c == '.' || *c == '_' || *c == '-')
@@ -456,7 +1028,7 @@ async fn get_cached_resource(app_handle: &tauri::AppHandle, url: &str) -> Result
     
     // Генерируем имя файла из URL
     let url_path = url_obj.path();
-    let file_name = url_path.split('/').last().unwrap_or("resource");
+    let file_name = url_path.split('/').next_back().unwrap_or("resource");
     let safe_filename = file_name
         .chars()
         .filter(|c| c.is_alphanumeric() || *c == '.' || *c == '_' || *c == '-')
@@ -489,36 +1061,31 @@ async fn get_cached_resource(app_handle: &tauri::AppHandle, url: &str) -> Result
     
     // Также ищем в папках сохраненных страниц
     if app_data_dir.exists() {
-        match fs::read_dir(&app_data_dir) {
-            Ok(entries) => {
-                for entry in entries {
-                    if let Ok(entry) = entry {
-                        let path = entry.path();
-                        if path.is_dir() {
-                            let folder_name = path.file_name()
-                                .and_then(|n| n.to_str())
-                                .unwrap_or("");
-                            
-                            if folder_name.starts_with("page_") {
-                                // Проверяем в подпапках css и images
-                                for subfolder in &["css", "images"] {
-                                    let resource_path = path.join(subfolder).join(&safe_filename);
-                                    if resource_path.exists() {
-                                        match fs::read(&resource_path) {
-                                            Ok(data) => {
-                                                info!("Found cached resource: {} in {}/{}", url, folder_name, subfolder);
-                                                return Ok(Some(data));
-                                            }
-                                            Err(_) => continue,
-                                        }
+        if let Ok(entries) = fs::read_dir(&app_data_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    let folder_name = path.file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("");
+                    
+                    if folder_name.starts_with("page_") {
+                        // Проверяем в подпапках css и images
+                        for subfolder in &["css", "images"] {
+                            let resource_path = path.join(subfolder).join(&safe_filename);
+                            if resource_path.exists() {
+                                match fs::read(&resource_path) {
+                                    Ok(data) => {
+                                        info!("Found cached resource: {} in {}/{}", url, folder_name, subfolder);
+                                        return Ok(Some(data));
                                     }
+                                    Err(_) => continue,
                                 }
                             }
                         }
                     }
                 }
             }
-            Err(_) => {}
         }
     }
     
@@ -536,7 +1103,7 @@ async fn get_cached_resource(app_handle: &tauri::AppHandle, url: &str) -> Result
 /// # Возвращает
 /// Байты ресурса или ошибку
 #[tauri::command]
-async fn fetch_resource(app_handle: tauri::AppHandle, url: String) -> Result<Vec<u8>, String> {
+async fn fetch_resource(app_handle: tauri::AppHandle, state: tauri::State<'_, AppState>, url: String) -> Result<Vec<u8>, String> {
     info!("fetch_resource called with URL: {}", url);
     
     if url.is_empty() {
@@ -562,13 +1129,7 @@ async fn fetch_resource(app_handle: tauri::AppHandle, url: String) -> Result<Vec
     
     const MAX_SIZE: u64 = 5 * 1024 * 1024; // 5MB max per resource
     
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-        .timeout(std::time::Duration::from_secs(30))
-        .build()
-        .map_err(|e| format!("Не удалось создать HTTP клиент: {}", e))?;
-    
-    let response = client
+    let response = state.http
         .get(&url)
         .send()
         .await
@@ -688,7 +1249,7 @@ async fn save_page_local(
     // Привязываем сохраненную страницу к сайту, если указаны site_id и url
     // Создаем новую версию с временной меткой
     if let (Some(site_id), Some(page_url)) = (site_id, url) {
-        let db = Database::new().await.map_err(|e| e.to_string())?;
+        let db = &app_handle.state::<AppState>().db;
         let folder_path = safe_filename.trim_end_matches("/index.html");
         let version_timestamp = Utc::now().to_rfc3339();
         if let Err(e) = db.save_page_for_site(site_id, &page_url, folder_path, &version_timestamp).await {
@@ -723,11 +1284,9 @@ async fn save_page_local(
 /// # Возвращает
 /// Вектор найденных модов или ошибку
 #[tauri::command]
-async fn test_parser(site_id: i64) -> Result<Vec<models::Mod>, String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    let site = db.get_site(site_id).await.map_err(|e| e.to_string())?;
-    let engine = ParserEngine::new();
-    engine.parse_site(&site).await.map_err(|e| e.to_string())
+async fn test_parser(state: tauri::State<'_, AppState>, site_id: i64) -> Result<Vec<models::Mod>, String> {
+    let site = state.db.get_site(site_id).await.map_err(|e| e.to_string())?;
+    updates::parse_site(&state, &site).await
 }
 
 /// Получить список всех уведомлений
@@ -737,9 +1296,8 @@ async fn test_parser(site_id: i64) -> Result<Vec<models::Mod>, String> {
 /// # Возвращает
 /// Вектор уведомлений или ошибку
 #[tauri::command]
-async fn get_notifications() -> Result<Vec<models::Notification>, String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    db.get_notifications().await.map_err(|e| e.to_string())
+async fn get_notifications(state: tauri::State<'_, AppState>) -> Result<Vec<models::Notification>, String> {
+    state.db.get_notifications().await.map_err(|e| e.to_string())
 }
 
 /// Отметить уведомление как прочитанное
@@ -750,9 +1308,8 @@ async fn get_notifications() -> Result<Vec<models::Notification>, String> {
 /// # Возвращает
 /// Пустой результат при успехе или ошибку
 #[tauri::command]
-async fn mark_notification_read(id: i64) -> Result<(), String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    db.mark_notification_read(id).await.map_err(|e| e.to_string())
+async fn mark_notification_read(state: tauri::State<'_, AppState>, id: i64) -> Result<(), String> {
+    state.db.mark_notification_read(id).await.map_err(|e| e.to_string())
 }
 
 /// Получить закешированную страницу из общего кеша
@@ -803,23 +1360,21 @@ async fn get_cached_page(app_handle: tauri::AppHandle, url: String) -> Result<Op
     
     match fs::read_dir(&app_data_dir) {
         Ok(entries) => {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        let folder_name = path.file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("");
-                        
-                        // Проверяем, соответствует ли папка этому URL
-                        if folder_name.starts_with("page_") && folder_name.contains(&hostname) {
-                            let index_file = path.join("index.html");
-                            if index_file.exists() {
-                                // Получаем время модификации для сортировки
-                                if let Ok(metadata) = fs::metadata(&index_file) {
-                                    if let Ok(modified) = metadata.modified() {
-                                        candidates.push((path.clone(), modified, folder_name.to_string()));
-                                    }
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    let folder_name = path.file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("");
+                    
+                    // Проверяем, соответствует ли папка этому URL
+                    if folder_name.starts_with("page_") && folder_name.contains(&hostname) {
+                        let index_file = path.join("index.html");
+                        if index_file.exists() {
+                            // Получаем время модификации для сортировки
+                            if let Ok(metadata) = fs::metadata(&index_file) {
+                                if let Ok(modified) = metadata.modified() {
+                                    candidates.push((path.clone(), modified, folder_name.to_string()));
                                 }
                             }
                         }
@@ -835,7 +1390,7 @@ async fn get_cached_page(app_handle: tauri::AppHandle, url: String) -> Result<Op
     info!("[CACHE] Found {} candidate folders for hostname: {}", candidates.len(), hostname);
     
     // Сортируем по дате модификации (новые первыми)
-    candidates.sort_by(|a, b| b.1.cmp(&a.1));
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.1));
     
     // Проверяем кандидатов, начиная с самых новых
     // Для каждого кандидата проверяем, соответствует ли он запрашиваемому URL
@@ -925,30 +1480,28 @@ async fn list_cached_pages(app_handle: tauri::AppHandle) -> Result<Vec<serde_jso
     
     match fs::read_dir(&app_data_dir) {
         Ok(entries) => {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        let index_file = path.join("index.html");
-                        if index_file.exists() {
-                            if let Ok(metadata) = fs::metadata(&index_file) {
-                                let modified = metadata.modified()
-                                    .ok()
-                                    .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
-                                    .map(|d| d.as_secs());
-                                
-                                let folder_name = path.file_name()
-                                    .and_then(|n| n.to_str())
-                                    .unwrap_or("unknown")
-                                    .to_string();
-                                
-                                cached_pages.push(serde_json::json!({
-                                    "folder": folder_name,
-                                    "path": path.to_string_lossy().to_string(),
-                                    "modified": modified,
-                                    "size": metadata.len()
-                                }));
-                            }
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    let index_file = path.join("index.html");
+                    if index_file.exists() {
+                        if let Ok(metadata) = fs::metadata(&index_file) {
+                            let modified = metadata.modified()
+                                .ok()
+                                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                                .map(|d| d.as_secs());
+                            
+                            let folder_name = path.file_name()
+                                .and_then(|n| n.to_str())
+                                .unwrap_or("unknown")
+                                .to_string();
+                            
+                            cached_pages.push(serde_json::json!({
+                                "folder": folder_name,
+                                "path": path.to_string_lossy().to_string(),
+                                "modified": modified,
+                                "size": metadata.len()
+                            }));
                         }
                     }
                 }
@@ -1032,7 +1585,7 @@ async fn get_saved_page_for_site(app_handle: tauri::AppHandle, site_id: i64, url
     
     info!("[DB_CACHE] get_saved_page_for_site called for site_id: {}, URL: {}", site_id, url);
     
-    let db = Database::new().await.map_err(|e| e.to_string())?;
+    let db = &app_handle.state::<AppState>().db;
     
     match db.get_saved_page(site_id, &url).await {
         Ok(Some(folder_path)) => {
@@ -1109,36 +1662,31 @@ async fn get_cache_folder_for_url(app_handle: tauri::AppHandle, url: String) ->
     // Ищем папки, которые начинаются с page_ и содержат hostname
     let mut candidates: Vec<(String, std::time::SystemTime)> = Vec::new();
     
-    match fs::read_dir(&app_data_dir) {
-        Ok(entries) => {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        let folder_name = path.file_name()
-                            .and_then(|n| n.to_str())
-                            .map(|s| s.to_string())
-                            .unwrap_or_default();
-                        
-                        if folder_name.starts_with("page_") && folder_name.contains(&hostname) {
-                            let index_file = path.join("index.html");
-                            if index_file.exists() {
-                                if let Ok(metadata) = fs::metadata(&index_file) {
-                                    if let Ok(modified) = metadata.modified() {
-                                        candidates.push((folder_name, modified));
-                                    }
-                                }
+    if let Ok(entries) = fs::read_dir(&app_data_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                let folder_name = path.file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|s| s.to_string())
+                    .unwrap_or_default();
+                
+                if folder_name.starts_with("page_") && folder_name.contains(&hostname) {
+                    let index_file = path.join("index.html");
+                    if index_file.exists() {
+                        if let Ok(metadata) = fs::metadata(&index_file) {
+                            if let Ok(modified) = metadata.modified() {
+                                candidates.push((folder_name, modified));
                             }
                         }
                     }
                 }
             }
         }
-        Err(_) => {}
     }
     
     // Сортируем по дате модификации (новые первыми)
-    candidates.sort_by(|a, b| b.1.cmp(&a.1));
+    candidates.sort_by_key(|c| std::cmp::Reverse(c.1));
     
     // Возвращаем самую новую папку
     if let Some((folder_name, _)) = candidates.first() {
@@ -1150,10 +1698,8 @@ async fn get_cache_folder_for_url(app_handle: tauri::AppHandle, url: String) ->
 
 /// Получить все версии сохраненной страницы для сайта
 #[tauri::command]
-async fn get_saved_page_versions(site_id: i64, url: String) -> Result<Vec<serde_json::Value>, String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    
-    match db.get_saved_page_versions(site_id, &url).await {
+async fn get_saved_page_versions(state: tauri::State<'_, AppState>, site_id: i64, url: String) -> Result<Vec<serde_json::Value>, String> {
+    match state.db.get_saved_page_versions(site_id, &url).await {
         Ok(versions) => {
             Ok(versions
                 .iter()
@@ -1183,9 +1729,8 @@ async fn get_saved_page_versions(site_id: i64, url: String) -> Result<Vec<serde_
 /// # Возвращает
 /// Пустой результат при успехе или ошибку
 #[tauri::command]
-async fn delete_saved_page_version(page_id: i64) -> Result<(), String> {
-    let db = Database::new().await.map_err(|e| e.to_string())?;
-    db.delete_saved_page_version(page_id).await.map_err(|e| e.to_string())?;
+async fn delete_saved_page_version(state: tauri::State<'_, AppState>, page_id: i64) -> Result<(), String> {
+    state.db.delete_saved_page_version(page_id).await.map_err(|e| e.to_string())?;
     Ok(())
 }
 
@@ -1202,33 +1747,86 @@ fn main() {
         .plugin(tauri_plugin_notification::init())
         .plugin(tauri_plugin_fs::init())
         .setup(|app| {
+            use tauri::Manager;
+            
             info!("Tauri app setup started");
             
-            // Initialize database
-            let app_handle = app.handle().clone();
+            // База данных лежит в директории данных приложения рядом с saved_pages,
+            // если путь не переопределен флагом --db-path или MOD_AGGREGATOR_DB
+            let app_data_dir = app.path().app_data_dir()?;
+            let db_location = db_path::resolve_from_env(&app_data_dir);
+            if db_location.source == db_path::DbPathSource::AppData {
+                if let Ok(legacy_dir) = std::env::current_dir() {
+                    if let Err(e) = db_path::migrate_legacy(&legacy_dir, &db_location.path) {
+                        warn!("Failed to migrate legacy database from {:?}: {}", legacy_dir, e);
+                    }
+                }
+            }
+            info!("Using database {:?} ({:?})", db_location.path, db_location.source);
+            
+            // Создаем общее состояние один раз: пул БД, HTTP клиент и движок парсинга
+            let app_state = tauri::async_runtime::block_on(AppState::new(&db_location.path, &app_data_dir))
+                .map_err(|e| -> Box<dyn std::error::Error> {
+                    error!("Failed to initialize application state: {}", e);
+                    e
+                })?;
+            match tauri::async_runtime::block_on(app_state.db.schema_version()) {
+                Ok(version) => info!(
+                    "Database initialized successfully (schema version {} of {})",
+                    version,
+                    migrations::latest_version()
+                ),
+                Err(e) => warn!("Database initialized, but schema version is unavailable: {}", e),
+            }
+            // Пересылаем изменения загрузок, фоновых задач и ход проверок сайтов во frontend
+            forward_events(app.handle().clone(), app_state.downloads.subscribe(), downloads::DOWNLOAD_EVENT);
+            forward_events(app.handle().clone(), app_state.jobs.subscribe(), jobs::JOB_EVENT);
+            forward_events(app.handle().clone(), app_state.checks.subscribe(), check_queue::CHECK_PROGRESS_EVENT);
+            // Подозрения на сбой парсеров (из любой проверки сайта) - в уведомления
+            let mut drift_events = app_state.drift.subscribe();
+            let notification_service = NotificationService::new(app.handle().clone(), app_state.db.clone());
             tauri::async_runtime::spawn(async move {
-                info!("Initializing database...");
-                if let Err(e) = Database::new().await {
-                    error!("Failed to initialize database: {}", e);
-                } else {
-                    info!("Database initialized successfully");
+                while let Some(drift) = next_event(&mut drift_events, "parser drift").await {
+                    if let Err(e) = notification_service.notify_parser_drift(&drift).await {
+                        warn!("Failed to send parser drift notification: {}", e);
+                    }
                 }
             });
+            app.manage(app_state);
             
-            // Start background update checker
-            let app_handle_clone = app_handle.clone();
+            // Фоновая проверка сайтов по их расписаниям (site_schedules)
+            let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
-                let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(3600));
                 loop {
-                    interval.tick().await;
-                    if let Ok(updates) = check_updates(None).await {
-                        if !updates.is_empty() {
-                            let notification_service = NotificationService::new(app_handle_clone.clone());
-                            for update in updates {
-                                notification_service.notify_update(&update).await.ok();
+                    let state = app_handle.state::<AppState>();
+                    match scheduler::due_sites(&state).await {
+                        Ok(due) if !due.is_empty() => {
+                            // Проверка выполняется как задача scheduled_check (можно отменить)
+                            let title = format!("Проверка сайтов по расписанию ({})", due.len());
+                            let check = async { Ok(scheduler::run_sites(&state, due.clone()).await) };
+                            match state.jobs.run(models::JobKind::ScheduledCheck, title, check).await {
+                                Ok(updates) => {
+                                    // Только моды, за которыми следят, и новые моды сайтов с notify_new_mods
+                                    let updates: Vec<_> = updates.into_iter().filter(|update| update.notify).collect();
+                                    if !updates.is_empty() {
+                                        let notification_service = NotificationService::new(app_handle.clone(), state.db.clone());
+                                        for update in updates {
+                                            notification_service.notify_update(&update).await.ok();
+                                        }
+                                    }
+                                }
+                                Err(e) => {
+                                    info!("Scheduled checks stopped: {}", e);
+                                    if let Err(e) = scheduler::postpone(&state, &due).await {
+                                        error!("Failed to postpone scheduled checks: {}", e);
+                                    }
+                                }
                             }
                         }
+                        Ok(_) => {}
+                        Err(e) => error!("Failed to run scheduled checks: {}", e),
                     }
+                    tokio::time::sleep(scheduler::next_wakeup(&state).await).await;
                 }
             });
             
@@ -1241,9 +1839,46 @@ fn main() {
             get_sites,
             add_site,
             update_site,
+            validate_parser_config,
             delete_site,
             get_mods,
+            get_mods_page,
+            search_mods,
+            get_mod_history,
+            get_mod_version_diff,
+            start_download,
+            pause_download,
+            resume_download,
+            cancel_download,
+            get_downloads,
+            install_mod,
+            uninstall_mod,
+            get_installations,
+            get_installed_files,
+            set_site_install_dir,
+            follow_mod,
+            unfollow_mod,
+            get_followed_mods,
+            set_site_notify_new_mods,
+            get_profiles,
+            create_profile,
+            update_profile,
+            delete_profile,
+            clone_profile,
+            get_profile_mods,
+            set_profile_mods,
+            export_profile,
+            import_profile,
             check_updates,
+            get_site_schedules,
+            get_site_schedule,
+            set_site_schedule,
+            run_site_check_now,
+            get_check_runs,
+            get_site_health,
+            get_sites_health,
+            list_jobs,
+            cancel_job,
             build_parser,
             fetch_page,
             save_page_local,
@@ -0,0 +1,618 @@
+use sqlx::{sqlite::SqlitePool, Executor, Row};
+use chrono::Utc;
+use log::info;
+
+/// Миграция схемы базы данных
+///
+/// Миграции нумеруются последовательно и применяются только вперед.
+/// Уже выпущенные миграции менять нельзя - только добавлять новые в конец
+/// списка `MIGRATIONS`.
+#[derive(Debug, Clone, Copy)]
+pub struct Migration {
+    /// Номер версии схемы после применения миграции
+    pub version: i64,
+    /// Короткое название миграции
+    pub name: &'static str,
+    /// SQL миграции (может содержать несколько выражений)
+    pub sql: &'static str,
+}
+
+/// Ошибки применения миграций
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    /// База данных создана более новой версией приложения
+    #[error("База данных имеет версию схемы {found}, а приложение поддерживает только до {supported}. Обновите приложение")]
+    NewerSchema { found: i64, supported: i64 },
+    /// Ошибка выполнения миграции
+    #[error("Ошибка миграции {version} ({name}): {source}")]
+    Failed {
+        version: i64,
+        name: &'static str,
+        #[source]
+        source: sqlx::Error,
+    },
+    /// Ошибка чтения или создания таблицы schema_version
+    #[error("Ошибка чтения версии схемы: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Список миграций схемы в порядке применения
+///
+/// Миграция 1 повторяет схему, которая раньше создавалась в `Database::init`
+/// через `CREATE TABLE IF NOT EXISTS`, поэтому она безопасно применяется и к
+/// базам, созданным до появления миграций.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "initial_schema",
+        sql: r#"
+            CREATE TABLE IF NOT EXISTS sites (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                url TEXT NOT NULL UNIQUE,
+                parser_config TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS mods (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                site_id INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                url TEXT NOT NULL UNIQUE,
+                version TEXT,
+                author TEXT,
+                description TEXT,
+                image_url TEXT,
+                changes TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (site_id) REFERENCES sites(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS notifications (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                mod_id INTEGER NOT NULL,
+                site_id INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                message TEXT NOT NULL,
+                read INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                FOREIGN KEY (mod_id) REFERENCES mods(id),
+                FOREIGN KEY (site_id) REFERENCES sites(id)
+            );
+
+            CREATE TABLE IF NOT EXISTS saved_pages (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                site_id INTEGER NOT NULL,
+                url TEXT NOT NULL,
+                folder_path TEXT NOT NULL,
+                version_timestamp TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (site_id) REFERENCES sites(id)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_mods_site_id ON mods(site_id);
+            CREATE INDEX IF NOT EXISTS idx_mods_url ON mods(url);
+            CREATE INDEX IF NOT EXISTS idx_notifications_read ON notifications(read);
+            CREATE INDEX IF NOT EXISTS idx_saved_pages_site_id ON saved_pages(site_id);
+            CREATE INDEX IF NOT EXISTS idx_saved_pages_url ON saved_pages(url);
+        "#,
+    },
+    Migration {
+        version: 2,
+        name: "mods_download_url",
+        sql: "ALTER TABLE mods ADD COLUMN download_url TEXT;",
+    },
+    Migration {
+        version: 3,
+        name: "mod_versions",
+        sql: r#"
+            CREATE TABLE mod_versions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                mod_id INTEGER NOT NULL,
+                version TEXT,
+                changes TEXT,
+                snapshot TEXT NOT NULL,
+                scraped_at TEXT NOT NULL,
+                FOREIGN KEY (mod_id) REFERENCES mods(id)
+            );
+
+            CREATE INDEX idx_mod_versions_mod_id ON mod_versions(mod_id, scraped_at);
+
+            -- Текущее состояние модов становится первой записью истории
+            INSERT INTO mod_versions (mod_id, version, changes, snapshot, scraped_at)
+            SELECT id, version, changes,
+                   json_object(
+                       'title', title,
+                       'url', url,
+                       'version', version,
+                       'author', author,
+                       'description', description,
+                       'image_url', image_url,
+                       'changes', changes,
+                       'download_url', download_url
+                   ),
+                   updated_at
+            FROM mods;
+        "#,
+    },
+    Migration {
+        version: 4,
+        name: "mods_files",
+        sql: "ALTER TABLE mods ADD COLUMN files TEXT NOT NULL DEFAULT '[]';",
+    },
+    Migration {
+        version: 5,
+        name: "downloads",
+        sql: r#"
+            CREATE TABLE downloads (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                mod_id INTEGER NOT NULL,
+                mod_version_id INTEGER,
+                version TEXT,
+                url TEXT NOT NULL,
+                file_name TEXT NOT NULL,
+                path TEXT NOT NULL,
+                status TEXT NOT NULL,
+                total_bytes INTEGER,
+                downloaded_bytes INTEGER NOT NULL DEFAULT 0,
+                content_type TEXT,
+                sha256 TEXT,
+                error TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (mod_id) REFERENCES mods(id),
+                FOREIGN KEY (mod_version_id) REFERENCES mod_versions(id)
+            );
+
+            CREATE INDEX idx_downloads_mod_id ON downloads(mod_id);
+            CREATE INDEX idx_downloads_status ON downloads(status);
+        "#,
+    },
+    Migration {
+        version: 6,
+        name: "installer",
+        sql: r#"
+            ALTER TABLE sites ADD COLUMN install_dir TEXT;
+
+            CREATE TABLE installations (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                mod_id INTEGER NOT NULL,
+                download_id INTEGER,
+                version TEXT,
+                install_dir TEXT NOT NULL,
+                installed_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL,
+                FOREIGN KEY (mod_id) REFERENCES mods(id),
+                FOREIGN KEY (download_id) REFERENCES downloads(id),
+                UNIQUE (mod_id, install_dir)
+            );
+
+            CREATE TABLE installed_files (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                installation_id INTEGER NOT NULL,
+                path TEXT NOT NULL,
+                size INTEGER NOT NULL,
+                FOREIGN KEY (installation_id) REFERENCES installations(id),
+                UNIQUE (installation_id, path)
+            );
+        "#,
+    },
+    Migration {
+        version: 7,
+        name: "profiles",
+        sql: r#"
+            CREATE TABLE profiles (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT,
+                install_dir TEXT,
+                created_at TEXT NOT NULL,
+                updated_at TEXT NOT NULL
+            );
+
+            CREATE TABLE profile_mods (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                profile_id INTEGER NOT NULL,
+                mod_id INTEGER NOT NULL,
+                pinned_version TEXT,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                position INTEGER NOT NULL,
+                FOREIGN KEY (profile_id) REFERENCES profiles(id),
+                FOREIGN KEY (mod_id) REFERENCES mods(id),
+                UNIQUE (profile_id, mod_id)
+            );
+
+            CREATE INDEX idx_profile_mods_profile_id ON profile_mods(profile_id, position);
+        "#,
+    },
+    Migration {
+        version: 8,
+        name: "subscriptions",
+        sql: r#"
+            ALTER TABLE mods ADD COLUMN followed INTEGER NOT NULL DEFAULT 0;
+            ALTER TABLE sites ADD COLUMN notify_new_mods INTEGER NOT NULL DEFAULT 0;
+
+            CREATE INDEX idx_mods_followed ON mods(followed);
+        "#,
+    },
+    Migration {
+        version: 9,
+        name: "mods_fts",
+        sql: r#"
+            CREATE VIRTUAL TABLE mods_fts USING fts5(
+                title, author, description, changes,
+                content='mods', content_rowid='id',
+                tokenize='unicode61 remove_diacritics 2'
+            );
+
+            CREATE TRIGGER mods_fts_insert AFTER INSERT ON mods BEGIN
+                INSERT INTO mods_fts (rowid, title, author, description, changes)
+                VALUES (new.id, new.title, new.author, new.description, new.changes);
+            END;
+
+            CREATE TRIGGER mods_fts_delete AFTER DELETE ON mods BEGIN
+                INSERT INTO mods_fts (mods_fts, rowid, title, author, description, changes)
+                VALUES ('delete', old.id, old.title, old.author, old.description, old.changes);
+            END;
+
+            CREATE TRIGGER mods_fts_update AFTER UPDATE OF title, author, description, changes ON mods BEGIN
+                INSERT INTO mods_fts (mods_fts, rowid, title, author, description, changes)
+                VALUES ('delete', old.id, old.title, old.author, old.description, old.changes);
+                INSERT INTO mods_fts (rowid, title, author, description, changes)
+                VALUES (new.id, new.title, new.author, new.description, new.changes);
+            END;
+
+            INSERT INTO mods_fts (mods_fts) VALUES ('rebuild');
+            CREATE INDEX idx_mods_author ON mods(author COLLATE NOCASE);
+        "#,
+    },
+    Migration {
+        version: 10,
+        name: "mods_listing_indexes",
+        sql: r#"
+            CREATE INDEX idx_mods_title ON mods(title COLLATE NOCASE, id);
+            CREATE INDEX idx_mods_created_at ON mods(created_at, id);
+            CREATE INDEX idx_mods_updated_at ON mods(updated_at, id);
+            CREATE INDEX idx_mods_site_title ON mods(site_id, title COLLATE NOCASE, id);
+            CREATE INDEX idx_mods_site_updated_at ON mods(site_id, updated_at, id);
+        "#,
+    },
+    Migration {
+        version: 11,
+        name: "site_schedules",
+        sql: r#"
+            CREATE TABLE site_schedules (
+                site_id INTEGER PRIMARY KEY,
+                interval_secs INTEGER,
+                cron TEXT,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                last_run_at TEXT,
+                next_run_at TEXT,
+                failure_count INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                FOREIGN KEY (site_id) REFERENCES sites(id)
+            );
+        "#,
+    },
+    Migration {
+        version: 12,
+        name: "check_runs",
+        sql: r#"
+            CREATE TABLE check_runs (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                site_id INTEGER NOT NULL,
+                started_at TEXT NOT NULL,
+                finished_at TEXT NOT NULL,
+                http_status INTEGER,
+                mods_found INTEGER NOT NULL DEFAULT 0,
+                new_mods INTEGER NOT NULL DEFAULT 0,
+                updated_mods INTEGER NOT NULL DEFAULT 0,
+                error TEXT,
+                FOREIGN KEY (site_id) REFERENCES sites(id)
+            );
+
+            CREATE INDEX idx_check_runs_site_id ON check_runs(site_id, id);
+        "#,
+    },
+    Migration {
+        version: 13,
+        name: "parser_drift",
+        sql: r#"
+            ALTER TABLE check_runs ADD COLUMN null_fraction REAL;
+            ALTER TABLE check_runs ADD COLUMN drift TEXT;
+            ALTER TABLE check_runs ADD COLUMN saved_page_id INTEGER;
+
+            -- Уведомления о сбое парсера не относятся к моду: mod_id становится
+            -- необязательным, добавляются тип уведомления и ссылка на снимок страницы
+            CREATE TABLE notifications_new (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                mod_id INTEGER,
+                site_id INTEGER NOT NULL,
+                title TEXT NOT NULL,
+                message TEXT NOT NULL,
+                read INTEGER NOT NULL DEFAULT 0,
+                created_at TEXT NOT NULL,
+                kind TEXT NOT NULL DEFAULT 'mod_update',
+                saved_page_id INTEGER,
+                FOREIGN KEY (mod_id) REFERENCES mods(id),
+                FOREIGN KEY (site_id) REFERENCES sites(id)
+            );
+
+            INSERT INTO notifications_new (id, mod_id, site_id, title, message, read, created_at, kind)
+            SELECT id, mod_id, site_id, title, message, read, created_at,
+                   CASE WHEN title = 'Новый мод' THEN 'new_mod' ELSE 'mod_update' END
+            FROM notifications;
+
+            DROP TABLE notifications;
+            ALTER TABLE notifications_new RENAME TO notifications;
+            CREATE INDEX idx_notifications_read ON notifications(read);
+        "#,
+    },
+];
+
+/// Последняя версия схемы, известная приложению
+pub fn latest_version() -> i64 {
+    MIGRATIONS.last().map_or(0, |m| m.version)
+}
+
+/// Получить текущую версию схемы базы данных
+///
+/// # Параметры
+/// * `pool` - пул подключений к базе данных
+///
+/// # Возвращает
+/// Номер версии (0 - миграции еще не применялись) или ошибку
+pub async fn current_version(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+    let row = sqlx::query("SELECT COALESCE(MAX(version), 0) FROM schema_version")
+        .fetch_one(pool)
+        .await?;
+    Ok(row.get(0))
+}
+
+/// Применить все недостающие миграции
+///
+/// # Параметры
+/// * `pool` - пул подключений к базе данных
+///
+/// # Возвращает
+/// Версию схемы после применения миграций или ошибку
+pub async fn run(pool: &SqlitePool) -> Result<i64, MigrationError> {
+    apply(pool, MIGRATIONS).await
+}
+
+/// Применить миграции из списка
+///
+/// Создает таблицу schema_version, отказывается работать с базой более новой
+/// версии и применяет каждую недостающую миграцию в отдельной транзакции:
+/// при ошибке изменения миграции откатываются, а версия не записывается.
+///
+/// # Параметры
+/// * `pool` - пул подключений к базе данных
+/// * `migrations` - список миграций, отсортированный по версии
+///
+/// # Возвращает
+/// Версию схемы после применения миграций или ошибку
+async fn apply(pool: &SqlitePool, migrations: &[Migration]) -> Result<i64, MigrationError> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            name TEXT NOT NULL,
+            applied_at TEXT NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    let supported = migrations.last().map_or(0, |m| m.version);
+    let initial = current_version(pool).await?;
+    if initial > supported {
+        return Err(MigrationError::NewerSchema { found: initial, supported });
+    }
+
+    let mut current = initial;
+    for migration in migrations.iter().filter(|m| m.version > initial) {
+        info!("Applying database migration {} ({})", migration.version, migration.name);
+        let failed = |source| MigrationError::Failed {
+            version: migration.version,
+            name: migration.name,
+            source,
+        };
+
+        let mut tx = pool.begin().await.map_err(failed)?;
+        tx.execute(migration.sql).await.map_err(failed)?;
+        sqlx::query("INSERT INTO schema_version (version, name, applied_at) VALUES (?, ?, ?)")
+            .bind(migration.version)
+            .bind(migration.name)
+            .bind(Utc::now().to_rfc3339())
+            .execute(&mut *tx)
+            .await
+            .map_err(failed)?;
+        tx.commit().await.map_err(failed)?;
+
+        current = migration.version;
+    }
+
+    Ok(current)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+
+    /// Схема, которую создавал `Database::init` до появления миграций
+    const LEGACY_SCHEMA: &str = r#"
+        CREATE TABLE IF NOT EXISTS sites (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            name TEXT NOT NULL,
+            url TEXT NOT NULL UNIQUE,
+            parser_config TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        );
+        CREATE TABLE IF NOT EXISTS mods (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            site_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            url TEXT NOT NULL UNIQUE,
+            version TEXT,
+            author TEXT,
+            description TEXT,
+            image_url TEXT,
+            changes TEXT,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (site_id) REFERENCES sites(id)
+        );
+        CREATE TABLE IF NOT EXISTS notifications (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            mod_id INTEGER NOT NULL,
+            site_id INTEGER NOT NULL,
+            title TEXT NOT NULL,
+            message TEXT NOT NULL,
+            read INTEGER NOT NULL DEFAULT 0,
+            created_at TEXT NOT NULL,
+            FOREIGN KEY (mod_id) REFERENCES mods(id),
+            FOREIGN KEY (site_id) REFERENCES sites(id)
+        );
+        CREATE TABLE IF NOT EXISTS saved_pages (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            site_id INTEGER NOT NULL,
+            url TEXT NOT NULL,
+            folder_path TEXT NOT NULL,
+            version_timestamp TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL,
+            FOREIGN KEY (site_id) REFERENCES sites(id)
+        );
+        CREATE INDEX IF NOT EXISTS idx_mods_site_id ON mods(site_id);
+        CREATE INDEX IF NOT EXISTS idx_mods_url ON mods(url);
+        CREATE INDEX IF NOT EXISTS idx_notifications_read ON notifications(read);
+        CREATE INDEX IF NOT EXISTS idx_saved_pages_site_id ON saved_pages(site_id);
+        CREATE INDEX IF NOT EXISTS idx_saved_pages_url ON saved_pages(url);
+
+        INSERT INTO sites (name, url, parser_config, created_at, updated_at)
+        VALUES ('Test', 'https://example.com', '{"list_selector": ".mod"}', '2025-01-01T00:00:00+00:00', '2025-01-01T00:00:00+00:00');
+        INSERT INTO mods (site_id, title, url, version, author, description, image_url, changes, created_at, updated_at)
+        VALUES (1, 'Old mod', 'https://example.com/mod/1', '1.0', 'Author', NULL, NULL, NULL, '2025-01-01T00:00:00+00:00', '2025-01-01T00:00:00+00:00');
+    "#;
+
+    /// Создать URL временной базы данных
+    fn temp_db_url(dir: &tempfile::TempDir) -> String {
+        format!("sqlite:{}?mode=rwc", dir.path().join("mod_aggregator.db").display())
+    }
+
+    /// Создать базу данных со схемой до появления миграций
+    async fn create_legacy_db(url: &str) {
+        let pool = SqlitePool::connect(url).await.unwrap();
+        pool.execute(LEGACY_SCHEMA).await.unwrap();
+        pool.close().await;
+    }
+
+    #[tokio::test]
+    async fn fresh_database_gets_latest_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = temp_db_url(&dir);
+        let db = Database::connect(&url).await.unwrap();
+
+        assert_eq!(db.schema_version().await.unwrap(), latest_version());
+        assert!(db.get_sites().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn legacy_database_is_upgraded_without_data_loss() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = temp_db_url(&dir);
+        create_legacy_db(&url).await;
+
+        let db = Database::connect(&url).await.unwrap();
+        assert_eq!(db.schema_version().await.unwrap(), latest_version());
+
+        let sites = db.get_sites().await.unwrap();
+        assert_eq!(sites.len(), 1);
+        assert_eq!(sites[0].parser_config["list_selector"], ".mod");
+
+        let mod_item = db.get_mod_by_url("https://example.com/mod/1").await.unwrap().unwrap();
+        assert_eq!(mod_item.title, "Old mod");
+        assert_eq!(mod_item.version.as_deref(), Some("1.0"));
+        assert_eq!(mod_item.download_url, None);
+
+        // Текущее состояние мода становится первой записью истории
+        let history = db.get_mod_history(mod_item.id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].version.as_deref(), Some("1.0"));
+        assert_eq!(history[0].snapshot["title"], "Old mod");
+    }
+
+    #[tokio::test]
+    async fn migrations_are_applied_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = temp_db_url(&dir);
+        create_legacy_db(&url).await;
+
+        drop(Database::connect(&url).await.unwrap());
+        drop(Database::connect(&url).await.unwrap());
+
+        let pool = SqlitePool::connect(&url).await.unwrap();
+        let applied: i64 = sqlx::query("SELECT COUNT(*) FROM schema_version")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(applied, MIGRATIONS.len() as i64);
+    }
+
+    #[tokio::test]
+    async fn newer_database_is_refused() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = temp_db_url(&dir);
+        drop(Database::connect(&url).await.unwrap());
+
+        let pool = SqlitePool::connect(&url).await.unwrap();
+        sqlx::query("INSERT INTO schema_version (version, name, applied_at) VALUES (?, 'future', '2099-01-01T00:00:00+00:00')")
+            .bind(latest_version() + 1)
+            .execute(&pool)
+            .await
+            .unwrap();
+        pool.close().await;
+
+        let err = apply(&SqlitePool::connect(&url).await.unwrap(), MIGRATIONS).await.unwrap_err();
+        assert!(matches!(err, MigrationError::NewerSchema { found, supported } if found == latest_version() + 1 && supported == latest_version()));
+        assert!(Database::connect(&url).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_migration_is_rolled_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let url = temp_db_url(&dir);
+        create_legacy_db(&url).await;
+        let pool = SqlitePool::connect(&url).await.unwrap();
+
+        let broken = [
+            MIGRATIONS[0],
+            Migration {
+                version: 2,
+                name: "broken",
+                sql: "ALTER TABLE mods ADD COLUMN partial TEXT; SELECT * FROM missing_table;",
+            },
+        ];
+        let err = apply(&pool, &broken).await.unwrap_err();
+        assert!(matches!(err, MigrationError::Failed { version: 2, .. }));
+        assert_eq!(current_version(&pool).await.unwrap(), 1);
+
+        // Колонка из неудачной миграции не должна остаться в схеме
+        let columns: Vec<String> = sqlx::query("SELECT name FROM pragma_table_info('mods')")
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .iter()
+            .map(|row| row.get(0))
+            .collect();
+        assert!(!columns.contains(&"partial".to_string()));
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::changes::{ModField, VersionChange};
 
 /// Структура данных для сайта
 /// 
@@ -12,6 +13,186 @@ pub struct Site {
     pub parser_config: serde_json::Value,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Директория игры/модов для установки модов сайта
+    #[serde(default)]
+    pub install_dir: Option<String>,
+    /// Уведомлять о новых модах сайта, за которыми пользователь еще не следит
+    #[serde(default)]
+    pub notify_new_mods: bool,
+}
+
+/// Расписание проверки сайта
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Schedule {
+    /// Фиксированный интервал между проверками
+    Interval { seconds: i64 },
+    /// Cron выражение из 5 полей в локальном времени (`0 */6 * * *`, `@daily`)
+    Cron { expression: String },
+}
+
+impl Schedule {
+    /// Интервал проверки по умолчанию (раньше был единым для всех сайтов)
+    pub const DEFAULT_INTERVAL_SECS: i64 = 3600;
+}
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Schedule::Interval { seconds: Self::DEFAULT_INTERVAL_SECS }
+    }
+}
+
+/// Расписание и состояние фоновой проверки сайта
+///
+/// Сайты без записи в `site_schedules` проверяются раз в час.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiteSchedule {
+    pub site_id: i64,
+    pub schedule: Schedule,
+    pub enabled: bool,
+    /// Начало последней проверки
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Время следующей проверки (None - как можно скорее, если включено)
+    pub next_run_at: Option<DateTime<Utc>>,
+    /// Количество неудачных проверок подряд (для экспоненциальной задержки)
+    pub failure_count: i64,
+    /// Ошибка последней проверки
+    pub last_error: Option<String>,
+}
+
+/// Запись о проверке сайта
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    pub id: i64,
+    pub site_id: i64,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+    /// HTTP статус первого ответа (страницы каталога, ленты или API), если ответ был
+    pub http_status: Option<u16>,
+    /// Количество модов, найденных парсером
+    pub mods_found: i64,
+    pub new_mods: i64,
+    pub updated_mods: i64,
+    /// Текст ошибки неудачной проверки
+    pub error: Option<String>,
+    /// Доля пустых полей найденных модов (0.0 - 1.0), None если модов нет
+    #[serde(default)]
+    pub null_fraction: Option<f64>,
+    /// Причина подозрения на сбой парсера (см. drift.rs)
+    #[serde(default)]
+    pub drift: Option<String>,
+    /// Снимок страницы, сохраненный при обнаружении сбоя парсера
+    #[serde(default)]
+    pub saved_page_id: Option<i64>,
+}
+
+/// Тип фоновой задачи
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobKind {
+    /// Проверка обновлений (команда `check_updates`)
+    CheckUpdates,
+    /// Фоновая проверка сайтов по расписанию
+    ScheduledCheck,
+    /// Загрузка страницы (команда `fetch_page`)
+    FetchPage,
+    /// Загрузка файла мода
+    Download,
+}
+
+/// Состояние фоновой задачи
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Выполняется
+    Running,
+    /// Завершена успешно
+    Completed,
+    /// Завершена ошибкой
+    Failed,
+    /// Отменена (`cancel_job`)
+    Cancelled,
+    /// Приостановлена и может быть продолжена (загрузка на паузе)
+    Paused,
+}
+
+/// Фоновая задача
+///
+/// Та же структура отправляется во frontend событием `job-progress`
+/// при каждом изменении задачи (см. jobs.rs).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Job {
+    pub id: i64,
+    pub kind: JobKind,
+    /// Описание задачи для списка задач
+    pub title: String,
+    pub status: JobStatus,
+    /// Доля выполненной работы (0.0 - 1.0), None если неизвестна
+    pub progress: Option<f64>,
+    /// Текущий этап задачи
+    pub message: Option<String>,
+    /// Текст ошибки завершившейся ошибкой задачи
+    pub error: Option<String>,
+    /// ID загрузки для задач `download`
+    pub download_id: Option<i64>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+/// Этап проверки сайта
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SiteCheckStatus {
+    /// Ждет свободного места (общий лимит или лимит хоста)
+    Queued,
+    /// Выполняется
+    Running,
+    /// Завершена успешно
+    Completed,
+    /// Завершена ошибкой или по таймауту
+    Failed,
+}
+
+/// Ход проверки сайта
+///
+/// Отправляется во frontend событием `site-check-progress` при каждом
+/// изменении этапа проверки (см. check_queue.rs).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiteCheckProgress {
+    pub site_id: i64,
+    pub site_name: String,
+    pub status: SiteCheckStatus,
+    /// Запись о завершенной проверке (для `completed` и `failed`)
+    pub run: Option<CheckRun>,
+}
+
+/// Состояние сайта по последним проверкам
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    /// Последняя проверка успешна и нашла моды
+    Ok,
+    /// Проверка прошла, но не нашла модов, хотя раньше находила, или
+    /// подозревается сбой парсера
+    Degraded,
+    /// Последняя проверка завершилась ошибкой
+    Failing,
+    /// Сайт еще не проверялся
+    Unknown,
+}
+
+/// Здоровье сайта
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SiteHealth {
+    pub site_id: i64,
+    pub status: HealthStatus,
+    /// Неудачных проверок подряд
+    pub consecutive_failures: i64,
+    /// Пояснение для статусов degraded и failing
+    pub reason: Option<String>,
+    pub last_run: Option<CheckRun>,
+    /// Время последней успешной проверки
+    pub last_success_at: Option<DateTime<Utc>>,
 }
 
 /// Структура данных для мода
@@ -28,10 +209,81 @@ pub struct Mod {
     pub description: Option<String>,
     pub image_url: Option<String>,
     pub changes: Option<String>,
+    /// Ссылка на скачивание (со страницы мода)
+    #[serde(default)]
+    pub download_url: Option<String>,
+    /// Файлы для скачивания текущей версии (например, ассеты релиза)
+    #[serde(default)]
+    pub files: Vec<ModFile>,
+    /// Пользователь следит за модом (уведомления об обновлениях приходят только для таких)
+    #[serde(default)]
+    pub followed: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// Файл для скачивания, относящийся к версии мода
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModFile {
+    /// Имя файла
+    pub name: String,
+    /// Прямая ссылка на скачивание
+    pub url: String,
+    /// Размер в байтах, если известен
+    #[serde(default)]
+    pub size: Option<i64>,
+    /// MIME тип, если известен
+    #[serde(default)]
+    pub content_type: Option<String>,
+}
+
+/// Поле сортировки списка модов
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModSort {
+    Title,
+    Author,
+    Created,
+    #[default]
+    Updated,
+}
+
+impl ModSort {
+    /// Направление по умолчанию: по алфавиту для текста, сначала новые для дат
+    pub fn default_direction(&self) -> SortDirection {
+        match self {
+            ModSort::Title | ModSort::Author => SortDirection::Asc,
+            ModSort::Created | ModSort::Updated => SortDirection::Desc,
+        }
+    }
+}
+
+/// Направление сортировки
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    Asc,
+    Desc,
+}
+
+/// Страница списка модов
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModPage {
+    pub mods: Vec<Mod>,
+    /// Общее количество модов (с учетом фильтра по сайту)
+    pub total: i64,
+    /// Размер страницы после ограничения `MAX_PAGE_SIZE`
+    pub limit: i64,
+    pub offset: i64,
+}
+
+impl ModPage {
+    /// Размер страницы по умолчанию
+    pub const DEFAULT_PAGE_SIZE: i64 = 50;
+    /// Максимальный размер страницы
+    pub const MAX_PAGE_SIZE: i64 = 500;
+}
+
 /// Структура данных для обновления мода
 /// 
 /// Используется для отслеживания изменений версий модов
@@ -42,6 +294,191 @@ pub struct ModUpdate {
     pub old_version: Option<String>,
     pub new_version: Option<String>,
     pub changes: Option<String>,
+    /// Поля мода, которые изменились с прошлой проверки
+    #[serde(default)]
+    pub changed_fields: Vec<ModField>,
+    /// Характер изменения версии (None - версия не менялась)
+    #[serde(default)]
+    pub version_change: Option<VersionChange>,
+    /// Мод найден впервые
+    #[serde(default)]
+    pub new_mod: bool,
+    /// Нужно ли уведомление: обновление мода, за которым следят, или новый
+    /// мод сайта с включенным `notify_new_mods`
+    #[serde(default)]
+    pub notify: bool,
+}
+
+/// Запись истории версий мода
+/// 
+/// Сохраняется при добавлении мода и при каждом обнаруженном изменении.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModVersion {
+    pub id: i64,
+    pub mod_id: i64,
+    pub version: Option<String>,
+    pub changes: Option<String>,
+    /// Снимок полей мода на момент парсинга (JSON объект)
+    pub snapshot: serde_json::Value,
+    pub scraped_at: DateTime<Utc>,
+}
+
+/// Изменение одного поля между двумя записями истории
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FieldDiff {
+    /// Имя поля снимка (title, version, description, ...)
+    pub field: String,
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+}
+
+/// Разница между двумя записями истории версий мода
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModVersionDiff {
+    pub mod_id: i64,
+    pub from: ModVersion,
+    pub to: ModVersion,
+    /// Характер изменения версии (None - версия не менялась)
+    pub version_change: Option<VersionChange>,
+    /// Измененные поля снимка
+    pub fields: Vec<FieldDiff>,
+}
+
+/// Статус загрузки файла мода
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DownloadStatus {
+    /// Создана, еще не начата
+    Queued,
+    /// Файл загружается
+    Downloading,
+    /// Приостановлена (в том числе прервана перезапуском приложения)
+    Paused,
+    /// Файл загружен и проверен
+    Completed,
+    /// Ошибка загрузки (можно продолжить)
+    Failed,
+    /// Отменена, частично загруженный файл удален
+    Cancelled,
+}
+
+impl DownloadStatus {
+    /// Значение для колонки `downloads.status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DownloadStatus::Queued => "queued",
+            DownloadStatus::Downloading => "downloading",
+            DownloadStatus::Paused => "paused",
+            DownloadStatus::Completed => "completed",
+            DownloadStatus::Failed => "failed",
+            DownloadStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Прочитать статус из колонки `downloads.status` (неизвестный - failed)
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "queued" => DownloadStatus::Queued,
+            "downloading" => DownloadStatus::Downloading,
+            "paused" => DownloadStatus::Paused,
+            "completed" => DownloadStatus::Completed,
+            "cancelled" => DownloadStatus::Cancelled,
+            _ => DownloadStatus::Failed,
+        }
+    }
+
+    /// Загрузка завершена (успешно или отменой) и не может быть продолжена
+    pub fn is_finished(&self) -> bool {
+        matches!(self, DownloadStatus::Completed | DownloadStatus::Cancelled)
+    }
+}
+
+/// Загрузка файла мода
+/// 
+/// Привязана к версии мода на момент начала загрузки (`mod_version_id`).
+/// Та же структура отправляется во frontend событием `download-progress`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Download {
+    pub id: i64,
+    pub mod_id: i64,
+    /// Запись истории версий мода, к которой относится файл
+    pub mod_version_id: Option<i64>,
+    pub version: Option<String>,
+    /// Ссылка на файл
+    pub url: String,
+    pub file_name: String,
+    /// Путь к файлу в директории данных приложения
+    pub path: String,
+    pub status: DownloadStatus,
+    /// Размер файла в байтах (если сервер его сообщил)
+    pub total_bytes: Option<i64>,
+    pub downloaded_bytes: i64,
+    pub content_type: Option<String>,
+    /// SHA-256 загруженного файла (hex)
+    pub sha256: Option<String>,
+    /// Текст последней ошибки
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Установка мода в директорию игры
+/// 
+/// Один мод установлен в директорию не больше одного раза: повторная установка
+/// (обновление) заменяет файлы и запись.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Installation {
+    pub id: i64,
+    pub mod_id: i64,
+    /// Загрузка, из которой установлены файлы
+    pub download_id: Option<i64>,
+    pub version: Option<String>,
+    /// Директория установки
+    pub install_dir: String,
+    pub installed_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Файл, записанный при установке мода
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledFile {
+    pub id: i64,
+    pub installation_id: i64,
+    /// Путь относительно директории установки (через `/`)
+    pub path: String,
+    pub size: i64,
+}
+
+/// Профиль (сборка модов)
+/// 
+/// Именованный набор модов для конкретной установки игры.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    /// Директория игры/модов профиля (для установки модов)
+    pub install_dir: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Мод в профиле
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileMod {
+    pub mod_id: i64,
+    /// Закрепленная версия (None = последняя)
+    #[serde(default)]
+    pub pinned_version: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Позиция в порядке загрузки (с 0)
+    #[serde(default)]
+    pub position: i64,
+}
+
+fn default_enabled() -> bool {
+    true
 }
 
 /// Структура данных для уведомления
@@ -50,12 +487,68 @@ pub struct ModUpdate {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Notification {
     pub id: i64,
-    pub mod_id: i64,
+    /// Тип уведомления
+    #[serde(default)]
+    pub kind: NotificationKind,
+    /// Мод уведомления (None для уведомлений о сайте)
+    pub mod_id: Option<i64>,
     pub site_id: i64,
     pub title: String,
     pub message: String,
     pub read: bool,
     pub created_at: DateTime<Utc>,
+    /// Сохраненная страница, к которой относится уведомление (снимок при сбое парсера)
+    #[serde(default)]
+    pub saved_page_id: Option<i64>,
+}
+
+/// Тип уведомления
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationKind {
+    /// Изменение мода, за которым следит пользователь
+    #[default]
+    ModUpdate,
+    /// Новый мод на сайте
+    NewMod,
+    /// Парсер сайта, вероятно, сломан (см. drift.rs)
+    ParserDrift,
+}
+
+impl NotificationKind {
+    /// Значение для колонки `notifications.kind`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationKind::ModUpdate => "mod_update",
+            NotificationKind::NewMod => "new_mod",
+            NotificationKind::ParserDrift => "parser_drift",
+        }
+    }
+
+    /// Прочитать тип из колонки `notifications.kind` (неизвестный - mod_update)
+    pub fn from_db(value: &str) -> Self {
+        match value {
+            "new_mod" => NotificationKind::NewMod,
+            "parser_drift" => NotificationKind::ParserDrift,
+            _ => NotificationKind::ModUpdate,
+        }
+    }
+}
+
+/// Подозрение на сбой парсера сайта
+///
+/// Отправляется в `DriftMonitor::subscribe` при первой проверке, результат
+/// которой сильно отличается от истории сайта.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParserDrift {
+    pub site_id: i64,
+    pub site_name: String,
+    /// ID проверки, в которой обнаружен сбой
+    pub check_run_id: i64,
+    /// Описание отличий от истории
+    pub reason: String,
+    /// Снимок загруженной страницы (None, если страница не сохранена)
+    pub saved_page_id: Option<i64>,
 }
 
 /// Структура данных для узла парсера
@@ -66,10 +559,13 @@ pub struct ParserNode {
     /// Уникальный идентификатор узла
     pub id: String,
     /// Тип узла: "selector", "extract", "filter", "transform", "output"
+    #[serde(alias = "type")]
     pub node_type: String,
     /// Конфигурация узла в формате JSON
+    #[serde(alias = "data")]
     pub config: serde_json::Value,
     /// Позиция узла на графе (x, y)
+    #[serde(default)]
     pub position: (f64, f64),
 }
 
@@ -81,6 +577,7 @@ pub struct ParserConfig {
     /// Список узлов парсера
     pub nodes: Vec<ParserNode>,
     /// Список связей между узлами (from_id, to_id)
+    #[serde(default)]
     pub connections: Vec<(String, String)>,
 }
 
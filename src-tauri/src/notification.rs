@@ -1,11 +1,14 @@
-use crate::models::{ModUpdate, Notification};
-use crate::database::Database;
+use mod_aggregator::changes::VersionChange;
+use mod_aggregator::models::{ModUpdate, Notification, NotificationKind, ParserDrift};
+use mod_aggregator::database::Database;
 
 /// Сервис для работы с уведомлениями
 /// 
 /// Предоставляет методы для создания и отправки уведомлений об обновлениях модов
+/// и о сбоях парсеров сайтов
 pub struct NotificationService {
     app_handle: tauri::AppHandle,
+    db: Database,
 }
 
 impl NotificationService {
@@ -13,16 +16,18 @@ impl NotificationService {
     /// 
     /// # Параметры
     /// * `app_handle` - handle приложения Tauri для отправки системных уведомлений
+    /// * `db` - общая база данных приложения
     /// 
     /// # Возвращает
     /// Новый экземпляр NotificationService
-    pub fn new(app_handle: tauri::AppHandle) -> Self {
-        NotificationService { app_handle }
+    pub fn new(app_handle: tauri::AppHandle, db: Database) -> Self {
+        NotificationService { app_handle, db }
     }
 
-    /// Создать и отправить уведомление об обновлении мода
+    /// Создать и отправить уведомление об обновлении мода или новом моде
     /// 
     /// Создает запись в базе данных и отправляет системное уведомление.
+    /// Вызывающий код отправляет только обновления с флагом `notify`.
     /// 
     /// # Параметры
     /// * `update` - информация об обновлении мода
@@ -30,26 +35,64 @@ impl NotificationService {
     /// # Возвращает
     /// Пустой результат при успехе или ошибку
     pub async fn notify_update(&self, update: &ModUpdate) -> Result<(), Box<dyn std::error::Error>> {
-        let db = Database::new().await?;
-        
-        let title = format!("Обновление мода");
-        let message = if let (Some(old_v), Some(new_v)) = (&update.old_version, &update.new_version) {
-            format!("Версия изменена: {} → {}", old_v, new_v)
+        let (kind, title, message) = if update.new_mod {
+            let mod_item = self.db.get_mod(update.mod_id).await?;
+            let message = match &update.new_version {
+                Some(version) => format!("{} (версия {})", mod_item.title, version),
+                None => mod_item.title,
+            };
+            (NotificationKind::NewMod, "Новый мод".to_string(), message)
         } else {
-            "Мод обновлен".to_string()
+            (NotificationKind::ModUpdate, "Обновление мода".to_string(), Self::update_message(update))
         };
 
-        let notification = Notification {
+        self.send(Notification {
             id: 0,
-            mod_id: update.mod_id,
+            kind,
+            mod_id: Some(update.mod_id),
             site_id: update.site_id,
             title,
             message,
             read: false,
             created_at: chrono::Utc::now(),
-        };
+            saved_page_id: None,
+        })
+        .await
+    }
 
-        db.add_notification(&notification).await?;
+    /// Создать и отправить уведомление о вероятном сбое парсера сайта
+    /// 
+    /// Уведомление ссылается на снимок страницы, загруженной при проверке,
+    /// чтобы можно было сравнить разметку с конфигурацией парсера.
+    /// 
+    /// # Параметры
+    /// * `drift` - подозрение на сбой парсера (см. drift.rs)
+    /// 
+    /// # Возвращает
+    /// Пустой результат при успехе или ошибку
+    pub async fn notify_parser_drift(&self, drift: &ParserDrift) -> Result<(), Box<dyn std::error::Error>> {
+        let mut message = format!("Результат проверки отличается от обычного: {}", drift.reason);
+        if drift.saved_page_id.is_some() {
+            message.push_str(". Загруженная страница сохранена");
+        }
+
+        self.send(Notification {
+            id: 0,
+            kind: NotificationKind::ParserDrift,
+            mod_id: None,
+            site_id: drift.site_id,
+            title: format!("Парсер сайта {}, вероятно, сломан", drift.site_name),
+            message,
+            read: false,
+            created_at: chrono::Utc::now(),
+            saved_page_id: drift.saved_page_id,
+        })
+        .await
+    }
+
+    /// Сохранить уведомление в базе данных и показать системное уведомление
+    async fn send(&self, notification: Notification) -> Result<(), Box<dyn std::error::Error>> {
+        self.db.add_notification(&notification).await?;
 
         // Show system notification
         #[cfg(not(target_os = "android"))]
@@ -65,5 +108,23 @@ impl NotificationService {
 
         Ok(())
     }
+
+    /// Текст уведомления об изменении мода
+    fn update_message(update: &ModUpdate) -> String {
+        match (&update.version_change, &update.old_version, &update.new_version) {
+            (Some(VersionChange::Upgraded), Some(old_v), Some(new_v)) => {
+                format!("Новая версия: {} → {}", old_v, new_v)
+            }
+            (Some(VersionChange::Downgraded), Some(old_v), Some(new_v)) => {
+                format!("Версия понижена: {} → {}", old_v, new_v)
+            }
+            (Some(VersionChange::Reformatted), Some(old_v), Some(new_v)) => {
+                format!("Изменен формат версии: {} → {}", old_v, new_v)
+            }
+            (Some(_), Some(old_v), Some(new_v)) => format!("Версия изменена: {} → {}", old_v, new_v),
+            (Some(_), None, Some(new_v)) => format!("Указана версия: {}", new_v),
+            _ => "Мод обновлен".to_string(),
+        }
+    }
 }
 
@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use scraper::{Html, Selector};
+
+/// Максимальное количество страниц по умолчанию
+fn default_max_pages() -> usize {
+    10
+}
+
+/// Номер первой страницы по умолчанию
+fn default_start_page() -> u64 {
+    1
+}
+
+/// Конфигурация постраничного обхода каталога
+///
+/// Задается ключом `pagination` в конфигурации парсера сайта. Режим
+/// выбирается полем `mode`:
+/// - `next_link` - переход по ссылке "следующая страница"
+/// - `url_template` - подстановка номера страницы в шаблон URL (`{page}`)
+/// - `offset` - увеличение параметра смещения в query строке
+///
+/// Во всех режимах обход ограничен `max_pages` и останавливается, если
+/// очередная страница не дала ни одного нового URL мода.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum Pagination {
+    /// Переход по ссылке на следующую страницу
+    NextLink {
+        /// CSS селектор ссылки (берется атрибут href)
+        selector: String,
+        #[serde(default = "default_max_pages")]
+        max_pages: usize,
+    },
+    /// Номер страницы в шаблоне URL, например `https://site/mods?page={page}`
+    UrlTemplate {
+        template: String,
+        #[serde(default = "default_start_page")]
+        start_page: u64,
+        #[serde(default = "default_max_pages")]
+        max_pages: usize,
+    },
+    /// Параметр смещения в query строке, например `?offset=20`
+    Offset {
+        param: String,
+        step: u64,
+        #[serde(default)]
+        start: u64,
+        #[serde(default = "default_max_pages")]
+        max_pages: usize,
+    },
+}
+
+impl Pagination {
+    /// Прочитать настройки пагинации из конфигурации парсера
+    ///
+    /// # Параметры
+    /// * `config` - конфигурация парсера сайта
+    ///
+    /// # Возвращает
+    /// Настройки пагинации, None если ключ `pagination` не задан, или ошибку
+    /// разбора (в том числе шаблон URL без `{page}`)
+    pub fn from_config(config: &serde_json::Value) -> Result<Option<Self>, String> {
+        match config.get("pagination") {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(value) => {
+                let pagination: Pagination = serde_json::from_value(value.clone())
+                    .map_err(|e| format!("Invalid pagination config: {}", e))?;
+                match &pagination {
+                    Pagination::NextLink { selector, .. } => {
+                        Selector::parse(selector)
+                            .map_err(|e| format!("Invalid pagination selector: {}", e))?;
+                    }
+                    // Без {page} все страницы совпали бы с первой
+                    Pagination::UrlTemplate { template, .. } if !template.contains("{page}") => {
+                        return Err(format!("Pagination template has no {{page}} placeholder: {}", template));
+                    }
+                    _ => {}
+                }
+                Ok(Some(pagination))
+            }
+        }
+    }
+
+    /// Максимальное количество загружаемых страниц
+    pub fn max_pages(&self) -> usize {
+        match self {
+            Pagination::NextLink { max_pages, .. }
+            | Pagination::UrlTemplate { max_pages, .. }
+            | Pagination::Offset { max_pages, .. } => (*max_pages).max(1),
+        }
+    }
+
+    /// Получить URL первой страницы
+    ///
+    /// # Параметры
+    /// * `list_url` - URL списка модов из конфигурации
+    ///
+    /// # Возвращает
+    /// URL первой страницы или ошибку построения URL
+    pub fn first_url(&self, list_url: &str) -> Result<String, String> {
+        self.page_url(list_url, 0)
+    }
+
+    /// Получить URL следующей страницы
+    ///
+    /// # Параметры
+    /// * `document` - текущая загруженная страница (для режима `next_link`)
+    /// * `current_url` - URL текущей страницы
+    /// * `list_url` - URL списка модов из конфигурации
+    /// * `page_index` - номер следующей страницы, начиная с 0
+    ///
+    /// # Возвращает
+    /// URL следующей страницы или None, если страниц больше нет
+    pub fn next_url(
+        &self,
+        document: &Html,
+        current_url: &str,
+        list_url: &str,
+        page_index: usize,
+    ) -> Option<String> {
+        match self {
+            Pagination::NextLink { selector, .. } => {
+                let selector = Selector::parse(selector).ok()?;
+                let href = document
+                    .select(&selector)
+                    .find_map(|e| e.value().attr("href"))?
+                    .trim();
+                if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+                    return None;
+                }
+                let next = url::Url::parse(current_url).ok()?.join(href).ok()?.to_string();
+                if next == current_url {
+                    None
+                } else {
+                    Some(next)
+                }
+            }
+            _ => self.page_url(list_url, page_index).ok(),
+        }
+    }
+
+    /// Построить URL страницы по номеру (режимы `url_template` и `offset`)
+    ///
+    /// # Параметры
+    /// * `list_url` - URL списка модов из конфигурации
+    /// * `page_index` - номер страницы, начиная с 0
+    ///
+    /// # Возвращает
+    /// URL страницы или ошибку построения URL
+    fn page_url(&self, list_url: &str, page_index: usize) -> Result<String, String> {
+        match self {
+            Pagination::NextLink { .. } => Ok(list_url.to_string()),
+            Pagination::UrlTemplate { template, start_page, .. } => {
+                let page = start_page + page_index as u64;
+                Ok(template.replace("{page}", &page.to_string()))
+            }
+            Pagination::Offset { param, step, start, .. } => {
+                let offset = start + step * page_index as u64;
+                let mut url = url::Url::parse(list_url)
+                    .map_err(|e| format!("Invalid list_url: {}", e))?;
+                // Заменяем параметр смещения, сохраняя остальные параметры запроса
+                let pairs: Vec<(String, String)> = url
+                    .query_pairs()
+                    .filter(|(key, _)| key != param)
+                    .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                    .collect();
+                url.query_pairs_mut()
+                    .clear()
+                    .extend_pairs(pairs)
+                    .append_pair(param, &offset.to_string());
+                Ok(url.to_string())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+    use crate::models::Site;
+    use crate::parser::ParserEngine;
+    use crate::test_support::{MockResponse, MockServer};
+
+    fn pagination(value: serde_json::Value) -> Pagination {
+        Pagination::from_config(&json!({ "pagination": value })).unwrap().unwrap()
+    }
+
+    #[test]
+    fn config_is_validated() {
+        assert!(Pagination::from_config(&json!({})).unwrap().is_none());
+        assert!(Pagination::from_config(&json!({"pagination": null})).unwrap().is_none());
+        let error = Pagination::from_config(&json!({
+            "pagination": {"mode": "url_template", "template": "https://example.com/mods?page=1"}
+        }))
+        .unwrap_err();
+        assert!(error.contains("{page}"));
+        assert!(Pagination::from_config(&json!({"pagination": {"mode": "next_link", "selector": "a[["}})).is_err());
+        assert!(Pagination::from_config(&json!({"pagination": {"mode": "offset", "param": "offset"}})).is_err());
+
+        // max_pages не меньше 1, чтобы загружалась хотя бы первая страница
+        assert_eq!(pagination(json!({"mode": "next_link", "selector": "a.next", "max_pages": 0})).max_pages(), 1);
+        assert_eq!(pagination(json!({"mode": "next_link", "selector": "a.next"})).max_pages(), 10);
+    }
+
+    #[test]
+    fn page_urls_are_built() {
+        let template = pagination(json!({
+            "mode": "url_template",
+            "template": "https://example.com/mods?page={page}",
+            "start_page": 3
+        }));
+        let document = Html::parse_document("");
+        assert_eq!(template.first_url("https://example.com").unwrap(), "https://example.com/mods?page=3");
+        assert_eq!(
+            template.next_url(&document, "https://example.com/mods?page=3", "https://example.com", 1).as_deref(),
+            Some("https://example.com/mods?page=4")
+        );
+
+        // Параметр смещения заменяется, остальные параметры сохраняются
+        let offset = pagination(json!({"mode": "offset", "param": "offset", "step": 20}));
+        let list_url = "https://example.com/mods?sort=new&offset=5&cat=2";
+        assert_eq!(offset.first_url(list_url).unwrap(), "https://example.com/mods?sort=new&cat=2&offset=0");
+        assert_eq!(
+            offset.next_url(&document, list_url, list_url, 2).as_deref(),
+            Some("https://example.com/mods?sort=new&cat=2&offset=40")
+        );
+        assert!(offset.first_url("not a url").is_err());
+    }
+
+    #[test]
+    fn next_link_stops_on_dead_links() {
+        let next = pagination(json!({"mode": "next_link", "selector": "a.next"}));
+        let current = "https://example.com/mods?page=2";
+        let next_url = |href: &str| {
+            let document = Html::parse_document(&format!("<a class=\"next\" href=\"{}\">Next</a>", href));
+            next.next_url(&document, current, "https://example.com/mods", 2)
+        };
+        assert_eq!(next_url("?page=3").as_deref(), Some("https://example.com/mods?page=3"));
+        assert_eq!(next_url("/mods/page/3").as_deref(), Some("https://example.com/mods/page/3"));
+        for href in ["#", "#top", "javascript:void(0)", "", current, "?page=2"] {
+            assert_eq!(next_url(href), None, "{}", href);
+        }
+        assert_eq!(next.next_url(&Html::parse_document("<p>last</p>"), current, current, 2), None);
+    }
+
+    #[tokio::test]
+    async fn pagination_stops_when_page_has_no_new_mods() {
+        // Страница 3 повторяет уже найденные моды, страница 4 не должна загружаться
+        let server = MockServer::start(|request| {
+            let links: &[&str] = match request.path.as_str() {
+                "/mods?page=1" => &["a", "b"],
+                "/mods?page=2" => &["b", "c"],
+                "/mods?page=3" => &["a", "c"],
+                _ => &["d"],
+            };
+            let body: String = links
+                .iter()
+                .map(|name| format!("<div class=\"mod\"><a href=\"https://example.com/{}\">{}</a></div>", name, name))
+                .collect();
+            MockResponse::ok(body).header("Content-Type", "text/html")
+        })
+        .await;
+        let site = Site {
+            id: 5,
+            name: "Paged".to_string(),
+            url: server.url("/mods"),
+            parser_config: json!({
+                "list_selector": ".mod",
+                "url_selector": "a",
+                "title_selector": "a",
+                "pagination": {"mode": "url_template", "template": server.url("/mods?page={page}")}
+            }),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            install_dir: None,
+            notify_new_mods: false,
+        };
+        let mods = ParserEngine::new(reqwest::Client::new()).parse_site(&site).await.unwrap();
+
+        let titles: Vec<_> = mods.iter().map(|m| m.title.as_str()).collect();
+        assert_eq!(titles, ["a", "b", "c"]);
+        assert!(mods.iter().all(|m| m.site_id == 5));
+        let paths: Vec<_> = server.requests().into_iter().map(|request| request.path).collect();
+        assert_eq!(paths, ["/mods?page=1", "/mods?page=2", "/mods?page=3"]);
+    }
+}
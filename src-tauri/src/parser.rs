@@ -1,27 +1,188 @@
-use reqwest;
+use std::collections::HashSet;
 use scraper::{Html, Selector};
 use crate::models::{Site, Mod};
+use crate::check_runs;
+use crate::detail::DetailCrawler;
+use crate::feed::{self, FeedConfig};
+use crate::json_api::JsonApiConfig;
+use crate::releases::{self, Release, ReleasesConfig};
+use crate::parser_graph::GraphExecutor;
+use crate::site_config::SiteParserConfig;
+use crate::source::SourceConfig;
 use chrono::Utc;
+use log::{debug, warn};
+
+/// Преобразовать относительную ссылку в абсолютную
+/// 
+/// # Параметры
+/// * `href` - значение атрибута href/src
+/// * `base_url` - базовый URL сайта (base_url из конфигурации парсера)
+/// 
+/// # Возвращает
+/// Абсолютный URL
+pub(crate) fn resolve_url(href: &str, base_url: &str) -> String {
+    if href.starts_with("http") {
+        href.to_string()
+    } else {
+        format!("{}{}", base_url, href)
+    }
+}
+
+/// Способ извлечения модов из одной страницы
+/// 
+/// Либо граф узлов из визуального редактора, либо плоские селекторы
+/// (`list_selector`, `title_selector` и т.д.).
+enum PageExtractor {
+    /// Граф узлов (ключ `nodes` в конфигурации)
+    Graph(GraphExecutor),
+    /// Плоская конфигурация из селекторов
+    Selectors(FlatSelectors),
+}
+
+impl PageExtractor {
+    /// Подготовить извлечение по конфигурации парсера
+    /// 
+    /// # Параметры
+    /// * `config` - проверенная конфигурация парсера сайта
+    /// 
+    /// # Возвращает
+    /// Готовый экстрактор или ошибку конфигурации
+    fn from_config(config: &SiteParserConfig) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        // Конфигурация из визуального редактора: граф узлов вместо плоских селекторов
+        if let Some(graph) = config.graph() {
+            return Ok(PageExtractor::Graph(GraphExecutor::new(&graph)?));
+        }
+        Ok(PageExtractor::Selectors(FlatSelectors::new(config)?))
+    }
+
+    /// Извлечь моды из загруженной страницы
+    /// 
+    /// # Параметры
+    /// * `document` - разобранная HTML страница
+    /// * `base_url` - базовый URL для относительных ссылок
+    /// 
+    /// # Возвращает
+    /// Вектор модов страницы (site_id проставляет вызывающая сторона)
+    fn extract(&self, document: &Html, base_url: &str) -> Vec<Mod> {
+        match self {
+            PageExtractor::Graph(executor) => executor.execute(document, base_url),
+            PageExtractor::Selectors(selectors) => document
+                .select(&selectors.list)
+                .filter_map(|element| selectors.parse_mod_element(&element, base_url))
+                .collect(),
+        }
+    }
+}
+
+/// Разобранные селекторы плоской конфигурации
+/// 
+/// Селекторы разбираются один раз на сайт, а не для каждого элемента списка.
+struct FlatSelectors {
+    list: Selector,
+    title: Option<Selector>,
+    url: Option<Selector>,
+    version: Option<Selector>,
+    author: Option<Selector>,
+    image: Option<Selector>,
+}
+
+impl FlatSelectors {
+    /// Разобрать селекторы конфигурации
+    /// 
+    /// # Параметры
+    /// * `config` - проверенная конфигурация парсера сайта
+    /// 
+    /// # Возвращает
+    /// Набор селекторов или ошибку
+    fn new(config: &SiteParserConfig) -> Result<Self, String> {
+        let parse = |selector: &Option<String>| {
+            selector
+                .as_deref()
+                .map(|s| Selector::parse(s).map_err(|e| format!("Invalid CSS selector '{}': {}", s, e)))
+                .transpose()
+        };
+        Ok(FlatSelectors {
+            list: parse(&config.list_selector)?.ok_or("Missing list_selector in parser config")?,
+            title: parse(&config.title_selector)?,
+            url: parse(&config.url_selector)?,
+            version: parse(&config.version_selector)?,
+            author: parse(&config.author_selector)?,
+            image: parse(&config.image_selector)?,
+        })
+    }
+
+    /// Распарсить элемент мода из HTML
+    /// 
+    /// # Параметры
+    /// * `element` - HTML элемент, содержащий данные о моде
+    /// * `base_url` - базовый URL для относительных ссылок
+    /// 
+    /// # Возвращает
+    /// Объект мода, если удалось извлечь данные, или None
+    fn parse_mod_element(&self, element: &scraper::element_ref::ElementRef, base_url: &str) -> Option<Mod> {
+        let text = |selector: &Option<Selector>| {
+            selector
+                .as_ref()
+                .and_then(|sel| element.select(sel).next())
+                .map(|e| e.text().collect::<String>().trim().to_string())
+        };
+        let attr = |selector: &Option<Selector>, name: &str| {
+            selector
+                .as_ref()
+                .and_then(|sel| element.select(sel).next())
+                .and_then(|e| e.value().attr(name))
+                .map(|value| resolve_url(value, base_url))
+        };
+
+        let url = attr(&self.url, "href").filter(|url| !url.is_empty())?;
+
+        Some(Mod {
+            id: 0,
+            site_id: 0,
+            title: text(&self.title).unwrap_or_else(|| "Unknown".to_string()),
+            url,
+            version: text(&self.version),
+            author: text(&self.author),
+            description: None,
+            image_url: attr(&self.image, "src"),
+            changes: None,
+            download_url: None,
+            files: Vec::new(),
+            followed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+    }
+}
 
 /// Движок для парсинга сайтов
 /// 
 /// Предоставляет методы для парсинга HTML страниц и извлечения данных о модах
 /// на основе конфигурации парсера сайта
-pub struct ParserEngine;
+#[derive(Clone)]
+pub struct ParserEngine {
+    client: reqwest::Client,
+}
 
 impl ParserEngine {
     /// Создать новый экземпляр парсера
     /// 
+    /// # Параметры
+    /// * `client` - общий HTTP клиент приложения
+    /// 
     /// # Возвращает
     /// Новый экземпляр ParserEngine
-    pub fn new() -> Self {
-        ParserEngine
+    pub fn new(client: reqwest::Client) -> Self {
+        ParserEngine { client }
     }
 
     /// Распарсить сайт и извлечь список модов
     /// 
-    /// Загружает страницу сайта, применяет конфигурацию парсера и извлекает
-    /// данные о модах из HTML.
+    /// Загружает страницы сайта, применяет конфигурацию парсера и извлекает
+    /// данные о модах из HTML (или из ленты RSS/Atom, JSON API и релизов репозитория,
+    /// если задан `source`). Если в конфигурации задан ключ `pagination`,
+    /// обходит каталог постранично, пока страницы дают новые URL модов
+    /// (но не больше `max_pages`).
     /// 
     /// # Параметры
     /// * `site` - объект сайта с конфигурацией парсера
@@ -29,139 +190,256 @@ impl ParserEngine {
     /// # Возвращает
     /// Вектор найденных модов или ошибку
     pub async fn parse_site(&self, site: &Site) -> Result<Vec<Mod>, Box<dyn std::error::Error + Send + Sync>> {
-        let config = &site.parser_config;
-        
-        // Get base URL and list selector from config
-        let list_url = config.get("list_url")
-            .and_then(|v| v.as_str())
-            .unwrap_or(&site.url);
-        
-        let list_selector = config.get("list_selector")
-            .and_then(|v| v.as_str())
-            .ok_or("Missing list_selector in parser config")?;
-
-        // Fetch the page
-        let client = reqwest::Client::builder()
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-            .build()?;
-        
-        let html = client.get(list_url).send().await?.text().await?;
-        let document = Html::parse_document(&html);
-
-        // Parse mod items - clone selector string to avoid lifetime issues
-        let selector_str = list_selector.to_string();
-        let selector = Selector::parse(&selector_str)
-            .map_err(|e| format!("Invalid CSS selector: {}", e))?;
+        // Неверные селекторы и опечатки в ключах - ошибка, а не пустой результат
+        let config = SiteParserConfig::from_value(&site.parser_config)?;
+        let list_url = config.list_url.as_deref().unwrap_or(&site.url);
+        let base_url = config.base_url.as_deref().unwrap_or("");
+
+        let mut mods = match &config.source {
+            Some(SourceConfig::Feed(feed)) => self.parse_feed(site, list_url, feed).await?,
+            Some(SourceConfig::JsonApi(api)) => self.parse_json_api(site, list_url, base_url, api).await?,
+            Some(SourceConfig::Releases(config)) => self.parse_releases(site, list_url, config).await?,
+            None => self.parse_pages(site, &config, list_url, base_url).await?,
+        };
+
+        // Второй этап: страницы модов с описанием, списком изменений и ссылкой на скачивание
+        if let Some(detail) = &config.detail {
+            let crawler = DetailCrawler::new(self.client.clone(), detail)?;
+            mods = crawler.enrich(mods, base_url).await;
+        }
+
+        Ok(mods)
+    }
+
+    /// Загрузить HTML страницы каталога и извлечь моды
+    /// 
+    /// # Параметры
+    /// * `site` - сайт из базы данных
+    /// * `config` - проверенная конфигурация парсера сайта
+    /// * `list_url` - URL первой страницы каталога
+    /// * `base_url` - базовый URL для относительных ссылок
+    /// 
+    /// # Возвращает
+    /// Вектор модов без повторяющихся URL или ошибку
+    async fn parse_pages(
+        &self,
+        site: &Site,
+        config: &SiteParserConfig,
+        list_url: &str,
+        base_url: &str,
+    ) -> Result<Vec<Mod>, Box<dyn std::error::Error + Send + Sync>> {
+        let extractor = PageExtractor::from_config(config)?;
+        let pagination = config.pagination.clone();
+        let max_pages = pagination.as_ref().map_or(1, |p| p.max_pages());
+
+        let mut page_url = match &pagination {
+            Some(p) => Some(p.first_url(list_url)?),
+            None => Some(list_url.to_string()),
+        };
+        let mut seen_urls = HashSet::new();
         let mut mods = Vec::new();
+        let mut page_index = 0;
 
-        for element in document.select(&selector) {
-            if let Some(mod_item) = self.parse_mod_element(&element, &document, config) {
-                mods.push(Mod {
-                    id: 0,
-                    site_id: site.id,
-                    ..mod_item
-                });
+        while let Some(url) = page_url.take() {
+            let html = match self.fetch_html(&url).await {
+                Ok(html) => html,
+                // Ошибка первой страницы - ошибка парсинга, последующих - конец обхода
+                Err(e) if page_index == 0 => return Err(e),
+                Err(e) => {
+                    warn!("Failed to fetch page {} of site {}: {}", url, site.name, e);
+                    break;
+                }
+            };
+            page_index += 1;
+
+            // Документ не переживает await (Html не Send), поэтому разбираем страницу целиком здесь
+            let (page_mods, next_url) = {
+                let document = Html::parse_document(&html);
+                let page_mods = extractor.extract(&document, base_url);
+                let next_url = pagination
+                    .as_ref()
+                    .filter(|_| page_index < max_pages)
+                    .and_then(|p| p.next_url(&document, &url, list_url, page_index));
+                (page_mods, next_url)
+            };
+
+            let mut new_urls = 0;
+            for mod_item in page_mods {
+                if seen_urls.insert(mod_item.url.clone()) {
+                    new_urls += 1;
+                    mods.push(Mod {
+                        id: 0,
+                        site_id: site.id,
+                        ..mod_item
+                    });
+                }
             }
+
+            if new_urls == 0 {
+                debug!("Page {} of site {} has no new mods, stopping pagination", url, site.name);
+                break;
+            }
+            page_url = next_url;
         }
 
         Ok(mods)
     }
 
-    /// Распарсить элемент мода из HTML
+    /// Загрузить ленту RSS/Atom и извлечь моды
     /// 
-    /// Извлекает данные о моде из HTML элемента на основе конфигурации парсера.
+    /// # Параметры
+    /// * `site` - сайт из базы данных
+    /// * `feed_url` - URL ленты
+    /// * `feed` - настройки источника
+    /// 
+    /// # Возвращает
+    /// Вектор модов без повторяющихся URL или ошибку
+    async fn parse_feed(
+        &self,
+        site: &Site,
+        feed_url: &str,
+        feed: &FeedConfig,
+    ) -> Result<Vec<Mod>, Box<dyn std::error::Error + Send + Sync>> {
+        let version_regex = feed.version_regex()?;
+        let xml = self.fetch_html(feed_url).await?;
+        let mut seen_urls = HashSet::new();
+
+        Ok(feed::parse_feed(&xml, feed_url, &version_regex)?
+            .into_iter()
+            .filter(|mod_item| seen_urls.insert(mod_item.url.clone()))
+            .map(|mod_item| Mod {
+                id: 0,
+                site_id: site.id,
+                ..mod_item
+            })
+            .collect())
+    }
+
+    /// Загрузить моды из JSON API
+    /// 
+    /// Обходит страницы API (курсор или номер страницы), пока они дают новые
+    /// URL модов (но не больше `max_pages`).
     /// 
     /// # Параметры
-    /// * `element` - HTML элемент, содержащий данные о моде
-    /// * `_document` - полный HTML документ (не используется, но нужен для совместимости)
-    /// * `config` - конфигурация парсера с селекторами
+    /// * `site` - сайт из базы данных
+    /// * `list_url` - URL списка (если в источнике не задан `url`)
+    /// * `base_url` - базовый URL для относительных ссылок (по умолчанию URL запроса)
+    /// * `api` - настройки источника
     /// 
     /// # Возвращает
-    /// Объект мода, если удалось извлечь данные, или None
-    fn parse_mod_element(
+    /// Вектор модов без повторяющихся URL или ошибку
+    async fn parse_json_api(
         &self,
-        element: &scraper::element_ref::ElementRef,
-        _document: &Html,
-        config: &serde_json::Value,
-    ) -> Option<Mod> {
-        // Extract title
-        let title_selector = config.get("title_selector")
-            .and_then(|v| v.as_str())
-            .and_then(|s| Selector::parse(s).ok());
-        
-        let title = title_selector
-            .and_then(|sel| element.select(&sel).next())
-            .map(|e| e.text().collect::<String>().trim().to_string())
-            .unwrap_or_else(|| "Unknown".to_string());
-
-        // Extract URL
-        let url_selector = config.get("url_selector")
-            .and_then(|v| v.as_str())
-            .and_then(|s| Selector::parse(s).ok());
-        
-        let url = url_selector
-            .and_then(|sel| element.select(&sel).next())
-            .and_then(|e| e.value().attr("href"))
-            .map(|href| {
-                if href.starts_with("http") {
-                    href.to_string()
-                } else {
-                    format!("{}{}", config.get("base_url").and_then(|v| v.as_str()).unwrap_or(""), href)
+        site: &Site,
+        list_url: &str,
+        base_url: &str,
+        api: &JsonApiConfig,
+    ) -> Result<Vec<Mod>, Box<dyn std::error::Error + Send + Sync>> {
+        let source = api.prepare()?;
+        let url = source.request_url(list_url);
+        let base_url = if base_url.is_empty() { url } else { base_url };
+        let max_pages = source.max_pages();
+
+        let mut page = Some(source.first_page());
+        let mut seen_urls = HashSet::new();
+        let mut mods = Vec::new();
+        let mut page_index = 0;
+
+        while let Some(current) = page.take() {
+            let response = source
+                .request(&self.client, url, &current)
+                .send()
+                .await
+                .and_then(|response| {
+                    check_runs::record_http_status(response.status());
+                    response.error_for_status()
+                });
+            let body: serde_json::Value = match response {
+                Ok(response) => match response.json().await {
+                    Ok(body) => body,
+                    Err(e) if page_index == 0 => return Err(format!("Invalid JSON response: {}", e).into()),
+                    Err(e) => {
+                        warn!("Invalid JSON on page {:?} of site {}: {}", current, site.name, e);
+                        break;
+                    }
+                },
+                // Ошибка первой страницы - ошибка парсинга, последующих - конец обхода
+                Err(e) if page_index == 0 => return Err(e.into()),
+                Err(e) => {
+                    warn!("Failed to fetch page {:?} of site {}: {}", current, site.name, e);
+                    break;
                 }
-            })
-            .unwrap_or_default();
+            };
+            page_index += 1;
 
-        if url.is_empty() {
-            return None;
+            let mut new_urls = 0;
+            for mod_item in source.extract(&body, base_url) {
+                if seen_urls.insert(mod_item.url.clone()) {
+                    new_urls += 1;
+                    mods.push(Mod {
+                        id: 0,
+                        site_id: site.id,
+                        ..mod_item
+                    });
+                }
+            }
+
+            if new_urls == 0 {
+                debug!("Page {:?} of site {} has no new mods, stopping pagination", current, site.name);
+                break;
+            }
+            page = source.next_page(&body, &current).filter(|_| page_index < max_pages);
         }
 
-        // Extract version
-        let version_selector = config.get("version_selector")
-            .and_then(|v| v.as_str())
-            .and_then(|s| Selector::parse(s).ok());
-        
-        let version = version_selector
-            .and_then(|sel| element.select(&sel).next())
-            .map(|e| e.text().collect::<String>().trim().to_string());
-
-        // Extract author
-        let author_selector = config.get("author_selector")
-            .and_then(|v| v.as_str())
-            .and_then(|s| Selector::parse(s).ok());
-        
-        let author = author_selector
-            .and_then(|sel| element.select(&sel).next())
-            .map(|e| e.text().collect::<String>().trim().to_string());
-
-        // Extract image
-        let image_selector = config.get("image_selector")
-            .and_then(|v| v.as_str())
-            .and_then(|s| Selector::parse(s).ok());
-        
-        let image_url = image_selector
-            .and_then(|sel| element.select(&sel).next())
-            .and_then(|e| e.value().attr("src"))
-            .map(|src| {
-                if src.starts_with("http") {
-                    src.to_string()
-                } else {
-                    format!("{}{}", config.get("base_url").and_then(|v| v.as_str()).unwrap_or(""), src)
-                }
-            });
+        Ok(mods)
+    }
 
-        Some(Mod {
-            id: 0,
-            site_id: 0,
-            title,
-            url,
-            version,
-            author,
-            description: None,
-            image_url,
-            changes: None,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
-        })
+    /// Загрузить последний релиз репозитория
+    /// 
+    /// # Параметры
+    /// * `site` - сайт из базы данных
+    /// * `repo_url` - URL страницы репозитория (URL мода)
+    /// * `config` - настройки источника
+    /// 
+    /// # Возвращает
+    /// Вектор из одного мода (пустой, если релизов нет) или ошибку
+    async fn parse_releases(
+        &self,
+        site: &Site,
+        repo_url: &str,
+        config: &ReleasesConfig,
+    ) -> Result<Vec<Mod>, Box<dyn std::error::Error + Send + Sync>> {
+        let url = config.releases_url(repo_url)?;
+        let mut request = self.client.get(&url).header("Accept", "application/vnd.github+json");
+        if let Some(token) = config.token()? {
+            request = request.bearer_auth(token);
+        }
+        let response = request.send().await?;
+        check_runs::record_http_status(response.status());
+        let list: Vec<Release> = response.error_for_status()?.json().await?;
+
+        Ok(releases::latest_release_mod(list, config, repo_url)?
+            .map(|mod_item| Mod {
+                id: 0,
+                site_id: site.id,
+                ..mod_item
+            })
+            .into_iter()
+            .collect())
     }
-}
 
+    /// Загрузить HTML страницу
+    /// 
+    /// # Параметры
+    /// * `url` - URL страницы
+    /// 
+    /// # Возвращает
+    /// HTML содержимое страницы или ошибку (в том числе для HTTP статуса ошибки)
+    async fn fetch_html(&self, url: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+        let response = self.client.get(url).send().await?;
+        check_runs::record_http_status(response.status());
+        let html = response.error_for_status()?.text().await?;
+        check_runs::record_page(url, &html);
+        Ok(html)
+    }
+}
@@ -4,6 +4,7 @@ use crate::models::ParserConfig;
 /// Построитель парсеров из CSS селекторов
 /// 
 /// Предоставляет методы для анализа HTML и построения конфигурации парсера
+#[derive(Default)]
 pub struct ParserBuilder;
 
 impl ParserBuilder {
@@ -0,0 +1,751 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use scraper::{ElementRef, Html, Selector};
+use chrono::Utc;
+use crate::models::{Mod, ParserConfig, ParserNode};
+use crate::parser::resolve_url;
+
+/// Ошибки интерпретации графа парсера
+///
+/// Описывают структурные проблемы графа, нарисованного в ParserBuilder:
+/// циклы, связи с несуществующими узлами, неизвестные типы узлов и
+/// некорректную конфигурацию отдельных узлов.
+#[derive(Debug, thiserror::Error)]
+pub enum GraphError {
+    /// Граф содержит цикл (перечислены узлы, не попавшие в топологический порядок)
+    #[error("Граф парсера содержит цикл через узлы: {}", .node_ids.join(", "))]
+    Cycle { node_ids: Vec<String> },
+    /// Связь ссылается на узел, которого нет в графе
+    #[error("Связь {from} → {to} ссылается на несуществующий узел {missing}")]
+    DanglingConnection { from: String, to: String, missing: String },
+    /// Тип узла не поддерживается интерпретатором
+    #[error("Неизвестный тип узла {node_id}: {node_type}")]
+    UnknownNodeType { node_id: String, node_type: String },
+    /// Несколько узлов имеют одинаковый ID
+    #[error("Повторяющийся ID узла: {node_id}")]
+    DuplicateNode { node_id: String },
+    /// Некорректная конфигурация узла (пустой селектор, неверное регулярное выражение и т.д.)
+    #[error("Некорректная конфигурация узла {node_id}: {message}")]
+    InvalidNodeConfig { node_id: String, message: String },
+    /// У узла output нет корневого selector узла или их несколько
+    #[error("Узел output {node_id} должен зависеть ровно от одного корневого selector узла (найдено: {found})")]
+    InvalidOutputRoot { node_id: String, found: usize },
+}
+
+/// Оператор сравнения для узла filter
+#[derive(Debug, Clone)]
+enum FilterOperator {
+    Contains(String),
+    Equals(String),
+    StartsWith(String),
+    EndsWith(String),
+    Regex(regex::Regex),
+}
+
+impl FilterOperator {
+    /// Проверить, проходит ли значение фильтр
+    ///
+    /// # Параметры
+    /// * `value` - проверяемое значение
+    ///
+    /// # Возвращает
+    /// true, если значение удовлетворяет условию
+    fn matches(&self, value: &str) -> bool {
+        match self {
+            FilterOperator::Contains(s) => value.contains(s.as_str()),
+            FilterOperator::Equals(s) => value == s,
+            FilterOperator::StartsWith(s) => value.starts_with(s.as_str()),
+            FilterOperator::EndsWith(s) => value.ends_with(s.as_str()),
+            FilterOperator::Regex(re) => re.is_match(value),
+        }
+    }
+}
+
+/// Функция преобразования для узла transform
+#[derive(Debug, Clone)]
+enum TransformFunction {
+    Trim,
+    Uppercase,
+    Lowercase,
+    Replace { pattern: regex::Regex, replacement: String },
+    ExtractNumber,
+    ExtractDate,
+}
+
+impl TransformFunction {
+    /// Применить преобразование к значению
+    ///
+    /// # Параметры
+    /// * `value` - исходное значение
+    ///
+    /// # Возвращает
+    /// Преобразованное значение или None, если извлечь данные не удалось
+    fn apply(&self, value: &str) -> Option<String> {
+        match self {
+            TransformFunction::Trim => Some(value.trim().to_string()),
+            TransformFunction::Uppercase => Some(value.to_uppercase()),
+            TransformFunction::Lowercase => Some(value.to_lowercase()),
+            TransformFunction::Replace { pattern, replacement } => {
+                Some(pattern.replace_all(value, replacement.as_str()).into_owned())
+            }
+            TransformFunction::ExtractNumber => {
+                // Берем первое число вида 1, 1.2 или 1.2.3 (подходит и для версий)
+                let re = regex::Regex::new(r"\d+(?:[.,]\d+)*").ok()?;
+                re.find(value).map(|m| m.as_str().to_string())
+            }
+            TransformFunction::ExtractDate => {
+                let re = regex::Regex::new(r"\d{4}-\d{2}-\d{2}|\d{1,2}[./]\d{1,2}[./]\d{2,4}").ok()?;
+                re.find(value).map(|m| m.as_str().to_string())
+            }
+        }
+    }
+}
+
+/// Типизированный узел графа после разбора конфигурации
+#[derive(Debug, Clone)]
+enum NodeKind {
+    /// CSS селектор: корневой ищет по документу, вложенный - внутри элементов родителя
+    Selector(Selector),
+    /// Извлечение значения из элемента в именованное поле
+    Extract {
+        selector: Option<Selector>,
+        attribute: String,
+        field: String,
+    },
+    /// Фильтр элементов или значений
+    Filter(FilterOperator),
+    /// Преобразование значения
+    Transform(TransformFunction),
+    /// Формирование записи мода из полей
+    Output { fields: Vec<String> },
+}
+
+/// Значение, вычисленное узлом для одного элемента списка
+#[derive(Debug, Clone)]
+enum NodeValue<'a> {
+    /// Набор HTML элементов
+    Elements(Vec<ElementRef<'a>>),
+    /// Именованное поле мода
+    Field { name: String, value: Option<String> },
+    /// Элемент отброшен фильтром - запись мода не формируется
+    Rejected,
+}
+
+/// Исполнитель графа парсера
+///
+/// Проверяет граф из ParserBuilder.svelte, сортирует узлы в топологическом
+/// порядке и вычисляет их для каждого элемента, найденного корневым
+/// selector узлом. Узлы output превращают собранные поля в записи `Mod`.
+pub struct GraphExecutor {
+    /// Узлы в топологическом порядке
+    order: Vec<String>,
+    /// Разобранные узлы по ID
+    nodes: HashMap<String, NodeKind>,
+    /// Входящие связи узла (в порядке объявления)
+    inputs: HashMap<String, Vec<String>>,
+    /// Корневой selector узел для каждого output узла
+    output_roots: Vec<(String, String)>,
+}
+
+impl GraphExecutor {
+    /// Построить исполнитель из конфигурации парсера
+    ///
+    /// Проверяет типы и конфигурацию узлов, связи и отсутствие циклов.
+    ///
+    /// # Параметры
+    /// * `config` - конфигурация парсера с узлами и связями
+    ///
+    /// # Возвращает
+    /// Готовый к выполнению исполнитель или структурированную ошибку графа
+    pub fn new(config: &ParserConfig) -> Result<Self, GraphError> {
+        let mut nodes = HashMap::new();
+        for node in &config.nodes {
+            if nodes.contains_key(&node.id) {
+                return Err(GraphError::DuplicateNode { node_id: node.id.clone() });
+            }
+            nodes.insert(node.id.clone(), Self::parse_node(node)?);
+        }
+
+        let mut inputs: HashMap<String, Vec<String>> = HashMap::new();
+        let mut outputs: HashMap<String, Vec<String>> = HashMap::new();
+        for (from, to) in &config.connections {
+            for id in [from, to] {
+                if !nodes.contains_key(id) {
+                    return Err(GraphError::DanglingConnection {
+                        from: from.clone(),
+                        to: to.clone(),
+                        missing: id.clone(),
+                    });
+                }
+            }
+            inputs.entry(to.clone()).or_default().push(from.clone());
+            outputs.entry(from.clone()).or_default().push(to.clone());
+        }
+
+        let order = Self::topological_order(config, &inputs, &outputs)?;
+
+        // Для каждого output узла находим единственный корневой selector
+        let mut output_roots = Vec::new();
+        for id in &order {
+            if let Some(NodeKind::Output { .. }) = nodes.get(id) {
+                let roots = Self::find_roots(id, &nodes, &inputs);
+                if roots.len() != 1 {
+                    return Err(GraphError::InvalidOutputRoot {
+                        node_id: id.clone(),
+                        found: roots.len(),
+                    });
+                }
+                output_roots.push((id.clone(), roots[0].clone()));
+            }
+        }
+
+        Ok(GraphExecutor {
+            order,
+            nodes,
+            inputs,
+            output_roots,
+        })
+    }
+
+    /// Разобрать конфигурацию отдельного узла
+    ///
+    /// # Параметры
+    /// * `node` - узел графа в исходном (JSON) виде
+    ///
+    /// # Возвращает
+    /// Типизированный узел или ошибку конфигурации
+    fn parse_node(node: &ParserNode) -> Result<NodeKind, GraphError> {
+        let invalid = |message: String| GraphError::InvalidNodeConfig {
+            node_id: node.id.clone(),
+            message,
+        };
+        let get_str = |key: &str| {
+            node.config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|s| s.trim().to_string())
+                .filter(|s| !s.is_empty())
+        };
+        let parse_selector = |s: &str| {
+            Selector::parse(s).map_err(|e| invalid(format!("неверный CSS селектор '{}': {}", s, e)))
+        };
+
+        match node.node_type.as_str() {
+            "selector" => {
+                let selector = get_str("selector").ok_or_else(|| invalid("пустой селектор".to_string()))?;
+                Ok(NodeKind::Selector(parse_selector(&selector)?))
+            }
+            "extract" => {
+                let selector = get_str("selector").map(|s| parse_selector(&s)).transpose()?;
+                let attribute = get_str("attribute").unwrap_or_else(|| "text".to_string());
+                // Имя поля можно задать явно, иначе выводим его из извлекаемого атрибута
+                let field = get_str("field").unwrap_or_else(|| match attribute.as_str() {
+                    "href" => "url".to_string(),
+                    "src" => "image_url".to_string(),
+                    "html" => "description".to_string(),
+                    _ => "title".to_string(),
+                });
+                Ok(NodeKind::Extract { selector, attribute, field })
+            }
+            "filter" => {
+                let condition = node.config
+                    .get("condition")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let operator = node.config
+                    .get("operator")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("contains");
+                let operator = match operator {
+                    "contains" => FilterOperator::Contains(condition),
+                    "equals" => FilterOperator::Equals(condition),
+                    "starts_with" => FilterOperator::StartsWith(condition),
+                    "ends_with" => FilterOperator::EndsWith(condition),
+                    "regex" => FilterOperator::Regex(
+                        regex::Regex::new(&condition)
+                            .map_err(|e| invalid(format!("неверное регулярное выражение: {}", e)))?,
+                    ),
+                    other => return Err(invalid(format!("неизвестный оператор фильтра '{}'", other))),
+                };
+                Ok(NodeKind::Filter(operator))
+            }
+            "transform" => {
+                let function = node.config
+                    .get("function")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("trim");
+                let function = match function {
+                    "trim" => TransformFunction::Trim,
+                    "uppercase" => TransformFunction::Uppercase,
+                    "lowercase" => TransformFunction::Lowercase,
+                    "replace" => {
+                        let pattern = get_str("pattern")
+                            .ok_or_else(|| invalid("для replace нужен параметр pattern".to_string()))?;
+                        TransformFunction::Replace {
+                            pattern: regex::Regex::new(&pattern)
+                                .map_err(|e| invalid(format!("неверное регулярное выражение: {}", e)))?,
+                            replacement: node.config
+                                .get("replacement")
+                                .and_then(|v| v.as_str())
+                                .unwrap_or("")
+                                .to_string(),
+                        }
+                    }
+                    "extract_number" => TransformFunction::ExtractNumber,
+                    "extract_date" => TransformFunction::ExtractDate,
+                    other => return Err(invalid(format!("неизвестная функция преобразования '{}'", other))),
+                };
+                Ok(NodeKind::Transform(function))
+            }
+            "output" => {
+                let fields = node.config
+                    .get("fields")
+                    .and_then(|v| v.as_array())
+                    .map(|arr| {
+                        arr.iter()
+                            .filter_map(|f| f.as_str())
+                            .map(|f| f.trim().to_string())
+                            .filter(|f| !f.is_empty())
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                Ok(NodeKind::Output { fields })
+            }
+            other => Err(GraphError::UnknownNodeType {
+                node_id: node.id.clone(),
+                node_type: other.to_string(),
+            }),
+        }
+    }
+
+    /// Отсортировать узлы в топологическом порядке (алгоритм Кана)
+    ///
+    /// Порядок объявления узлов сохраняется там, где это возможно, чтобы
+    /// результат был детерминированным.
+    ///
+    /// # Параметры
+    /// * `config` - конфигурация парсера
+    /// * `inputs` - входящие связи узлов
+    /// * `outputs` - исходящие связи узлов
+    ///
+    /// # Возвращает
+    /// Список ID узлов в порядке вычисления или ошибку цикла
+    fn topological_order(
+        config: &ParserConfig,
+        inputs: &HashMap<String, Vec<String>>,
+        outputs: &HashMap<String, Vec<String>>,
+    ) -> Result<Vec<String>, GraphError> {
+        let mut in_degree: HashMap<&str, usize> = config.nodes
+            .iter()
+            .map(|n| (n.id.as_str(), inputs.get(&n.id).map_or(0, |v| v.len())))
+            .collect();
+
+        let mut queue: VecDeque<&str> = config.nodes
+            .iter()
+            .map(|n| n.id.as_str())
+            .filter(|id| in_degree[id] == 0)
+            .collect();
+
+        let mut order = Vec::with_capacity(config.nodes.len());
+        while let Some(id) = queue.pop_front() {
+            order.push(id.to_string());
+            for next in outputs.get(id).into_iter().flatten() {
+                let degree = in_degree.get_mut(next.as_str()).expect("узел проверен при разборе связей");
+                *degree -= 1;
+                if *degree == 0 {
+                    queue.push_back(next.as_str());
+                }
+            }
+        }
+
+        if order.len() != config.nodes.len() {
+            let done: HashSet<&str> = order.iter().map(|s| s.as_str()).collect();
+            return Err(GraphError::Cycle {
+                node_ids: config.nodes
+                    .iter()
+                    .map(|n| n.id.clone())
+                    .filter(|id| !done.contains(id.as_str()))
+                    .collect(),
+            });
+        }
+
+        Ok(order)
+    }
+
+    /// Найти корневые selector узлы, от которых зависит узел
+    ///
+    /// Корневым считается selector узел без входящих связей - он задает
+    /// список элементов (карточек модов) на странице.
+    ///
+    /// # Параметры
+    /// * `id` - ID узла
+    /// * `nodes` - разобранные узлы
+    /// * `inputs` - входящие связи узлов
+    ///
+    /// # Возвращает
+    /// Список ID корневых selector узлов
+    fn find_roots(
+        id: &str,
+        nodes: &HashMap<String, NodeKind>,
+        inputs: &HashMap<String, Vec<String>>,
+    ) -> Vec<String> {
+        let mut roots = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![id.to_string()];
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current.clone()) {
+                continue;
+            }
+            match inputs.get(&current) {
+                Some(parents) if !parents.is_empty() => stack.extend(parents.iter().cloned()),
+                _ => {
+                    if let Some(NodeKind::Selector(_)) = nodes.get(&current) {
+                        roots.push(current);
+                    }
+                }
+            }
+        }
+        roots.sort();
+        roots
+    }
+
+    /// Выполнить граф над HTML документом
+    ///
+    /// Для каждого output узла перебирает элементы его корневого селектора
+    /// и вычисляет все узлы графа в топологическом порядке. Записи без URL
+    /// и отброшенные фильтрами элементы пропускаются.
+    ///
+    /// # Параметры
+    /// * `document` - разобранный HTML документ
+    /// * `base_url` - базовый URL для относительных ссылок
+    ///
+    /// # Возвращает
+    /// Вектор модов (site_id = 0, его проставляет вызывающая сторона)
+    pub fn execute(&self, document: &Html, base_url: &str) -> Vec<Mod> {
+        let mut mods = Vec::new();
+
+        for (output_id, root_id) in &self.output_roots {
+            let root_selector = match self.nodes.get(root_id) {
+                Some(NodeKind::Selector(sel)) => sel,
+                _ => continue,
+            };
+
+            for element in document.select(root_selector) {
+                let mut values: HashMap<&str, NodeValue> = HashMap::new();
+                values.insert(root_id.as_str(), NodeValue::Elements(vec![element]));
+
+                for id in &self.order {
+                    if id == root_id || values.contains_key(id.as_str()) {
+                        continue;
+                    }
+                    let node_inputs: Vec<&NodeValue> = self.inputs
+                        .get(id)
+                        .into_iter()
+                        .flatten()
+                        .filter_map(|input| values.get(input.as_str()))
+                        .collect();
+                    // Узлы без вычисленных входов относятся к другим корням - пропускаем их
+                    if node_inputs.is_empty() {
+                        continue;
+                    }
+                    if let Some(value) = self.evaluate(&self.nodes[id], &node_inputs, base_url) {
+                        values.insert(id.as_str(), value);
+                    }
+                }
+
+                if let Some(mod_item) = self.build_mod(output_id, &values) {
+                    mods.push(mod_item);
+                }
+            }
+        }
+
+        mods
+    }
+
+    /// Вычислить значение узла по значениям его входов
+    ///
+    /// # Параметры
+    /// * `node` - узел для вычисления
+    /// * `inputs` - значения входящих узлов
+    /// * `base_url` - базовый URL для относительных ссылок
+    ///
+    /// # Возвращает
+    /// Значение узла или None для output узлов (они собираются отдельно)
+    fn evaluate<'a>(&self, node: &NodeKind, inputs: &[&NodeValue<'a>], base_url: &str) -> Option<NodeValue<'a>> {
+        if inputs.iter().any(|v| matches!(v, NodeValue::Rejected)) {
+            return Some(NodeValue::Rejected);
+        }
+
+        let elements: Vec<ElementRef<'a>> = inputs
+            .iter()
+            .filter_map(|v| match v {
+                NodeValue::Elements(els) => Some(els.iter().copied()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        let field = inputs.iter().find_map(|v| match v {
+            NodeValue::Field { name, value } => Some((name.clone(), value.clone())),
+            _ => None,
+        });
+
+        match node {
+            NodeKind::Selector(sel) => Some(NodeValue::Elements(
+                elements.iter().flat_map(|e| e.select(sel)).collect(),
+            )),
+            NodeKind::Extract { selector, attribute, field } => {
+                let target = match selector {
+                    Some(sel) => elements.iter().find_map(|e| e.select(sel).next()),
+                    None => elements.first().copied(),
+                };
+                let value = target.and_then(|e| Self::extract_value(&e, attribute, base_url));
+                Some(NodeValue::Field { name: field.clone(), value })
+            }
+            NodeKind::Filter(operator) => match field {
+                Some((name, value)) => {
+                    if value.as_deref().is_some_and(|v| operator.matches(v)) {
+                        Some(NodeValue::Field { name, value })
+                    } else {
+                        Some(NodeValue::Rejected)
+                    }
+                }
+                None => {
+                    let kept: Vec<ElementRef<'a>> = elements
+                        .into_iter()
+                        .filter(|e| operator.matches(e.text().collect::<String>().trim()))
+                        .collect();
+                    if kept.is_empty() {
+                        Some(NodeValue::Rejected)
+                    } else {
+                        Some(NodeValue::Elements(kept))
+                    }
+                }
+            },
+            NodeKind::Transform(function) => field.map(|(name, value)| NodeValue::Field {
+                name,
+                value: value.and_then(|v| function.apply(&v)),
+            }),
+            NodeKind::Output { .. } => None,
+        }
+    }
+
+    /// Извлечь значение атрибута из элемента
+    ///
+    /// # Параметры
+    /// * `element` - HTML элемент
+    /// * `attribute` - "text", "html" или имя атрибута (href, src, data-*)
+    /// * `base_url` - базовый URL для относительных ссылок
+    ///
+    /// # Возвращает
+    /// Значение или None, если атрибут отсутствует
+    fn extract_value(element: &ElementRef, attribute: &str, base_url: &str) -> Option<String> {
+        let value = match attribute {
+            "text" => element.text().collect::<String>().trim().to_string(),
+            "html" => element.inner_html().trim().to_string(),
+            "href" | "src" => resolve_url(element.value().attr(attribute)?, base_url),
+            other => element.value().attr(other)?.trim().to_string(),
+        };
+        if value.is_empty() {
+            None
+        } else {
+            Some(value)
+        }
+    }
+
+    /// Сформировать мод из значений, собранных output узлом
+    ///
+    /// # Параметры
+    /// * `output_id` - ID output узла
+    /// * `values` - вычисленные значения узлов для текущего элемента
+    ///
+    /// # Возвращает
+    /// Мод или None, если элемент отброшен фильтром или у него нет URL
+    fn build_mod(&self, output_id: &str, values: &HashMap<&str, NodeValue>) -> Option<Mod> {
+        let allowed = match self.nodes.get(output_id) {
+            Some(NodeKind::Output { fields }) => fields,
+            _ => return None,
+        };
+
+        let mut fields: HashMap<String, String> = HashMap::new();
+        for input in self.inputs.get(output_id).into_iter().flatten() {
+            match values.get(input.as_str()) {
+                Some(NodeValue::Rejected) => return None,
+                Some(NodeValue::Field { name, value: Some(value) })
+                    if allowed.is_empty() || allowed.contains(name) =>
+                {
+                    fields.entry(name.clone()).or_insert_with(|| value.clone());
+                }
+                _ => {}
+            }
+        }
+
+        let url = fields.remove("url")?;
+
+        Some(Mod {
+            id: 0,
+            site_id: 0,
+            title: fields.remove("title").unwrap_or_else(|| "Unknown".to_string()),
+            url,
+            version: fields.remove("version"),
+            author: fields.remove("author"),
+            description: fields.remove("description"),
+            image_url: fields.remove("image_url"),
+            changes: fields.remove("changes"),
+            download_url: fields.remove("download_url"),
+            files: Vec::new(),
+            followed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn node(id: &str, node_type: &str, config: serde_json::Value) -> ParserNode {
+        ParserNode {
+            id: id.to_string(),
+            node_type: node_type.to_string(),
+            config,
+            position: (0.0, 0.0),
+        }
+    }
+
+    fn graph(nodes: Vec<ParserNode>, connections: &[(&str, &str)]) -> ParserConfig {
+        ParserConfig {
+            nodes,
+            connections: connections.iter().map(|(from, to)| (from.to_string(), to.to_string())).collect(),
+        }
+    }
+
+    fn error(config: &ParserConfig) -> GraphError {
+        GraphExecutor::new(config).err().expect("граф должен быть отклонен")
+    }
+
+    #[test]
+    fn invalid_graphs_are_rejected() {
+        let list = || node("list", "selector", json!({"selector": ".mod"}));
+        let title = || node("title", "extract", json!({}));
+
+        let config = graph(
+            vec![list(), title(), node("a", "transform", json!({})), node("b", "transform", json!({}))],
+            &[("list", "title"), ("title", "a"), ("a", "b"), ("b", "a")],
+        );
+        assert!(matches!(error(&config), GraphError::Cycle { node_ids } if node_ids == ["a", "b"]));
+
+        let config = graph(vec![list(), title()], &[("list", "title"), ("title", "out")]);
+        assert!(matches!(
+            error(&config),
+            GraphError::DanglingConnection { from, to, missing } if (from.as_str(), to.as_str(), missing.as_str()) == ("title", "out", "out")
+        ));
+
+        let config = graph(vec![list(), title(), node("list", "output", json!({}))], &[]);
+        assert!(matches!(error(&config), GraphError::DuplicateNode { node_id } if node_id == "list"));
+
+        let config = graph(vec![list(), node("js", "script", json!({}))], &[]);
+        assert!(matches!(
+            error(&config),
+            GraphError::UnknownNodeType { node_id, node_type } if node_id == "js" && node_type == "script"
+        ));
+
+        for (node_type, node_config) in [
+            ("selector", json!({"selector": "  "})),
+            ("selector", json!({"selector": "div[["})),
+            ("filter", json!({"operator": "regex", "condition": "("})),
+            ("filter", json!({"operator": "between"})),
+            ("transform", json!({"function": "replace"})),
+            ("transform", json!({"function": "reverse"})),
+        ] {
+            let config = graph(vec![node("bad", node_type, node_config.clone())], &[]);
+            assert!(
+                matches!(error(&config), GraphError::InvalidNodeConfig { ref node_id, .. } if node_id == "bad"),
+                "{} {}",
+                node_type,
+                node_config
+            );
+        }
+
+        // Output без корневого селектора и с двумя корнями
+        let config = graph(vec![title(), node("out", "output", json!({}))], &[("title", "out")]);
+        assert!(matches!(error(&config), GraphError::InvalidOutputRoot { node_id, found: 0 } if node_id == "out"));
+        let config = graph(
+            vec![list(), node("other", "selector", json!({"selector": ".card"})), title(), node("out", "output", json!({}))],
+            &[("list", "title"), ("other", "title"), ("title", "out")],
+        );
+        assert!(matches!(error(&config), GraphError::InvalidOutputRoot { found: 2, .. }));
+    }
+
+    #[test]
+    fn graph_builds_mods_from_html() {
+        let html = r#"
+            <div class="mod"><a class="title" href="/mods/trees">  Trees Pack </a><span class="version">Version 1.2.3</span></div>
+            <div class="mod"><a class="title" href="/mods/rocks">Rocks</a><span class="version">v2</span></div>
+            <div class="mod"><a class="title">Trees without link</a></div>
+            <div class="mod"><a class="title" href="https://cdn.example.com/tools">Tools</a></div>
+        "#;
+        let config = graph(
+            vec![
+                node("list", "selector", json!({"selector": ".mod"})),
+                node("title", "extract", json!({"selector": "a.title", "attribute": "text"})),
+                node("trim", "transform", json!({"function": "trim"})),
+                node("only_t", "filter", json!({"operator": "starts_with", "condition": "T"})),
+                node("url", "extract", json!({"selector": "a.title", "attribute": "href"})),
+                node("version", "extract", json!({"selector": ".version", "field": "version"})),
+                node("number", "transform", json!({"function": "extract_number"})),
+                node("out", "output", json!({"fields": ["title", "url", "version"]})),
+            ],
+            &[
+                ("list", "title"),
+                ("title", "trim"),
+                ("trim", "only_t"),
+                ("only_t", "out"),
+                ("list", "url"),
+                ("url", "out"),
+                ("list", "version"),
+                ("version", "number"),
+                ("number", "out"),
+            ],
+        );
+        let executor = GraphExecutor::new(&config).unwrap();
+        let mods = executor.execute(&Html::parse_document(html), "https://example.com");
+
+        // Rocks отброшен фильтром, карточка без ссылки пропущена
+        let found: Vec<_> = mods
+            .iter()
+            .map(|m| (m.title.as_str(), m.url.as_str(), m.version.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Trees Pack", "https://example.com/mods/trees", Some("1.2.3")),
+                ("Tools", "https://cdn.example.com/tools", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn nodes_saved_by_parser_builder_are_read() {
+        // Узел в формате svelte-flow (type/data) и в формате сохранения (node_type/config)
+        let config: ParserConfig = serde_json::from_value(json!({
+            "nodes": [
+                {"id": "list", "type": "selector", "data": {"label": "Selector", "selector": ".mod"}, "position": [100.0, 50.0]},
+                {"id": "url", "type": "extract", "data": {"label": "Extract", "attribute": "href", "selector": "a"}},
+                {"id": "out", "node_type": "output", "config": {"label": "Output", "fields": ["title", "url"]}, "position": [300.0, 50.0]}
+            ],
+            "connections": [["list", "url"], ["url", "out"]]
+        }))
+        .unwrap();
+        assert_eq!(config.nodes[0].node_type, "selector");
+        assert_eq!(config.nodes[0].config["selector"], ".mod");
+        assert_eq!(config.nodes[0].position, (100.0, 50.0));
+        assert_eq!(config.nodes[1].position, (0.0, 0.0));
+
+        let html = r#"<div class="mod"><a href="/trees">Trees</a></div>"#;
+        let mods = GraphExecutor::new(&config).unwrap().execute(&Html::parse_document(html), "https://example.com");
+        assert_eq!(mods.len(), 1);
+        assert_eq!((mods[0].title.as_str(), mods[0].url.as_str()), ("Unknown", "https://example.com/trees"));
+    }
+}
@@ -1,3 +1,146 @@
-#[cfg(feature = "synthira")]
 pub mod synthira;
 
+use std::sync::Arc;
+use async_trait::async_trait;
+use crate::models::{Mod, Site};
+
+pub use synthira::SynthiraParser;
+
+/// Ошибка нативного парсера
+pub type SiteParserError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Нативный парсер конкретного сайта
+///
+/// Используется вместо конфигурируемого `ParserEngine`, когда разметку сайта
+/// проще разобрать кодом, чем селекторами. Реализации регистрируются в
+/// `ParserRegistry`.
+#[async_trait]
+pub trait SiteParser: Send + Sync {
+    /// Уникальное имя парсера (используется в ключе `parser` конфигурации сайта)
+    fn name(&self) -> &str;
+
+    /// Проверить, подходит ли парсер для URL сайта
+    ///
+    /// # Параметры
+    /// * `url` - URL сайта
+    ///
+    /// # Возвращает
+    /// true, если парсер умеет разбирать этот сайт
+    fn matches(&self, url: &str) -> bool;
+
+    /// Получить список модов сайта
+    ///
+    /// # Параметры
+    /// * `site` - сайт из базы данных
+    ///
+    /// # Возвращает
+    /// Вектор модов или ошибку загрузки/парсинга
+    async fn parse(&self, site: &Site) -> Result<Vec<Mod>, SiteParserError>;
+}
+
+/// Значение ключа `parser`, принудительно включающее `ParserEngine`
+pub const GENERIC_PARSER: &str = "generic";
+
+/// Реестр нативных парсеров
+///
+/// Парсер для сайта выбирается по ключу `parser` конфигурации сайта, а если
+/// ключ не задан - по URL сайта (первый подходящий в порядке регистрации).
+#[derive(Clone, Default)]
+pub struct ParserRegistry {
+    parsers: Vec<Arc<dyn SiteParser>>,
+}
+
+impl ParserRegistry {
+    /// Создать реестр со встроенными парсерами
+    ///
+    /// # Параметры
+    /// * `client` - общий HTTP клиент приложения
+    ///
+    /// # Возвращает
+    /// Реестр с зарегистрированными встроенными парсерами
+    pub fn with_builtin(client: reqwest::Client) -> Self {
+        let mut registry = Self::default();
+        registry.register(Arc::new(SynthiraParser::new(client)));
+        registry
+    }
+
+    /// Зарегистрировать парсер
+    ///
+    /// # Параметры
+    /// * `parser` - нативный парсер
+    pub fn register(&mut self, parser: Arc<dyn SiteParser>) {
+        self.parsers.push(parser);
+    }
+
+    /// Имена зарегистрированных парсеров
+    pub fn names(&self) -> Vec<&str> {
+        self.parsers.iter().map(|p| p.name()).collect()
+    }
+
+    /// Найти нативный парсер для сайта
+    ///
+    /// # Параметры
+    /// * `site` - сайт из базы данных
+    ///
+    /// # Возвращает
+    /// Парсер, None если нужно использовать `ParserEngine`, или ошибку,
+    /// если в конфигурации указан незарегистрированный парсер
+    pub fn find(&self, site: &Site) -> Result<Option<Arc<dyn SiteParser>>, String> {
+        self.find_for(&site.url, &site.parser_config)
+    }
+
+    /// Найти нативный парсер по URL и конфигурации сайта
+    ///
+    /// # Параметры
+    /// * `url` - URL сайта
+    /// * `parser_config` - конфигурация парсера сайта
+    ///
+    /// # Возвращает
+    /// Парсер, None если нужно использовать `ParserEngine`, или ошибку,
+    /// если в конфигурации указан незарегистрированный парсер
+    pub fn find_for(&self, url: &str, parser_config: &serde_json::Value) -> Result<Option<Arc<dyn SiteParser>>, String> {
+        match parser_config.get("parser").and_then(|v| v.as_str()) {
+            Some(GENERIC_PARSER) => Ok(None),
+            Some(name) => self
+                .parsers
+                .iter()
+                .find(|p| p.name() == name)
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| format!("Неизвестный парсер: {}", name)),
+            None => Ok(self.parsers.iter().find(|p| p.matches(url)).cloned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn site(url: &str, parser_config: serde_json::Value) -> Site {
+        Site {
+            id: 1,
+            name: "Test".to_string(),
+            url: url.to_string(),
+            parser_config,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            install_dir: None,
+            notify_new_mods: false,
+        }
+    }
+
+    #[test]
+    fn native_parser_is_selected_by_url_or_config() {
+        let registry = ParserRegistry::with_builtin(reqwest::Client::new());
+        let name = |site: &Site| registry.find(site).unwrap().map(|p| p.name().to_string());
+
+        assert_eq!(name(&site("https://synthira.ru/mods", serde_json::json!({}))), Some("synthira".to_string()));
+        assert_eq!(name(&site("https://www.synthira.ru/", serde_json::json!({}))), Some("synthira".to_string()));
+        assert_eq!(name(&site("https://notsynthira.ru/", serde_json::json!({}))), None);
+        assert_eq!(name(&site("https://synthira.ru/", serde_json::json!({"parser": "generic"}))), None);
+        assert_eq!(name(&site("https://mirror.example/", serde_json::json!({"parser": "synthira"}))), Some("synthira".to_string()));
+        assert!(registry.find(&site("https://example.com/", serde_json::json!({"parser": "missing"}))).is_err());
+    }
+}
@@ -1,36 +1,47 @@
-use crate::models::Mod;
-use reqwest;
+use async_trait::async_trait;
 use scraper::{Html, Selector};
 use chrono::Utc;
+use crate::models::{Mod, Site};
+use crate::check_runs;
+use super::{SiteParser, SiteParserError};
 
-pub struct SynthiraParser;
+/// Домен сайта
+const HOST: &str = "synthira.ru";
+
+/// Нативный парсер каталога synthira.ru
+pub struct SynthiraParser {
+    client: reqwest::Client,
+}
 
 impl SynthiraParser {
-    pub fn new() -> Self {
-        SynthiraParser
+    /// Создать парсер
+    ///
+    /// # Параметры
+    /// * `client` - HTTP клиент для загрузки страниц
+    pub fn new(client: reqwest::Client) -> Self {
+        SynthiraParser { client }
     }
 
-    pub async fn parse(&self, url: &str) -> Result<Vec<Mod>, Box<dyn std::error::Error>> {
-        let client = reqwest::Client::builder()
-            .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36")
-            .build()?;
-        
-        let html = client.get(url).send().await?.text().await?;
-        let document = Html::parse_document(&html);
+    /// Разобрать страницу каталога
+    ///
+    /// # Параметры
+    /// * `html` - HTML страницы
+    /// * `url` - URL страницы (для относительных ссылок)
+    ///
+    /// # Возвращает
+    /// Вектор найденных модов
+    fn parse_page(&self, html: &str, url: &str) -> Vec<Mod> {
+        let document = Html::parse_document(html);
 
-        let mut mods = Vec::new();
-        
         // Parse mod items - adjust selectors based on actual synthira.ru structure
         // This is a template that should be adjusted
-        let list_selector = Selector::parse(".mod-item, .article-item, .post-item")?;
-        
-        for element in document.select(&list_selector) {
-            if let Some(mod_item) = self.parse_mod_element(&element, url) {
-                mods.push(mod_item);
-            }
-        }
+        let list_selector = Selector::parse(".mod-item, .article-item, .post-item")
+            .expect("valid list selector");
 
-        Ok(mods)
+        document
+            .select(&list_selector)
+            .filter_map(|element| self.parse_mod_element(&element, url))
+            .collect()
     }
 
     fn parse_mod_element(
@@ -50,13 +61,7 @@ impl SynthiraParser {
         let url = url_selector
             .and_then(|sel| element.select(&sel).next())
             .and_then(|e| e.value().attr("href"))
-            .map(|href| {
-                if href.starts_with("http") {
-                    href.to_string()
-                } else {
-                    format!("{}{}", base_url.trim_end_matches('/'), href)
-                }
-            })
+            .and_then(|href| join_url(base_url, href))
             .unwrap_or_default();
 
         if url.is_empty() || title == "Unknown" {
@@ -80,13 +85,7 @@ impl SynthiraParser {
         let image_url = image_selector
             .and_then(|sel| element.select(&sel).next())
             .and_then(|e| e.value().attr("src"))
-            .map(|src| {
-                if src.starts_with("http") {
-                    src.to_string()
-                } else {
-                    format!("{}{}", base_url.trim_end_matches('/'), src)
-                }
-            });
+            .and_then(|src| join_url(base_url, src));
 
         Some(Mod {
             id: 0,
@@ -98,9 +97,47 @@ impl SynthiraParser {
             description: None,
             image_url,
             changes: None,
+            download_url: None,
+            files: Vec::new(),
+            followed: false,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         })
     }
 }
 
+
+#[async_trait]
+impl SiteParser for SynthiraParser {
+    fn name(&self) -> &str {
+        "synthira"
+    }
+
+    fn matches(&self, url: &str) -> bool {
+        url::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(str::to_lowercase))
+            .is_some_and(|host| host == HOST || host.ends_with(&format!(".{}", HOST)))
+    }
+
+    async fn parse(&self, site: &Site) -> Result<Vec<Mod>, SiteParserError> {
+        let response = self.client.get(&site.url).send().await?;
+        check_runs::record_http_status(response.status());
+        let html = response.error_for_status()?.text().await?;
+        check_runs::record_page(&site.url, &html);
+
+        Ok(self
+            .parse_page(&html, &site.url)
+            .into_iter()
+            .map(|mod_item| Mod { site_id: site.id, ..mod_item })
+            .collect())
+    }
+}
+
+/// Разрешить ссылку относительно URL страницы
+fn join_url(base_url: &str, href: &str) -> Option<String> {
+    url::Url::parse(base_url)
+        .and_then(|base| base.join(href))
+        .map(|u| u.to_string())
+        .ok()
+}
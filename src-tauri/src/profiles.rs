@@ -0,0 +1,455 @@
+use serde::{Deserialize, Serialize};
+use chrono::Utc;
+use log::info;
+use crate::database::Database;
+use crate::models::{Mod, Profile, ProfileMod};
+
+/// Версия формата манифеста профиля
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Ошибки работы с профилями
+#[derive(Debug, thiserror::Error)]
+pub enum ProfileError {
+    #[error("Профиль {0} не найден")]
+    NotFound(i64),
+    #[error("Профиль с именем '{0}' уже существует")]
+    NameTaken(String),
+    #[error("Имя профиля не может быть пустым")]
+    EmptyName,
+    #[error("Мод {0} не найден")]
+    ModNotFound(i64),
+    #[error("Мод {0} указан в профиле дважды")]
+    DuplicateMod(i64),
+    #[error("Некорректный манифест: {0}")]
+    Manifest(String),
+    #[error("Манифест версии {0} не поддерживается, обновите приложение")]
+    UnsupportedVersion(u32),
+    #[error("Ошибка базы данных: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Формат манифеста профиля
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ManifestFormat {
+    Json,
+    Toml,
+}
+
+/// Манифест профиля для обмена между пользователями
+///
+/// Моды указываются по URL: ID в разных базах не совпадают.
+/// Директория установки не экспортируется - она у каждого своя.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileManifest {
+    pub manifest_version: u32,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Моды в порядке загрузки
+    #[serde(default)]
+    pub mods: Vec<ManifestMod>,
+}
+
+/// Мод в манифесте профиля
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestMod {
+    /// URL страницы мода
+    pub url: String,
+    #[serde(default)]
+    pub title: String,
+    /// URL сайта мода (чтобы добавить мод, которого еще нет в базе)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub site_url: Option<String>,
+    /// Закрепленная версия (без поля - последняя)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Результат импорта профиля
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileImport {
+    pub profile: Profile,
+    /// URL модов из манифеста, сайтов которых нет в базе (в профиль не попали)
+    pub missing: Vec<String>,
+}
+
+impl ProfileManifest {
+    /// Прочитать манифест
+    ///
+    /// # Параметры
+    /// * `text` - содержимое манифеста
+    /// * `format` - формат (JSON или TOML)
+    ///
+    /// # Возвращает
+    /// Манифест или ошибку разбора/неподдерживаемой версии
+    pub fn parse(text: &str, format: ManifestFormat) -> Result<Self, ProfileError> {
+        let manifest: ProfileManifest = match format {
+            ManifestFormat::Json => serde_json::from_str(text).map_err(|e| ProfileError::Manifest(e.to_string()))?,
+            ManifestFormat::Toml => toml::from_str(text).map_err(|e| ProfileError::Manifest(e.to_string()))?,
+        };
+        if manifest.manifest_version > MANIFEST_VERSION {
+            return Err(ProfileError::UnsupportedVersion(manifest.manifest_version));
+        }
+        if manifest.name.trim().is_empty() {
+            return Err(ProfileError::EmptyName);
+        }
+        Ok(manifest)
+    }
+
+    /// Записать манифест в текст
+    ///
+    /// # Параметры
+    /// * `format` - формат (JSON или TOML)
+    ///
+    /// # Возвращает
+    /// Текст манифеста или ошибку сериализации
+    pub fn to_text(&self, format: ManifestFormat) -> Result<String, ProfileError> {
+        match format {
+            ManifestFormat::Json => serde_json::to_string_pretty(self).map_err(|e| ProfileError::Manifest(e.to_string())),
+            ManifestFormat::Toml => toml::to_string_pretty(self).map_err(|e| ProfileError::Manifest(e.to_string())),
+        }
+    }
+}
+
+/// Получить профиль, преобразовав отсутствие записи в `ProfileError::NotFound`
+async fn get_profile(db: &Database, id: i64) -> Result<Profile, ProfileError> {
+    db.get_profile(id).await.map_err(|e| match e {
+        sqlx::Error::RowNotFound => ProfileError::NotFound(id),
+        e => ProfileError::Database(e),
+    })
+}
+
+/// Преобразовать ошибку уникальности имени в `ProfileError::NameTaken`
+fn name_error(e: sqlx::Error, name: &str) -> ProfileError {
+    match &e {
+        sqlx::Error::Database(db_error) if db_error.is_unique_violation() => ProfileError::NameTaken(name.to_string()),
+        _ => ProfileError::Database(e),
+    }
+}
+
+/// Проверить и нормализовать имя профиля
+fn profile_name(name: &str) -> Result<&str, ProfileError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ProfileError::EmptyName);
+    }
+    Ok(name)
+}
+
+/// Создать профиль
+///
+/// # Параметры
+/// * `db` - база данных
+/// * `name` - уникальное имя
+/// * `description` - описание
+/// * `install_dir` - директория игры/модов профиля
+///
+/// # Возвращает
+/// Созданный профиль или ошибку
+pub async fn create_profile(
+    db: &Database,
+    name: &str,
+    description: Option<&str>,
+    install_dir: Option<&str>,
+) -> Result<Profile, ProfileError> {
+    let name = profile_name(name)?;
+    db.add_profile(name, description, install_dir).await.map_err(|e| name_error(e, name))
+}
+
+/// Изменить имя, описание и директорию профиля
+///
+/// # Параметры
+/// * `db` - база данных
+/// * `id` - ID профиля
+/// * `name` - новое имя
+/// * `description` - новое описание
+/// * `install_dir` - новая директория игры/модов
+///
+/// # Возвращает
+/// Пустой результат или ошибку
+pub async fn update_profile(
+    db: &Database,
+    id: i64,
+    name: &str,
+    description: Option<&str>,
+    install_dir: Option<&str>,
+) -> Result<(), ProfileError> {
+    let name = profile_name(name)?;
+    get_profile(db, id).await?;
+    db.update_profile(id, name, description, install_dir).await.map_err(|e| name_error(e, name))
+}
+
+/// Заменить список модов профиля
+///
+/// # Параметры
+/// * `db` - база данных
+/// * `id` - ID профиля
+/// * `mods` - моды в порядке загрузки
+///
+/// # Возвращает
+/// Сохраненный список или ошибку, если мод не найден или повторяется
+pub async fn set_profile_mods(db: &Database, id: i64, mods: &[ProfileMod]) -> Result<Vec<ProfileMod>, ProfileError> {
+    get_profile(db, id).await?;
+    let mut seen = std::collections::HashSet::new();
+    for profile_mod in mods {
+        if !seen.insert(profile_mod.mod_id) {
+            return Err(ProfileError::DuplicateMod(profile_mod.mod_id));
+        }
+        db.get_mod(profile_mod.mod_id).await.map_err(|e| match e {
+            sqlx::Error::RowNotFound => ProfileError::ModNotFound(profile_mod.mod_id),
+            e => ProfileError::Database(e),
+        })?;
+    }
+    db.set_profile_mods(id, mods).await?;
+    Ok(db.get_profile_mods(id).await?)
+}
+
+/// Скопировать профиль вместе со списком модов
+///
+/// # Параметры
+/// * `db` - база данных
+/// * `id` - ID исходного профиля
+/// * `name` - имя копии
+///
+/// # Возвращает
+/// Новый профиль или ошибку
+pub async fn clone_profile(db: &Database, id: i64, name: &str) -> Result<Profile, ProfileError> {
+    let source = get_profile(db, id).await?;
+    let name = profile_name(name)?;
+    let profile = db.clone_profile(&source, name).await.map_err(|e| name_error(e, name))?;
+    info!("Cloned profile '{}' into '{}'", source.name, profile.name);
+    Ok(profile)
+}
+
+/// Экспортировать профиль в манифест
+///
+/// # Параметры
+/// * `db` - база данных
+/// * `id` - ID профиля
+/// * `format` - формат манифеста
+///
+/// # Возвращает
+/// Текст манифеста или ошибку
+pub async fn export_profile(db: &Database, id: i64, format: ManifestFormat) -> Result<String, ProfileError> {
+    let profile = get_profile(db, id).await?;
+    let sites = db.get_sites().await?;
+
+    let mut mods = Vec::new();
+    for profile_mod in db.get_profile_mods(id).await? {
+        let mod_item = db.get_mod(profile_mod.mod_id).await?;
+        mods.push(ManifestMod {
+            site_url: sites.iter().find(|site| site.id == mod_item.site_id).map(|site| site.url.clone()),
+            url: mod_item.url,
+            title: mod_item.title,
+            version: profile_mod.pinned_version,
+            enabled: profile_mod.enabled,
+        });
+    }
+
+    ProfileManifest {
+        manifest_version: MANIFEST_VERSION,
+        name: profile.name,
+        description: profile.description,
+        mods,
+    }
+    .to_text(format)
+}
+
+/// Импортировать профиль из манифеста
+///
+/// Моды ищутся по URL. Мод, которого нет в базе, добавляется без версии, если
+/// его сайт (`site_url`) уже есть в базе - данные заполнит следующая проверка
+/// обновлений. Остальные моды возвращаются в `missing`.
+///
+/// # Параметры
+/// * `db` - база данных
+/// * `text` - содержимое манифеста
+/// * `format` - формат манифеста
+/// * `name` - имя нового профиля (None = имя из манифеста)
+///
+/// # Возвращает
+/// Созданный профиль и список пропущенных модов или ошибку
+pub async fn import_profile(
+    db: &Database,
+    text: &str,
+    format: ManifestFormat,
+    name: Option<&str>,
+) -> Result<ProfileImport, ProfileError> {
+    let manifest = ProfileManifest::parse(text, format)?;
+    let profile = create_profile(db, name.unwrap_or(&manifest.name), manifest.description.as_deref(), None).await?;
+    let sites = db.get_sites().await?;
+
+    let mut mods: Vec<ProfileMod> = Vec::new();
+    let mut missing = Vec::new();
+    for entry in &manifest.mods {
+        let mod_item = match db.get_mod_by_url(&entry.url).await? {
+            Some(mod_item) => mod_item,
+            None => {
+                let site = entry
+                    .site_url
+                    .as_deref()
+                    .and_then(|url| sites.iter().find(|site| site.url.trim_end_matches('/') == url.trim_end_matches('/')));
+                let Some(site) = site else {
+                    missing.push(entry.url.clone());
+                    continue;
+                };
+                db.add_mod(&Mod {
+                    id: 0,
+                    site_id: site.id,
+                    title: if entry.title.trim().is_empty() { entry.url.clone() } else { entry.title.clone() },
+                    url: entry.url.clone(),
+                    version: None,
+                    author: None,
+                    description: None,
+                    image_url: None,
+                    changes: None,
+                    download_url: None,
+                    files: Vec::new(),
+                    followed: false,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })
+                .await?
+            }
+        };
+        if mods.iter().any(|m| m.mod_id == mod_item.id) {
+            continue;
+        }
+        mods.push(ProfileMod {
+            mod_id: mod_item.id,
+            pinned_version: entry.version.clone(),
+            enabled: entry.enabled,
+            position: mods.len() as i64,
+        });
+    }
+
+    db.set_profile_mods(profile.id, &mods).await?;
+    info!(
+        "Imported profile '{}' with {} mods ({} missing)",
+        profile.name,
+        mods.len(),
+        missing.len()
+    );
+    Ok(ProfileImport { profile, missing })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    async fn add_mod(db: &Database, site_id: i64, title: &str) -> Mod {
+        db.add_mod(&Mod {
+            id: 0,
+            site_id,
+            title: title.to_string(),
+            url: format!("https://example.com/mods/{}", title),
+            version: Some("1.0".to_string()),
+            author: None,
+            description: None,
+            image_url: None,
+            changes: None,
+            download_url: None,
+            files: Vec::new(),
+            followed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn profile_is_cloned_exported_and_imported() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).await.unwrap();
+        let site = db.add_site("Example", "https://example.com", &json!({})).await.unwrap();
+        let trees = add_mod(&db, site.id, "trees").await;
+        let rocks = add_mod(&db, site.id, "rocks").await;
+
+        let profile = create_profile(&db, " Survival ", Some("Hard mode"), Some("/games/mods")).await.unwrap();
+        assert_eq!(profile.name, "Survival");
+        let mods = vec![
+            ProfileMod { mod_id: rocks.id, pinned_version: Some("0.9".to_string()), enabled: true, position: 0 },
+            ProfileMod { mod_id: trees.id, pinned_version: None, enabled: false, position: 0 },
+        ];
+        let saved = set_profile_mods(&db, profile.id, &mods).await.unwrap();
+        assert_eq!(saved.iter().map(|m| (m.mod_id, m.position)).collect::<Vec<_>>(), vec![(rocks.id, 0), (trees.id, 1)]);
+        assert!(matches!(
+            create_profile(&db, "Survival", None, None).await,
+            Err(ProfileError::NameTaken(_))
+        ));
+
+        let copy = clone_profile(&db, profile.id, "Survival copy").await.unwrap();
+        assert_eq!(copy.install_dir.as_deref(), Some("/games/mods"));
+        let copied = db.get_profile_mods(copy.id).await.unwrap();
+        assert_eq!(
+            copied.iter().map(|m| (m.mod_id, m.pinned_version.as_deref(), m.enabled)).collect::<Vec<_>>(),
+            vec![(rocks.id, Some("0.9"), true), (trees.id, None, false)]
+        );
+        let profile_count = db.get_profiles().await.unwrap().len();
+        assert!(matches!(
+            clone_profile(&db, profile.id, "Survival copy").await,
+            Err(ProfileError::NameTaken(_))
+        ));
+        assert_eq!(db.get_profiles().await.unwrap().len(), profile_count);
+
+        let manifest = export_profile(&db, profile.id, ManifestFormat::Toml).await.unwrap();
+        assert!(manifest.contains("[[mods]]"));
+        assert!(!manifest.contains("/games/mods"));
+
+        // Другая база: сайт есть, модов еще нет; один мод с неизвестного сайта
+        let other_dir = tempfile::tempdir().unwrap();
+        let other = Database::open(&other_dir.path().join("test.db")).await.unwrap();
+        other.add_site("Example", "https://example.com/", &json!({})).await.unwrap();
+        let mut parsed = ProfileManifest::parse(&manifest, ManifestFormat::Toml).unwrap();
+        parsed.mods.push(ManifestMod {
+            url: "https://unknown.example.org/mod".to_string(),
+            title: "Unknown".to_string(),
+            site_url: Some("https://unknown.example.org".to_string()),
+            version: None,
+            enabled: true,
+        });
+        let json_manifest = parsed.to_text(ManifestFormat::Json).unwrap();
+
+        let imported = import_profile(&other, &json_manifest, ManifestFormat::Json, None).await.unwrap();
+        assert_eq!(imported.profile.name, "Survival");
+        assert_eq!(imported.profile.description.as_deref(), Some("Hard mode"));
+        assert_eq!(imported.profile.install_dir, None);
+        assert_eq!(imported.missing, vec!["https://unknown.example.org/mod"]);
+
+        let imported_mods = other.get_profile_mods(imported.profile.id).await.unwrap();
+        let mut urls = Vec::new();
+        for m in &imported_mods {
+            let mod_item = other.get_mod(m.mod_id).await.unwrap();
+            urls.push((mod_item.url, m.pinned_version.clone(), m.enabled));
+        }
+        assert_eq!(
+            urls,
+            vec![
+                (rocks.url.clone(), Some("0.9".to_string()), true),
+                (trees.url.clone(), None, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn newer_manifest_is_rejected() {
+        let text = r#"{"manifest_version": 2, "name": "Future", "mods": []}"#;
+        assert!(matches!(
+            ProfileManifest::parse(text, ManifestFormat::Json),
+            Err(ProfileError::UnsupportedVersion(2))
+        ));
+        let text = "manifest_version = 1\nname = \"Minimal\"\n\n[[mods]]\nurl = \"https://example.com/mods/trees\"\n";
+        let manifest = ProfileManifest::parse(text, ManifestFormat::Toml).unwrap();
+        assert!(manifest.mods[0].enabled);
+        assert_eq!(manifest.mods[0].version, None);
+    }
+}
@@ -0,0 +1,345 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use crate::models::{Mod, ModFile};
+
+/// Базовый URL API по умолчанию (GitHub)
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+/// Количество релизов в одном запросе (с запасом на черновики и pre-release)
+const RELEASES_PER_PAGE: u32 = 20;
+
+/// Конфигурация источника релизов репозитория
+///
+/// Задается как `{"type": "releases", ...}` в ключе `source` конфигурации
+/// парсера. Формат API совпадает у GitHub, Gitea и Forgejo
+/// (`GET {api_url}/repos/{owner}/{repo}/releases`). Сайт соответствует
+/// одному моду: каждый новый релиз становится новой версией мода в истории.
+/// В мод попадает только самый новый релиз (см. `latest_release_mod`), поэтому
+/// история версий начинается с первой проверки сайта.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReleasesConfig {
+    /// Базовый URL API: `https://api.github.com`, `https://git.example.com/api/v1`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// Репозиторий `owner/name` (по умолчанию из пути URL сайта)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Учитывать pre-release релизы
+    #[serde(default)]
+    pub include_prereleases: bool,
+    /// Регулярное выражение имени файла для `download_url` (по умолчанию первый файл)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub asset_regex: Option<String>,
+    /// Токен доступа (заголовок `Authorization: Bearer`)
+    ///
+    /// Хранится в конфигурации сайта открытым текстом, поэтому не
+    /// сериализуется, а в списках сайтов и экспорте скрывается (см.
+    /// `site_config::redact_secrets`). Лучше задавать `token_env`.
+    #[serde(default, skip_serializing)]
+    pub token: Option<String>,
+    /// Имя переменной окружения с токеном доступа (вместо `token`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
+}
+
+/// Релиз из ответа API
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub author: Option<ReleaseAuthor>,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// Автор релиза
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAuthor {
+    pub login: String,
+}
+
+/// Файл релиза
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+    #[serde(default)]
+    pub size: Option<i64>,
+    #[serde(default)]
+    pub content_type: Option<String>,
+}
+
+impl ReleasesConfig {
+    /// Проверить конфигурацию
+    ///
+    /// # Возвращает
+    /// Пустой результат или ошибку с именем некорректного поля
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(api_url) = &self.api_url {
+            url::Url::parse(api_url).map_err(|e| format!("api_url: invalid URL: {}", e))?;
+        }
+        if let Some(repo) = &self.repo {
+            parse_repo(repo).ok_or_else(|| format!("repo: expected 'owner/name', got '{}'", repo))?;
+        }
+        if self.token_env.as_deref().is_some_and(|name| name.trim().is_empty()) {
+            return Err("token_env: expected environment variable name".to_string());
+        }
+        self.asset_regex()?;
+        Ok(())
+    }
+
+    /// Токен доступа из `token_env` или `token`
+    ///
+    /// # Возвращает
+    /// Токен, None если он не задан, или ошибку, если переменная окружения
+    /// `token_env` не установлена
+    pub fn token(&self) -> Result<Option<String>, String> {
+        match &self.token_env {
+            Some(name) => std::env::var(name)
+                .map(Some)
+                .map_err(|_| format!("token_env: environment variable '{}' is not set", name)),
+            None => Ok(self.token.clone()),
+        }
+    }
+
+    /// Скомпилировать регулярное выражение имени файла
+    pub fn asset_regex(&self) -> Result<Option<Regex>, String> {
+        self.asset_regex
+            .as_deref()
+            .map(|pattern| Regex::new(pattern).map_err(|e| format!("asset_regex: {}", e)))
+            .transpose()
+    }
+
+    /// URL списка релизов
+    ///
+    /// # Параметры
+    /// * `site_url` - URL сайта (`https://github.com/owner/repo`), если `repo` не задан
+    ///
+    /// # Возвращает
+    /// URL запроса или ошибку, если репозиторий не удалось определить
+    pub fn releases_url(&self, site_url: &str) -> Result<String, String> {
+        let (owner, name) = match &self.repo {
+            Some(repo) => parse_repo(repo),
+            None => url::Url::parse(site_url).ok().and_then(|url| parse_repo(url.path())),
+        }
+        .ok_or_else(|| format!("Cannot determine repository from '{}', set 'repo'", site_url))?;
+
+        let api_url = self.api_url.as_deref().unwrap_or(DEFAULT_API_URL).trim_end_matches('/');
+        Ok(format!("{}/repos/{}/{}/releases?per_page={}", api_url, owner, name, RELEASES_PER_PAGE))
+    }
+}
+
+/// Разобрать `owner/name` (допускаются `/` по краям и суффикс `.git`)
+fn parse_repo(repo: &str) -> Option<(String, String)> {
+    let mut parts = repo.trim_matches('/').split('/');
+    let owner = parts.next().filter(|s| !s.is_empty())?;
+    let name = parts.next().map(|s| s.trim_end_matches(".git")).filter(|s| !s.is_empty())?;
+    Some((owner.to_string(), name.to_string()))
+}
+
+/// Преобразовать последний подходящий релиз в мод
+///
+/// Черновики пропускаются, pre-release - если не включены в конфигурации.
+/// Последним считается релиз с самой поздней датой публикации.
+///
+/// Более старые релизы отбрасываются: в историю версий (mod_versions) они не
+/// добавляются, даже если вышли до добавления сайта или между двумя
+/// проверками. История копится по одной записи за проверку, в которой
+/// изменился последний релиз.
+///
+/// # Параметры
+/// * `releases` - релизы из ответа API
+/// * `config` - настройки источника
+/// * `mod_url` - URL мода (страница репозитория)
+///
+/// # Возвращает
+/// Мод с версией из тега, списком изменений из описания релиза и файлами
+/// релиза, None если подходящих релизов нет, или ошибку конфигурации
+pub fn latest_release_mod(releases: Vec<Release>, config: &ReleasesConfig, mod_url: &str) -> Result<Option<Mod>, String> {
+    let asset_regex = config.asset_regex()?;
+    let release_date = |release: &Release| release.published_at.or(release.created_at);
+
+    // API возвращает релизы от новых к старым; при равных датах остается первый
+    let mut latest: Option<Release> = None;
+    for release in releases {
+        if release.draft || (release.prerelease && !config.include_prereleases) {
+            continue;
+        }
+        if latest.as_ref().is_none_or(|current| release_date(&release) > release_date(current)) {
+            latest = Some(release);
+        }
+    }
+    let Some(release) = latest else { return Ok(None) };
+
+    let files: Vec<ModFile> = release
+        .assets
+        .iter()
+        .map(|asset| ModFile {
+            name: asset.name.clone(),
+            url: asset.browser_download_url.clone(),
+            size: asset.size,
+            content_type: asset.content_type.clone(),
+        })
+        .collect();
+    let download_url = files
+        .iter()
+        .find(|file| asset_regex.as_ref().is_none_or(|regex| regex.is_match(&file.name)))
+        .map(|file| file.url.clone());
+
+    let repo_name = url::Url::parse(mod_url)
+        .ok()
+        .and_then(|url| parse_repo(url.path()))
+        .or_else(|| config.repo.as_deref().and_then(parse_repo))
+        .map(|(_, name)| name);
+    let title = repo_name
+        .or_else(|| release.name.clone().filter(|name| !name.trim().is_empty()))
+        .unwrap_or_else(|| "Unknown".to_string());
+    let date = release_date(&release).unwrap_or_else(Utc::now);
+
+    Ok(Some(Mod {
+        id: 0,
+        site_id: 0,
+        title,
+        url: mod_url.to_string(),
+        version: Some(release.tag_name),
+        author: release.author.map(|author| author.login),
+        description: None,
+        image_url: None,
+        changes: release.body.map(|body| body.trim().to_string()).filter(|body| !body.is_empty()),
+        download_url,
+        files,
+        followed: false,
+        created_at: date,
+        updated_at: date,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Site;
+    use crate::parser::ParserEngine;
+    use serde_json::json;
+    use crate::test_support::{MockResponse, MockServer};
+
+    #[test]
+    fn repo_is_taken_from_config_or_site_url() {
+        let config = ReleasesConfig::default();
+        assert_eq!(
+            config.releases_url("https://github.com/alice/trees/").unwrap(),
+            "https://api.github.com/repos/alice/trees/releases?per_page=20"
+        );
+        assert!(config.releases_url("https://github.com/").is_err());
+
+        let config = ReleasesConfig {
+            api_url: Some("https://git.example.com/api/v1/".to_string()),
+            repo: Some("bob/rocks.git".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.releases_url("https://example.com").unwrap(),
+            "https://git.example.com/api/v1/repos/bob/rocks/releases?per_page=20"
+        );
+        assert!(ReleasesConfig { repo: Some("bob".to_string()), ..Default::default() }.validate().is_err());
+    }
+
+    #[test]
+    fn only_latest_release_is_kept() {
+        let release = |tag: &str, published_at: &str, prerelease: bool| Release {
+            tag_name: tag.to_string(),
+            name: None,
+            body: Some(format!("Changes in {}", tag)),
+            draft: false,
+            prerelease,
+            published_at: Some(published_at.parse().unwrap()),
+            created_at: None,
+            author: None,
+            assets: Vec::new(),
+        };
+        // Порядок ответа не важен - выбирается самая поздняя дата
+        let releases = vec![
+            release("v1.1.0", "2025-02-01T00:00:00Z", false),
+            release("v1.2.0-rc1", "2025-03-01T00:00:00Z", true),
+            release("v1.0.0", "2025-01-01T00:00:00Z", false),
+            release("v1.2.0", "2025-04-01T00:00:00Z", false),
+        ];
+        let url = "https://github.com/alice/trees";
+
+        let latest = latest_release_mod(releases.clone(), &ReleasesConfig::default(), url).unwrap().unwrap();
+        assert_eq!(latest.version.as_deref(), Some("v1.2.0"));
+        assert_eq!(latest.changes.as_deref(), Some("Changes in v1.2.0"));
+
+        let stable = latest_release_mod(releases[..3].to_vec(), &ReleasesConfig::default(), url).unwrap().unwrap();
+        assert_eq!(stable.version.as_deref(), Some("v1.1.0"));
+        let config = ReleasesConfig { include_prereleases: true, ..Default::default() };
+        let prerelease = latest_release_mod(releases[..3].to_vec(), &config, url).unwrap().unwrap();
+        assert_eq!(prerelease.version.as_deref(), Some("v1.2.0-rc1"));
+
+        let drafts = releases.into_iter().map(|release| Release { draft: true, ..release }).collect();
+        assert!(latest_release_mod(drafts, &ReleasesConfig::default(), url).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn latest_release_is_fetched_from_api() {
+        let releases = json!([
+            {"tag_name": "v2.0.0-rc1", "prerelease": true, "published_at": "2025-03-01T00:00:00Z", "assets": []},
+            {"tag_name": "v1.9.0", "draft": true, "assets": []},
+            {
+                "tag_name": "v1.8.0",
+                "name": "Autumn update",
+                "body": "- New trees\n- Fixed crash\n",
+                "published_at": "2025-02-01T10:00:00Z",
+                "author": {"login": "alice"},
+                "assets": [
+                    {"name": "trees-src.tar.gz", "browser_download_url": "https://files.example.com/src.tar.gz", "size": 10},
+                    {"name": "trees-1.8.0.zip", "browser_download_url": "https://files.example.com/trees.zip", "size": 2048, "content_type": "application/zip"}
+                ]
+            },
+            {"tag_name": "v1.7.0", "published_at": "2025-01-01T10:00:00Z", "assets": []}
+        ]);
+        let server = MockServer::respond_with(MockResponse::ok(releases.to_string()).header("Content-Type", "application/json")).await;
+
+        let site = Site {
+            id: 3,
+            name: "Trees".to_string(),
+            url: "https://github.com/alice/trees".to_string(),
+            parser_config: json!({
+                "source": {"type": "releases", "api_url": server.address(), "asset_regex": "\\.zip$"}
+            }),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            install_dir: None,
+            notify_new_mods: false,
+        };
+        let mods = ParserEngine::new(reqwest::Client::new()).parse_site(&site).await.unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!((requests[0].method.as_str(), requests[0].path.as_str()), ("GET", "/repos/alice/trees/releases?per_page=20"));
+        assert_eq!(mods.len(), 1);
+        let m = &mods[0];
+        assert_eq!(m.site_id, 3);
+        assert_eq!(m.title, "trees");
+        assert_eq!(m.url, "https://github.com/alice/trees");
+        assert_eq!(m.version.as_deref(), Some("v1.8.0"));
+        assert_eq!(m.author.as_deref(), Some("alice"));
+        assert_eq!(m.changes.as_deref(), Some("- New trees\n- Fixed crash"));
+        assert_eq!(m.download_url.as_deref(), Some("https://files.example.com/trees.zip"));
+        assert_eq!(m.files.len(), 2);
+        assert_eq!(m.files[1].size, Some(2048));
+        assert_eq!(m.files[1].content_type.as_deref(), Some("application/zip"));
+    }
+}
@@ -0,0 +1,557 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, TimeZone, Timelike, Utc};
+use futures::future::join_all;
+use log::{info, warn};
+use crate::database::Database;
+use crate::jobs;
+use crate::models::{ModUpdate, Schedule, Site, SiteSchedule};
+use crate::state::AppState;
+use crate::updates;
+
+/// Минимальный интервал между проверками сайта
+pub const MIN_INTERVAL_SECS: i64 = 60;
+
+/// Задержка после первой неудачной проверки, удваивается с каждой следующей
+const BACKOFF_BASE_SECS: i64 = 15 * 60;
+
+/// Максимальная задержка после неудачных проверок
+const BACKOFF_MAX_SECS: i64 = 24 * 3600;
+
+/// Случайный сдвиг - до 10% задержки, но не больше этого значения
+const MAX_JITTER_SECS: i64 = 5 * 60;
+
+/// Разброс первой проверки сайтов, которые еще не проверялись
+const STARTUP_JITTER_SECS: i64 = 60;
+
+/// Максимальное время ожидания фоновой проверки между просмотрами расписаний
+/// (изменения расписаний подхватываются не позже чем через минуту)
+pub const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Ошибки работы с расписаниями
+#[derive(Debug, thiserror::Error)]
+pub enum ScheduleError {
+    #[error("Некорректное cron выражение '{expression}': {reason}")]
+    InvalidCron { expression: String, reason: String },
+    #[error("Интервал проверки должен быть не меньше {0} секунд")]
+    IntervalTooShort(i64),
+    #[error("Сайт {0} не найден")]
+    SiteNotFound(i64),
+    #[error("Ошибка базы данных: {0}")]
+    Database(#[from] sqlx::Error),
+}
+
+/// Cron выражение из 5 полей: минута, час, день месяца, месяц, день недели
+///
+/// Поддерживаются `*`, числа, диапазоны `a-b`, шаги `*/n` и `a-b/n`, списки
+/// через запятую и сокращения `@hourly`, `@daily`, `@weekly`, `@monthly`,
+/// `@yearly`. День недели: 0-7, 0 и 7 - воскресенье. Если заданы и день
+/// месяца, и день недели, достаточно совпадения одного из них (как в cron).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpr {
+    minutes: u64,
+    hours: u64,
+    days: u64,
+    months: u64,
+    weekdays: u64,
+    any_day: bool,
+    any_weekday: bool,
+}
+
+impl FromStr for CronExpr {
+    type Err = String;
+
+    fn from_str(expression: &str) -> Result<Self, Self::Err> {
+        let expression = match expression.trim() {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        let [minute, hour, day, month, weekday] = fields[..] else {
+            return Err(format!("ожидается 5 полей, получено {}", fields.len()));
+        };
+
+        let mut weekdays = parse_field(weekday, 0, 7).map_err(|e| format!("день недели: {}", e))?;
+        // 7 - тоже воскресенье
+        if weekdays & (1 << 7) != 0 {
+            weekdays = (weekdays & !(1 << 7)) | 1;
+        }
+        Ok(CronExpr {
+            minutes: parse_field(minute, 0, 59).map_err(|e| format!("минута: {}", e))?,
+            hours: parse_field(hour, 0, 23).map_err(|e| format!("час: {}", e))?,
+            days: parse_field(day, 1, 31).map_err(|e| format!("день месяца: {}", e))?,
+            months: parse_field(month, 1, 12).map_err(|e| format!("месяц: {}", e))?,
+            weekdays,
+            any_day: day.starts_with('*'),
+            any_weekday: weekday.starts_with('*'),
+        })
+    }
+}
+
+/// Разобрать поле cron выражения в битовую маску допустимых значений
+fn parse_field(field: &str, min: u32, max: u32) -> Result<u64, String> {
+    let mut mask = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step.parse().map_err(|_| format!("некорректный шаг '{}'", step))?;
+                if step == 0 {
+                    return Err("шаг не может быть 0".to_string());
+                }
+                (range, Some(step))
+            }
+            None => (part, None),
+        };
+        let number = |value: &str| -> Result<u32, String> {
+            let n: u32 = value.parse().map_err(|_| format!("некорректное значение '{}'", value))?;
+            if n < min || n > max {
+                return Err(format!("значение {} вне диапазона {}-{}", n, min, max));
+            }
+            Ok(n)
+        };
+        let (start, end) = match range {
+            "*" => (min, max),
+            _ => match range.split_once('-') {
+                Some((start, end)) => (number(start)?, number(end)?),
+                // `5/15` - от 5 до конца диапазона с шагом 15
+                None if step.is_some() => (number(range)?, max),
+                None => {
+                    let n = number(range)?;
+                    (n, n)
+                }
+            },
+        };
+        if start > end {
+            return Err(format!("пустой диапазон {}-{}", start, end));
+        }
+        for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            mask |= 1 << value;
+        }
+    }
+    Ok(mask)
+}
+
+impl CronExpr {
+    fn day_matches(&self, date: NaiveDate) -> bool {
+        let day = self.days & (1 << date.day()) != 0;
+        let weekday = self.weekdays & (1 << date.weekday().num_days_from_sunday()) != 0;
+        match (self.any_day, self.any_weekday) {
+            (true, true) => true,
+            (true, false) => weekday,
+            (false, true) => day,
+            (false, false) => day || weekday,
+        }
+    }
+
+    /// Ближайшее время срабатывания строго после `after`
+    ///
+    /// Время сравнивается в часовом поясе `after`; несуществующее из-за
+    /// перехода на летнее время локальное время пропускается.
+    ///
+    /// # Параметры
+    /// * `after` - момент, после которого ищется срабатывание
+    ///
+    /// # Возвращает
+    /// Время срабатывания или None, если выражение не срабатывает в ближайшие годы
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        // Високосный год и 29 февраля в нужный день недели встречаются раз в 28 лет
+        let limit = start + Duration::days(366 * 28);
+        let mut t = start;
+        while t < limit {
+            if self.months & (1 << t.month()) == 0 {
+                let (year, month) = if t.month() == 12 { (t.year() + 1, 1) } else { (t.year(), t.month() + 1) };
+                t = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.day_matches(t.date()) {
+                t = (t.date() + Duration::days(1)).and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if self.hours & (1 << t.hour()) == 0 {
+                t = t.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if self.minutes & (1 << t.minute()) == 0 {
+                t += Duration::minutes(1);
+                continue;
+            }
+            if let Some(time) = tz.from_local_datetime(&t).earliest() {
+                if time > *after {
+                    return Some(time);
+                }
+            }
+            t += Duration::minutes(1);
+        }
+        None
+    }
+}
+
+/// Проверить расписание перед сохранением
+///
+/// # Параметры
+/// * `schedule` - расписание сайта
+///
+/// # Возвращает
+/// Пустой результат или ошибку с причиной
+pub fn validate(schedule: &Schedule) -> Result<(), ScheduleError> {
+    match schedule {
+        Schedule::Interval { seconds } if *seconds < MIN_INTERVAL_SECS => {
+            Err(ScheduleError::IntervalTooShort(MIN_INTERVAL_SECS))
+        }
+        Schedule::Interval { .. } => Ok(()),
+        Schedule::Cron { expression } => {
+            let invalid = |reason: String| ScheduleError::InvalidCron { expression: expression.clone(), reason };
+            let cron: CronExpr = expression.parse().map_err(invalid)?;
+            cron.next_after(&Local::now())
+                .map(|_| ())
+                .ok_or_else(|| invalid("выражение никогда не срабатывает".to_string()))
+        }
+    }
+}
+
+/// Время следующей проверки по расписанию без учета ошибок и разброса
+fn regular_next(schedule: &Schedule, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    match schedule {
+        Schedule::Interval { seconds } => Some(after + Duration::seconds((*seconds).max(MIN_INTERVAL_SECS))),
+        Schedule::Cron { expression } => {
+            let cron: CronExpr = expression.parse().ok()?;
+            cron.next_after(&after.with_timezone(&Local)).map(|time| time.with_timezone(&Utc))
+        }
+    }
+}
+
+/// Задержка после `failures` неудачных проверок подряд
+///
+/// 15 минут после первой ошибки, дальше удваивается до 24 часов.
+pub fn backoff(failures: i64) -> Duration {
+    if failures <= 0 {
+        return Duration::zero();
+    }
+    let exponent = (failures - 1).min(16) as u32;
+    Duration::seconds(BACKOFF_BASE_SECS.saturating_mul(1 << exponent).min(BACKOFF_MAX_SECS))
+}
+
+/// Случайный сдвиг, чтобы сайты с одинаковым расписанием не проверялись одновременно
+fn jitter(delay: Duration) -> Duration {
+    let max = (delay.num_seconds() / 10).min(MAX_JITTER_SECS);
+    if max <= 0 {
+        return Duration::zero();
+    }
+    Duration::seconds(fastrand::i64(0..=max))
+}
+
+/// Время следующей проверки сайта
+///
+/// Берется ближайшее время по расписанию; после неудачных проверок - не
+/// раньше экспоненциальной задержки (`backoff`). К результату добавляется
+/// случайный сдвиг до 10% задержки.
+///
+/// # Параметры
+/// * `schedule` - расписание сайта
+/// * `now` - момент окончания проверки (или изменения расписания)
+/// * `failures` - количество неудачных проверок подряд
+///
+/// # Возвращает
+/// Время следующей проверки
+pub fn next_run(schedule: &Schedule, now: DateTime<Utc>, failures: i64) -> DateTime<Utc> {
+    let regular = regular_next(schedule, now)
+        .unwrap_or_else(|| now + Duration::seconds(Schedule::DEFAULT_INTERVAL_SECS));
+    let next = regular.max(now + backoff(failures));
+    next + jitter(next - now)
+}
+
+/// Пора ли проверять сайт
+pub fn is_due(schedule: &SiteSchedule, now: DateTime<Utc>) -> bool {
+    schedule.enabled && schedule.next_run_at.is_some_and(|next| next <= now)
+}
+
+/// Изменить расписание сайта
+///
+/// Следующая проверка считается от текущего момента.
+///
+/// # Параметры
+/// * `db` - база данных
+/// * `site_id` - ID сайта
+/// * `schedule` - интервал или cron выражение
+/// * `enabled` - включена ли фоновая проверка сайта
+///
+/// # Возвращает
+/// Сохраненное расписание или ошибку проверки
+pub async fn set_schedule(db: &Database, site_id: i64, schedule: Schedule, enabled: bool) -> Result<SiteSchedule, ScheduleError> {
+    let schedule = match schedule {
+        Schedule::Cron { expression } => Schedule::Cron { expression: expression.trim().to_string() },
+        interval => interval,
+    };
+    validate(&schedule)?;
+    let mut site_schedule = get_schedule(db, site_id).await?;
+    site_schedule.next_run_at = enabled.then(|| next_run(&schedule, Utc::now(), site_schedule.failure_count));
+    site_schedule.schedule = schedule;
+    site_schedule.enabled = enabled;
+    db.save_site_schedule(&site_schedule).await?;
+    Ok(site_schedule)
+}
+
+/// Получить расписание сайта
+pub async fn get_schedule(db: &Database, site_id: i64) -> Result<SiteSchedule, ScheduleError> {
+    db.get_site_schedule(site_id).await.map_err(|e| match e {
+        sqlx::Error::RowNotFound => ScheduleError::SiteNotFound(site_id),
+        e => ScheduleError::Database(e),
+    })
+}
+
+/// Получить сайт для проверки по расписанию
+pub async fn get_site(db: &Database, site_id: i64) -> Result<Site, ScheduleError> {
+    db.get_site(site_id).await.map_err(|e| match e {
+        sqlx::Error::RowNotFound => ScheduleError::SiteNotFound(site_id),
+        e => ScheduleError::Database(e),
+    })
+}
+
+/// Проверить сайт сейчас и записать результат в расписание
+///
+/// Успешная проверка сбрасывает счетчик ошибок, неудачная - увеличивает его
+/// и откладывает следующую проверку (`backoff`).
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+/// * `site` - сайт из базы данных
+///
+/// # Возвращает
+/// Обновления модов сайта или ошибку проверки
+pub async fn run_site(state: &AppState, site: &Site) -> Result<Vec<ModUpdate>, String> {
+    let mut schedule = get_schedule(&state.db, site.id).await.map_err(|e| e.to_string())?;
+
+    let started = Utc::now();
+    let result = updates::check_site(state, site).await;
+    match &result {
+        Ok(_) => {
+            schedule.failure_count = 0;
+            schedule.last_error = None;
+        }
+        Err(e) => {
+            schedule.failure_count += 1;
+            schedule.last_error = Some(e.clone());
+            warn!("Check of site {} failed ({} in a row): {}", site.name, schedule.failure_count, e);
+        }
+    }
+    schedule.last_run_at = Some(started);
+    schedule.next_run_at = schedule
+        .enabled
+        .then(|| next_run(&schedule.schedule, Utc::now(), schedule.failure_count));
+    state.db.save_site_schedule(&schedule).await.map_err(|e| e.to_string())?;
+    result
+}
+
+/// Проверить все сайты, которым пора по расписанию
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+///
+/// # Возвращает
+/// Обновления модов проверенных сайтов или ошибку чтения расписаний
+pub async fn run_due(state: &AppState) -> Result<Vec<ModUpdate>, String> {
+    let due = due_sites(state).await?;
+    Ok(run_sites(state, due).await)
+}
+
+/// Получить сайты, которым пора на проверку по расписанию
+///
+/// Сайтам, которые еще не проверялись (новым или без сохраненного
+/// расписания), назначается проверка в ближайшую минуту со случайным
+/// сдвигом, чтобы после запуска приложения они не проверялись разом.
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+///
+/// # Возвращает
+/// ID сайтов или ошибку чтения расписаний
+pub async fn due_sites(state: &AppState) -> Result<Vec<i64>, String> {
+    let now = Utc::now();
+    let schedules = state.db.get_site_schedules().await.map_err(|e| e.to_string())?;
+
+    let mut due = Vec::new();
+    for mut schedule in schedules.into_iter().filter(|schedule| schedule.enabled) {
+        if schedule.next_run_at.is_none() {
+            schedule.next_run_at = Some(now + Duration::seconds(fastrand::i64(0..=STARTUP_JITTER_SECS)));
+            state.db.save_site_schedule(&schedule).await.map_err(|e| e.to_string())?;
+            continue;
+        }
+        if is_due(&schedule, now) {
+            due.push(schedule.site_id);
+        }
+    }
+    Ok(due)
+}
+
+/// Проверить сайты по расписанию
+///
+/// Сайты проверяются одновременно в пределах лимитов `state.checks`, в
+/// задаче (jobs.rs) после каждого сайта сообщается ход проверки с именем
+/// сайта. Ошибки проверки записываются в расписания сайтов (`run_site`).
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+/// * `site_ids` - ID сайтов
+///
+/// # Возвращает
+/// Обновления модов успешно проверенных сайтов
+pub async fn run_sites(state: &AppState, site_ids: Vec<i64>) -> Vec<ModUpdate> {
+    let total = site_ids.len();
+    let done = AtomicUsize::new(0);
+    let results = join_all(site_ids.into_iter().map(|site_id| {
+        let done = &done;
+        async move {
+            let site = get_site(&state.db, site_id).await;
+            // Сайт мог быть удален после выбора сайтов для проверки
+            let name = site.as_ref().map_or_else(|_| format!("Сайт {}", site_id), |site| site.name.clone());
+            info!("Running scheduled check of site {}", name);
+            let result = match site {
+                Ok(site) => run_site(state, &site).await,
+                Err(e) => Err(e.to_string()),
+            };
+            jobs::report_progress(done.fetch_add(1, Ordering::SeqCst) + 1, total, name);
+            result
+        }
+    }))
+    .await;
+    results.into_iter().filter_map(Result::ok).flatten().collect()
+}
+
+/// Отложить непроверенные сайты до следующего времени по расписанию
+///
+/// Используется после отмены фоновой проверки, чтобы отмененные сайты не
+/// проверялись снова через минуту. Сайты, которые успели проверить, не меняются.
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+/// * `site_ids` - ID сайтов отмененной проверки
+///
+/// # Возвращает
+/// Пустой результат или ошибку базы данных
+pub async fn postpone(state: &AppState, site_ids: &[i64]) -> Result<(), ScheduleError> {
+    let now = Utc::now();
+    for &site_id in site_ids {
+        let mut schedule = get_schedule(&state.db, site_id).await?;
+        if is_due(&schedule, now) {
+            schedule.next_run_at = Some(next_run(&schedule.schedule, now, schedule.failure_count));
+            state.db.save_site_schedule(&schedule).await?;
+        }
+    }
+    Ok(())
+}
+
+/// Сколько ждать до следующей проверки по расписанию
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+///
+/// # Возвращает
+/// Время до ближайшей проверки, не больше `POLL_INTERVAL`
+pub async fn next_wakeup(state: &AppState) -> std::time::Duration {
+    let Ok(schedules) = state.db.get_site_schedules().await else {
+        return POLL_INTERVAL;
+    };
+    let now = Utc::now();
+    schedules
+        .iter()
+        .filter(|schedule| schedule.enabled)
+        .map(|schedule| schedule.next_run_at.map_or(Duration::zero(), |next| next - now))
+        .min()
+        .and_then(|delay| delay.to_std().ok())
+        .map_or(POLL_INTERVAL, |delay| delay.min(POLL_INTERVAL))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDateTime;
+    use serde_json::json;
+    use crate::models::JobKind;
+    use crate::test_support::test_state;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M").unwrap().and_utc()
+    }
+
+    fn next(expression: &str, after: &str) -> Option<String> {
+        let cron: CronExpr = expression.parse().unwrap();
+        cron.next_after(&utc(after)).map(|time| time.format("%Y-%m-%d %H:%M").to_string())
+    }
+
+    #[test]
+    fn cron_expressions_are_parsed_and_evaluated() {
+        assert_eq!(next("*/15 * * * *", "2025-03-07 10:07").as_deref(), Some("2025-03-07 10:15"));
+        assert_eq!(next("*/15 * * * *", "2025-03-07 10:15").as_deref(), Some("2025-03-07 10:30"));
+        // Пятница вечером -> понедельник утром
+        assert_eq!(next("30 9 * * 1-5", "2025-03-07 10:00").as_deref(), Some("2025-03-10 09:30"));
+        // День месяца ИЛИ день недели (воскресенье как 7)
+        assert_eq!(next("0 0 20 * 7", "2025-03-07 10:00").as_deref(), Some("2025-03-09 00:00"));
+        assert_eq!(next("@monthly", "2025-12-15 00:00").as_deref(), Some("2026-01-01 00:00"));
+        assert_eq!(next("0 12 29 2 *", "2025-03-01 00:00").as_deref(), Some("2028-02-29 12:00"));
+        assert_eq!(next("0 0 30 2 *", "2025-03-01 00:00"), None);
+
+        for invalid in ["* * * *", "60 * * * *", "*/0 * * * *", "5-1 * * * *", "a * * * *"] {
+            assert!(invalid.parse::<CronExpr>().is_err(), "{}", invalid);
+        }
+        assert!(validate(&Schedule::Cron { expression: "0 0 31 4 *".to_string() }).is_err());
+        assert!(validate(&Schedule::Interval { seconds: 30 }).is_err());
+    }
+
+    #[test]
+    fn failures_back_off_exponentially() {
+        assert_eq!(backoff(0), Duration::zero());
+        assert_eq!(backoff(1), Duration::minutes(15));
+        assert_eq!(backoff(3), Duration::hours(1));
+        assert_eq!(backoff(100), Duration::hours(24));
+
+        let now = utc("2025-03-07 10:00");
+        let hourly = Schedule::Interval { seconds: 3600 };
+        // Пока задержка меньше интервала, действует расписание
+        let next = next_run(&hourly, now, 1);
+        assert!(next >= now + Duration::hours(1) && next <= now + Duration::minutes(66));
+        let next = next_run(&hourly, now, 5);
+        assert!(next >= now + Duration::hours(4) && next <= now + Duration::minutes(245));
+    }
+
+    #[tokio::test]
+    async fn failed_scheduled_check_is_backed_off() {
+        let (_dir, state) = test_state().await;
+        // Порт 9 (discard) закрыт - проверка завершится ошибкой соединения
+        let site = state
+            .db
+            .add_site("Down", "http://127.0.0.1:9/feed", &json!({"source": {"type": "feed"}}))
+            .await
+            .unwrap();
+
+        // Первый просмотр расписаний только назначает проверку
+        assert!(run_due(&state).await.unwrap().is_empty());
+        let mut schedule = state.db.get_site_schedule(site.id).await.unwrap();
+        assert_eq!(schedule.schedule, Schedule::default());
+        assert!(schedule.next_run_at.unwrap() <= Utc::now() + Duration::seconds(STARTUP_JITTER_SECS));
+        assert!(next_wakeup(&state).await <= POLL_INTERVAL);
+
+        schedule.next_run_at = Some(Utc::now() - Duration::seconds(1));
+        state.db.save_site_schedule(&schedule).await.unwrap();
+        run_due(&state).await.unwrap();
+        let schedule = state.db.get_site_schedule(site.id).await.unwrap();
+        assert_eq!(schedule.failure_count, 1);
+        assert!(schedule.last_error.is_some() && schedule.last_run_at.is_some());
+        assert!(schedule.next_run_at.unwrap() >= Utc::now() + Duration::minutes(59));
+
+        // Ход проверки в задаче подписан именем сайта
+        let check = async { Ok(run_sites(&state, vec![site.id]).await) };
+        state.jobs.run(JobKind::CheckUpdates, "Проверка", check).await.unwrap();
+        assert_eq!(state.jobs.list()[0].message.as_deref(), Some("Down"));
+
+        let cron = Schedule::Cron { expression: " @daily ".to_string() };
+        let saved = set_schedule(&state.db, site.id, cron, false).await.unwrap();
+        assert_eq!((saved.schedule, saved.enabled, saved.next_run_at), (Schedule::Cron { expression: "@daily".to_string() }, false, None));
+        assert!(matches!(set_schedule(&state.db, 999, Schedule::default(), true).await, Err(ScheduleError::SiteNotFound(999))));
+    }
+}
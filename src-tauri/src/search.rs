@@ -0,0 +1,185 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use crate::models::Mod;
+
+/// Количество результатов поиска по умолчанию
+pub const DEFAULT_SEARCH_LIMIT: i64 = 50;
+
+/// Максимальное количество результатов за один запрос
+pub const MAX_SEARCH_LIMIT: i64 = 200;
+
+/// Маркеры начала и конца совпадения в сниппете FTS5 (заменяются на `<mark>`)
+pub const MATCH_START: char = '\u{2}';
+pub const MATCH_END: char = '\u{3}';
+
+/// Фильтры поиска модов
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SearchFilters {
+    /// Только моды сайта
+    #[serde(default)]
+    pub site_id: Option<i64>,
+    /// Точное совпадение автора без учета регистра
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Моды, обновленные не раньше даты
+    #[serde(default)]
+    pub updated_from: Option<DateTime<Utc>>,
+    /// Моды, обновленные не позже даты
+    #[serde(default)]
+    pub updated_to: Option<DateTime<Utc>>,
+    /// Только моды с подпиской
+    #[serde(default)]
+    pub followed_only: bool,
+}
+
+/// Порядок результатов поиска
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSort {
+    /// По релевантности (bm25); без запроса - по дате обновления
+    #[default]
+    Relevance,
+    /// Сначала недавно обновленные
+    Updated,
+    /// Сначала недавно добавленные
+    Created,
+    /// По названию
+    Title,
+}
+
+/// Найденный мод
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    #[serde(rename = "mod")]
+    pub mod_item: Mod,
+    /// Фрагмент текста с совпадениями в `<mark>` (HTML экранирован),
+    /// None если поиск без запроса
+    pub snippet: Option<String>,
+}
+
+/// Страница результатов поиска
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchResults {
+    /// Общее количество найденных модов без учета limit/offset
+    pub total: i64,
+    pub hits: Vec<SearchHit>,
+}
+
+/// Преобразовать пользовательский запрос в запрос FTS5
+///
+/// Синтаксис FTS5 пользователю не доступен: каждое слово берется в кавычки
+/// и ищется как префикс, слова объединяются через AND. Так запрос с
+/// кавычками, `-` или `:` не приводит к ошибке разбора.
+///
+/// # Параметры
+/// * `query` - строка поиска
+///
+/// # Возвращает
+/// Запрос для MATCH или None, если в строке нет слов
+pub fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .filter(|term| term.chars().any(char::is_alphanumeric))
+        .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// Экранировать HTML в сниппете и заменить маркеры совпадений на `<mark>`
+///
+/// # Параметры
+/// * `snippet` - результат функции `snippet()` с маркерами `MATCH_START`/`MATCH_END`
+///
+/// # Возвращает
+/// Безопасный для вставки в HTML фрагмент
+pub fn highlight(snippet: &str) -> String {
+    let mut html = String::with_capacity(snippet.len());
+    for c in snippet.chars() {
+        match c {
+            MATCH_START => html.push_str("<mark>"),
+            MATCH_END => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::models::ModFile;
+
+    fn test_mod(site_id: i64, title: &str, author: &str, description: &str) -> Mod {
+        Mod {
+            id: 0,
+            site_id,
+            title: title.to_string(),
+            url: format!("https://example.com/{}", title.to_lowercase().replace(' ', "-")),
+            version: Some("1.0".to_string()),
+            author: Some(author.to_string()),
+            description: Some(description.to_string()),
+            image_url: None,
+            changes: None,
+            download_url: None,
+            files: Vec::<ModFile>::new(),
+            followed: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn user_query_is_quoted() {
+        assert_eq!(fts_query("  big tree"), Some("\"big\"* \"tree\"*".to_string()));
+        assert_eq!(fts_query("say \"hi\" -x"), Some("\"say\"* \"\"\"hi\"\"\"* \"-x\"*".to_string()));
+        assert_eq!(fts_query(" - : "), None);
+        assert_eq!(highlight("a <b> \u{2}tree\u{3}"), "a &lt;b&gt; <mark>tree</mark>");
+    }
+
+    #[tokio::test]
+    async fn search_uses_index_filters_and_snippets() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::open(&dir.path().join("test.db")).await.unwrap();
+        let site = db.add_site("Site", "https://example.com", &serde_json::json!({})).await.unwrap();
+        let other = db.add_site("Other", "https://other.example.com", &serde_json::json!({})).await.unwrap();
+
+        let trees = db.add_mod(&test_mod(site.id, "Tall Trees", "Alice", "Adds <tall> forests")).await.unwrap();
+        db.add_mod(&test_mod(site.id, "Rocks", "Bob", "Boulders near trees")).await.unwrap();
+        db.add_mod(&test_mod(other.id, "Treehouse", "alice", "Houses")).await.unwrap();
+
+        let results = db.search_mods("tree", &SearchFilters::default(), SearchSort::Relevance, 10, 0).await.unwrap();
+        assert_eq!(results.total, 3);
+        // Совпадение в названии весит больше, чем в описании
+        assert_ne!(results.hits[2].mod_item.title, "Tall Trees");
+        assert_eq!(results.hits.iter().find(|hit| hit.mod_item.id == trees.id).unwrap().snippet.as_deref(), Some("Tall <mark>Trees</mark>"));
+
+        let filters = SearchFilters { author: Some("ALICE".to_string()), site_id: Some(site.id), ..Default::default() };
+        let results = db.search_mods("tree", &filters, SearchSort::Title, 10, 0).await.unwrap();
+        assert_eq!(results.total, 1);
+        assert_eq!(results.hits[0].mod_item.id, trees.id);
+
+        // Индекс обновляется вместе с модом
+        let mut renamed = trees.clone();
+        renamed.description = Some("Now with lakes".to_string());
+        db.update_mod(trees.id, &renamed).await.unwrap();
+        let results = db.search_mods("lakes", &SearchFilters::default(), SearchSort::Relevance, 10, 0).await.unwrap();
+        assert_eq!(results.hits[0].snippet.as_deref(), Some("Now with <mark>lakes</mark>"));
+        assert_eq!(db.search_mods("forests", &SearchFilters::default(), SearchSort::Relevance, 10, 0).await.unwrap().total, 0);
+
+        // Без запроса - фильтры и постраничный вывод
+        db.set_mod_followed(trees.id, true).await.unwrap();
+        let followed = SearchFilters { followed_only: true, ..Default::default() };
+        let results = db.search_mods("", &followed, SearchSort::Relevance, 10, 0).await.unwrap();
+        assert_eq!((results.total, results.hits[0].snippet.clone()), (1, None));
+        let page = db.search_mods("", &SearchFilters::default(), SearchSort::Title, 1, 1).await.unwrap();
+        assert_eq!((page.total, page.hits.len(), page.hits[0].mod_item.title.as_str()), (3, 1, "Tall Trees"));
+        let future = SearchFilters { updated_from: Some(Utc::now() + chrono::Duration::days(1)), ..Default::default() };
+        assert_eq!(db.search_mods("", &future, SearchSort::Updated, 10, 0).await.unwrap().total, 0);
+    }
+}
@@ -0,0 +1,478 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use scraper::Selector;
+use log::warn;
+use crate::detail::DetailConfig;
+use crate::models::{ParserConfig, ParserNode, Site};
+use crate::pagination::Pagination;
+use crate::parser_graph::GraphExecutor;
+use crate::parsers::ParserRegistry;
+use crate::source::SourceConfig;
+
+/// Текущая версия схемы конфигурации парсера
+///
+/// Конфигурации без `config_version` (созданные до появления схемы) имеют
+/// версию 0 и обновляются функцией `upgrade`.
+pub const CURRENT_CONFIG_VERSION: u32 = 1;
+
+/// Поля с CSS селекторами плоской конфигурации
+const SELECTOR_FIELDS: [&str; 6] = [
+    "list_selector",
+    "title_selector",
+    "url_selector",
+    "version_selector",
+    "author_selector",
+    "image_selector",
+];
+
+/// Строковые поля конфигурации
+const STRING_FIELDS: [&str; 3] = ["parser", "list_url", "base_url"];
+
+/// Поля, которые обрабатываются отдельно от строковых
+const OTHER_FIELDS: [&str; 6] = ["config_version", "source", "pagination", "detail", "nodes", "connections"];
+
+/// Поля источника данных (`source`) с секретами
+const SECRET_SOURCE_FIELDS: [&str; 1] = ["token"];
+
+/// Значение, которым в конфигурации заменяются секреты
+pub const REDACTED_SECRET: &str = "********";
+
+/// Ключи, которые сохранял старый конструктор парсера (`<атрибут>_selector`)
+const LEGACY_RENAMES: [(&str, &str); 3] = [
+    ("text_selector", "title_selector"),
+    ("href_selector", "url_selector"),
+    ("src_selector", "image_selector"),
+];
+
+/// Конфигурация парсера сайта
+///
+/// Хранится в `sites.parser_config` как JSON. Либо плоские селекторы
+/// (`list_selector`, `title_selector`, ...), либо граф узлов из визуального
+/// редактора (`nodes` + `connections`); для нативных парсеров (`parser`)
+/// и источников, отличных от HTML (`source`), селекторы не нужны.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SiteParserConfig {
+    /// Версия схемы конфигурации
+    #[serde(default)]
+    pub config_version: u32,
+    /// Имя нативного парсера или "generic" (см. parsers/)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parser: Option<String>,
+    /// URL страницы со списком модов (по умолчанию URL сайта)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_url: Option<String>,
+    /// Базовый URL для относительных ссылок
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Источник данных, отличный от HTML (см. source.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<SourceConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list_selector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_selector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url_selector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_selector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author_selector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub image_selector: Option<String>,
+    /// Постраничный обход каталога (см. pagination.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pagination: Option<Pagination>,
+    /// Обход страниц модов (см. detail.rs)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detail: Option<DetailConfig>,
+    /// Узлы графа парсера
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<ParserNode>,
+    /// Связи между узлами графа
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub connections: Vec<(String, String)>,
+}
+
+/// Ошибка в одном поле конфигурации
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[error("{field}: {message}")]
+pub struct FieldError {
+    /// Имя поля (`version_selector`, `pagination`, ...)
+    pub field: String,
+    /// Описание ошибки
+    pub message: String,
+}
+
+/// Ошибки проверки конфигурации парсера
+#[derive(Debug, Clone, Serialize, Deserialize, thiserror::Error)]
+#[error("{}", .errors.iter().map(|e| e.to_string()).collect::<Vec<_>>().join("; "))]
+pub struct ConfigValidationError {
+    pub errors: Vec<FieldError>,
+}
+
+/// Проверить, нужно ли обновить сохраненную конфигурацию
+///
+/// # Параметры
+/// * `value` - конфигурация парсера из базы данных
+///
+/// # Возвращает
+/// true, если версия конфигурации меньше текущей
+pub fn needs_upgrade(value: &Value) -> bool {
+    config_version(value) < CURRENT_CONFIG_VERSION
+}
+
+/// Обновить конфигурацию до текущей версии схемы
+///
+/// Версия 0 -> 1: ключи старого конструктора (`text_selector`, `href_selector`,
+/// `src_selector`) переименовываются, пустые строки удаляются. Неизвестные
+/// ключи сохраняются, чтобы обновление никогда не теряло данные.
+///
+/// # Параметры
+/// * `value` - конфигурация парсера любой версии
+///
+/// # Возвращает
+/// Конфигурацию текущей версии
+pub fn upgrade(value: &Value) -> Value {
+    let mut map = value.as_object().cloned().unwrap_or_default();
+
+    if config_version(value) < 1 {
+        for (old, new) in LEGACY_RENAMES {
+            if let Some(selector) = map.remove(old) {
+                if !has_text(&map, new) {
+                    map.insert(new.to_string(), selector);
+                }
+            }
+        }
+        for field in SELECTOR_FIELDS.iter().chain(STRING_FIELDS.iter()) {
+            if map.get(*field).is_some_and(|v| v.is_null() || v.as_str().is_some_and(|s| s.trim().is_empty())) {
+                map.remove(*field);
+            }
+        }
+    }
+
+    map.insert("config_version".to_string(), Value::from(CURRENT_CONFIG_VERSION));
+    Value::Object(map)
+}
+
+impl SiteParserConfig {
+    /// Прочитать конфигурацию для парсинга
+    ///
+    /// Обновляет конфигурацию до текущей версии и проверяет ее. Неизвестные
+    /// ключи только логируются, чтобы сохраненные ранее сайты продолжали работать.
+    ///
+    /// # Параметры
+    /// * `value` - конфигурация парсера сайта
+    ///
+    /// # Возвращает
+    /// Типизированную конфигурацию или ошибки по полям
+    pub fn from_value(value: &Value) -> Result<Self, ConfigValidationError> {
+        check(&upgrade(value), false, true)
+    }
+
+    /// Граф узлов для `GraphExecutor`, если конфигурация задана графом
+    pub fn graph(&self) -> Option<ParserConfig> {
+        (!self.nodes.is_empty()).then(|| ParserConfig {
+            nodes: self.nodes.clone(),
+            connections: self.connections.clone(),
+        })
+    }
+}
+
+/// Проверить конфигурацию перед сохранением сайта
+///
+/// В отличие от `SiteParserConfig::from_value` неизвестные ключи считаются
+/// ошибкой (обычно это опечатка в имени поля).
+///
+/// # Параметры
+/// * `parsers` - реестр нативных парсеров
+/// * `url` - URL сайта
+/// * `value` - конфигурация парсера
+///
+/// # Возвращает
+/// Конфигурацию текущей версии для записи в базу данных или ошибки по полям
+pub fn validate_for_site(parsers: &ParserRegistry, url: &str, value: &Value) -> Result<Value, ConfigValidationError> {
+    let upgraded = upgrade(value);
+    // Сайту с нативным парсером селекторы не нужны
+    let native = match parsers.find_for(url, &upgraded) {
+        Ok(parser) => parser.is_some(),
+        Err(message) => {
+            return Err(ConfigValidationError {
+                errors: vec![FieldError { field: "parser".to_string(), message }],
+            })
+        }
+    };
+    check(&upgraded, true, !native)?;
+    Ok(upgraded)
+}
+
+/// Скрыть секреты конфигурации (токен источника релизов)
+///
+/// Используется для списков сайтов и экспорта. Заданный секрет заменяется
+/// на `REDACTED_SECRET`, чтобы было видно, что он есть.
+///
+/// # Параметры
+/// * `value` - конфигурация парсера
+///
+/// # Возвращает
+/// Копию конфигурации без секретов
+pub fn redact_secrets(value: &Value) -> Value {
+    let mut value = value.clone();
+    if let Some(source) = value.get_mut("source").and_then(Value::as_object_mut) {
+        for field in SECRET_SOURCE_FIELDS {
+            if source.get(field).is_some_and(|secret| !secret.is_null()) {
+                source.insert(field.to_string(), Value::from(REDACTED_SECRET));
+            }
+        }
+    }
+    value
+}
+
+/// Скрыть секреты в конфигурации парсера сайта (см. `redact_secrets`)
+pub fn redact_site(site: Site) -> Site {
+    Site {
+        parser_config: redact_secrets(&site.parser_config),
+        ..site
+    }
+}
+
+/// Вернуть сохраненные секреты в конфигурацию
+///
+/// Форма сайта получает конфигурацию со скрытыми секретами и отправляет
+/// `REDACTED_SECRET` обратно без изменений. Такое значение заменяется
+/// секретом из сохраненной конфигурации (или удаляется, если его там нет).
+///
+/// # Параметры
+/// * `value` - новая конфигурация парсера
+/// * `stored` - сохраненная конфигурация сайта
+///
+/// # Возвращает
+/// Конфигурацию с настоящими секретами
+pub fn restore_secrets(value: &Value, stored: &Value) -> Value {
+    let mut value = value.clone();
+    if let Some(source) = value.get_mut("source").and_then(Value::as_object_mut) {
+        for field in SECRET_SOURCE_FIELDS {
+            if source.get(field).and_then(Value::as_str) != Some(REDACTED_SECRET) {
+                continue;
+            }
+            match stored.get("source").and_then(|stored| stored.get(field)) {
+                Some(secret) => source.insert(field.to_string(), secret.clone()),
+                None => source.remove(field),
+            };
+        }
+    }
+    value
+}
+
+/// Проверить обновленную конфигурацию
+///
+/// # Параметры
+/// * `value` - конфигурация текущей версии
+/// * `reject_unknown` - считать неизвестные ключи ошибкой
+/// * `require_selectors` - требовать `list_selector`/`url_selector` (если нет графа и `source`)
+///
+/// # Возвращает
+/// Типизированную конфигурацию или все найденные ошибки
+fn check(value: &Value, reject_unknown: bool, require_selectors: bool) -> Result<SiteParserConfig, ConfigValidationError> {
+    let empty = Map::new();
+    let map = value.as_object().unwrap_or(&empty);
+    let mut errors = Vec::new();
+    let mut error = |field: &str, message: String| errors.push(FieldError { field: field.to_string(), message });
+
+    for key in map.keys() {
+        let known = SELECTOR_FIELDS.contains(&key.as_str())
+            || STRING_FIELDS.contains(&key.as_str())
+            || OTHER_FIELDS.contains(&key.as_str());
+        if known {
+            continue;
+        }
+        if reject_unknown {
+            error(key, "неизвестное поле".to_string());
+        } else {
+            warn!("Unknown parser config field '{}' is ignored", key);
+        }
+    }
+
+    for field in SELECTOR_FIELDS.iter().chain(STRING_FIELDS.iter()) {
+        if map.get(*field).is_some_and(|v| !v.is_string()) {
+            error(field, "должно быть строкой".to_string());
+        }
+    }
+
+    for field in SELECTOR_FIELDS {
+        if let Some(selector) = map.get(field).and_then(Value::as_str) {
+            if let Err(e) = Selector::parse(selector) {
+                error(field, format!("некорректный CSS селектор: {}", e));
+            }
+        }
+    }
+
+    for field in ["list_url", "base_url"] {
+        if let Some(url) = map.get(field).and_then(Value::as_str) {
+            if let Err(e) = url::Url::parse(url) {
+                error(field, format!("некорректный URL: {}", e));
+            }
+        }
+    }
+
+    let has_source = match SourceConfig::from_config(value) {
+        Ok(source) => source.is_some(),
+        Err(message) => {
+            error("source", message);
+            true
+        }
+    };
+    if let Err(message) = Pagination::from_config(value) {
+        error("pagination", message);
+    }
+    if let Err(message) = DetailConfig::from_config(value) {
+        error("detail", message);
+    }
+
+    let has_graph = map.get("nodes").and_then(Value::as_array).is_some_and(|nodes| !nodes.is_empty());
+    if has_graph {
+        match serde_json::from_value::<ParserConfig>(value.clone()) {
+            Ok(graph) => {
+                if let Err(e) = GraphExecutor::new(&graph) {
+                    error("nodes", e.to_string());
+                }
+            }
+            Err(e) => error("nodes", format!("некорректный граф парсера: {}", e)),
+        }
+    } else if require_selectors && !has_source {
+        for field in ["list_selector", "url_selector"] {
+            if !has_text(map, field) {
+                error(field, "обязательное поле".to_string());
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        serde_json::from_value(value.clone()).map_err(|e| ConfigValidationError {
+            errors: vec![FieldError { field: String::new(), message: e.to_string() }],
+        })
+    } else {
+        Err(ConfigValidationError { errors })
+    }
+}
+
+/// Версия конфигурации (0, если не указана)
+fn config_version(value: &Value) -> u32 {
+    value
+        .get("config_version")
+        .and_then(Value::as_u64)
+        .and_then(|v| u32::try_from(v).ok())
+        .unwrap_or(0)
+}
+
+/// Проверить, что поле - непустая строка
+fn has_text(map: &Map<String, Value>, field: &str) -> bool {
+    map.get(field).and_then(Value::as_str).is_some_and(|s| !s.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn fields(err: ConfigValidationError) -> Vec<String> {
+        err.errors.into_iter().map(|e| e.field).collect()
+    }
+
+    #[test]
+    fn legacy_config_is_upgraded() {
+        let legacy = json!({
+            "list_selector": ".mod",
+            "text_selector": "h2",
+            "href_selector": "a",
+            "version_selector": "",
+            "custom": 1
+        });
+        assert!(needs_upgrade(&legacy));
+
+        let upgraded = upgrade(&legacy);
+        assert!(!needs_upgrade(&upgraded));
+        assert_eq!(upgraded["title_selector"], "h2");
+        assert_eq!(upgraded["url_selector"], "a");
+        assert!(upgraded.get("text_selector").is_none());
+        assert!(upgraded.get("version_selector").is_none());
+        // Неизвестные ключи не теряются
+        assert_eq!(upgraded["custom"], 1);
+
+        let config = SiteParserConfig::from_value(&legacy).unwrap();
+        assert_eq!(config.title_selector.as_deref(), Some("h2"));
+    }
+
+    #[test]
+    fn errors_are_reported_per_field() {
+        let registry = ParserRegistry::default();
+        let err = validate_for_site(
+            &registry,
+            "https://example.com",
+            &json!({
+                "version_selector": "div[",
+                "titel_selector": "h2",
+                "pagination": {"mode": "next_link"}
+            }),
+        )
+        .unwrap_err();
+
+        let mut names = fields(err);
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["list_selector", "pagination", "titel_selector", "url_selector", "version_selector"]
+        );
+    }
+
+    #[test]
+    fn native_parser_does_not_need_selectors() {
+        let registry = ParserRegistry::with_builtin(reqwest::Client::new());
+        let config = validate_for_site(&registry, "https://synthira.ru/", &json!({})).unwrap();
+        assert_eq!(config["config_version"], CURRENT_CONFIG_VERSION);
+
+        let err = validate_for_site(&registry, "https://example.com/", &json!({"parser": "missing"})).unwrap_err();
+        assert_eq!(fields(err), vec!["parser"]);
+    }
+
+    #[test]
+    fn feed_source_does_not_need_selectors() {
+        let registry = ParserRegistry::default();
+        let config = validate_for_site(
+            &registry,
+            "https://example.com/feed.xml",
+            &json!({"source": {"type": "feed", "version_regex": "v(\\d+)"}}),
+        )
+        .unwrap();
+        let typed = SiteParserConfig::from_value(&config).unwrap();
+        assert!(matches!(typed.source, Some(SourceConfig::Feed(_))));
+
+        let err = validate_for_site(
+            &registry,
+            "https://example.com/feed.xml",
+            &json!({"source": {"type": "feed", "version_regex": "v("}}),
+        )
+        .unwrap_err();
+        assert_eq!(fields(err), vec!["source"]);
+    }
+
+    #[test]
+    fn secrets_are_redacted_and_restored() {
+        let stored = json!({"source": {"type": "releases", "repo": "alice/trees", "token": "ghp_secret"}});
+        let redacted = redact_secrets(&stored);
+        assert_eq!(redacted["source"]["token"], REDACTED_SECRET);
+        assert_eq!(redacted["source"]["repo"], "alice/trees");
+        assert_eq!(redact_secrets(&json!({"list_selector": ".mod"})), json!({"list_selector": ".mod"}));
+
+        // Форма вернула скрытый токен - остается сохраненный
+        let mut edited = redacted.clone();
+        edited["source"]["repo"] = json!("alice/rocks");
+        let restored = restore_secrets(&edited, &stored);
+        assert_eq!(restored["source"]["token"], "ghp_secret");
+        assert_eq!(restored["source"]["repo"], "alice/rocks");
+        // Новый токен заменяет сохраненный
+        edited["source"]["token"] = json!("ghp_new");
+        assert_eq!(restore_secrets(&edited, &stored)["source"]["token"], "ghp_new");
+        // Скрытое значение без сохраненного токена не сохраняется
+        assert!(restore_secrets(&redacted, &json!({})).pointer("/source/token").is_none());
+    }
+}
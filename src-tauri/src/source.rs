@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use crate::feed::FeedConfig;
+use crate::json_api::JsonApiConfig;
+use crate::releases::ReleasesConfig;
+
+/// Источник данных сайта
+///
+/// Задается ключом `source` в конфигурации парсера сайта. Тип выбирается
+/// полем `type`:
+/// - `feed` - лента RSS/Atom (см. feed.rs)
+/// - `json_api` - JSON API с путями к полям (см. json_api.rs)
+/// - `releases` - релизы репозитория GitHub/Gitea/Forgejo (см. releases.rs)
+///
+/// Если ключ не задан, сайт парсится как HTML страница по CSS селекторам
+/// или графу узлов.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum SourceConfig {
+    /// Лента RSS или Atom
+    Feed(FeedConfig),
+    /// JSON API
+    JsonApi(Box<JsonApiConfig>),
+    /// Релизы репозитория
+    Releases(ReleasesConfig),
+}
+
+impl SourceConfig {
+    /// Прочитать источник данных из конфигурации парсера
+    ///
+    /// # Параметры
+    /// * `config` - конфигурация парсера сайта
+    ///
+    /// # Возвращает
+    /// Источник, None если ключ `source` не задан (HTML), или ошибку разбора
+    pub fn from_config(config: &serde_json::Value) -> Result<Option<Self>, String> {
+        match config.get("source") {
+            None | Some(serde_json::Value::Null) => Ok(None),
+            Some(value) => {
+                let source: SourceConfig = serde_json::from_value(value.clone())
+                    .map_err(|e| format!("Invalid source config: {}", e))?;
+                match &source {
+                    SourceConfig::Feed(feed) => {
+                        feed.version_regex()?;
+                    }
+                    SourceConfig::JsonApi(api) => {
+                        api.prepare()?;
+                    }
+                    SourceConfig::Releases(releases) => releases.validate()?,
+                }
+                Ok(Some(source))
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use std::path::Path;
+use std::time::Duration;
+use crate::check_queue::{CheckLimits, CheckQueue};
+use crate::database::Database;
+use crate::downloads::DownloadManager;
+use crate::drift::DriftMonitor;
+use crate::installer::Installer;
+use crate::jobs::JobManager;
+use crate::parser::ParserEngine;
+use crate::parsers::ParserRegistry;
+
+/// User-Agent для всех HTTP запросов приложения
+pub const USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36";
+
+/// Общее состояние приложения
+///
+/// Создается один раз при запуске (в `setup`) и передается в Tauri команды
+/// через managed state. Пул подключений SQLite и HTTP клиент внутри
+/// используют `Arc`, поэтому клонирование дешевое.
+pub struct AppState {
+    /// База данных (общий пул подключений)
+    pub db: Database,
+    /// HTTP клиент для загрузки страниц и ресурсов
+    pub http: reqwest::Client,
+    /// Движок парсинга сайтов (использует тот же HTTP клиент)
+    pub engine: ParserEngine,
+    /// Нативные парсеры сайтов (имеют приоритет над `engine`)
+    pub parsers: ParserRegistry,
+    /// Менеджер загрузок файлов модов
+    pub downloads: DownloadManager,
+    /// Установщик модов в директорию игры
+    pub installer: Installer,
+    /// Обнаружение сбоев парсеров по истории проверок
+    pub drift: DriftMonitor,
+    /// Лимиты одновременных проверок сайтов и их ход
+    pub checks: CheckQueue,
+    /// Фоновые задачи с прогрессом и отменой
+    pub jobs: JobManager,
+}
+
+impl AppState {
+    /// Создать состояние приложения
+    ///
+    /// Открывает базу данных (с применением миграций), создает общий HTTP клиент
+    /// и переводит в паузу загрузки, прерванные прошлым закрытием приложения.
+    ///
+    /// # Параметры
+    /// * `db_path` - путь к файлу базы данных
+    /// * `data_dir` - директория данных приложения (для загруженных файлов и снимков страниц)
+    ///
+    /// # Возвращает
+    /// Состояние приложения или ошибку
+    pub async fn new(db_path: &Path, data_dir: &Path) -> Result<Self, Box<dyn std::error::Error + Send + Sync>> {
+        let db = Database::open(db_path).await?;
+        let http = build_http_client()?;
+        let downloads = DownloadManager::new(db.clone(), data_dir)?;
+        let installer = Installer::new(db.clone());
+        let drift = DriftMonitor::new(db.clone(), data_dir);
+        downloads.recover_interrupted().await?;
+
+        Ok(AppState {
+            db,
+            engine: ParserEngine::new(http.clone()),
+            parsers: ParserRegistry::with_builtin(http.clone()),
+            http,
+            downloads,
+            installer,
+            drift,
+            checks: CheckQueue::new(CheckLimits::default()),
+            jobs: JobManager::new(),
+        })
+    }
+}
+
+/// Создать HTTP клиент приложения
+///
+/// # Возвращает
+/// HTTP клиент с User-Agent браузера и таймаутом 30 секунд или ошибку
+pub fn build_http_client() -> Result<reqwest::Client, reqwest::Error> {
+    reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(Duration::from_secs(30))
+        .build()
+}
@@ -0,0 +1,222 @@
+// Общие помощники тестов: локальный HTTP сервер и состояние приложения
+// во временной директории. Подключается только в тестах (lib.rs).
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+use crate::state::AppState;
+
+/// Создать состояние приложения с базой во временной директории
+///
+/// # Возвращает
+/// Временную директорию (удаляется при удалении) и состояние
+pub async fn test_state() -> (tempfile::TempDir, AppState) {
+    let dir = tempfile::tempdir().unwrap();
+    let state = AppState::new(&dir.path().join("test.db"), dir.path()).await.unwrap();
+    (dir, state)
+}
+
+/// Запрос, полученный тестовым сервером
+#[derive(Debug, Clone)]
+pub struct MockRequest {
+    /// Метод (`GET`, `POST`, ...)
+    pub method: String,
+    /// Путь с query string
+    pub path: String,
+    /// Заголовки (имена в нижнем регистре)
+    pub headers: Vec<(String, String)>,
+    /// Время получения запроса
+    pub received_at: Instant,
+}
+
+impl MockRequest {
+    /// Значение заголовка (имя без учета регистра)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_lowercase();
+        self.headers
+            .iter()
+            .find(|(header, _)| *header == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Ответ тестового сервера
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    /// Задержка перед ответом
+    pub delay: Duration,
+}
+
+impl MockResponse {
+    /// Ответ с заданным статусом и телом
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        MockResponse {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// Ответ `200 OK`
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self::new(200, body)
+    }
+
+    /// Добавить заголовок (`Content-Length` и `Connection` ставятся сервером)
+    pub fn header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.push((name.to_string(), value.into()));
+        self
+    }
+
+    /// Отвечать с задержкой
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+type Handler = dyn Fn(&MockRequest) -> MockResponse + Send + Sync;
+
+/// Локальный HTTP сервер для тестов
+///
+/// Отвечает на каждый запрос ответом обработчика, запоминает полученные
+/// запросы и наибольшее количество одновременно обрабатываемых запросов.
+/// Останавливается при удалении.
+pub struct MockServer {
+    address: String,
+    requests: Arc<Mutex<Vec<MockRequest>>>,
+    max_in_flight: Arc<AtomicUsize>,
+    task: JoinHandle<()>,
+}
+
+impl MockServer {
+    /// Запустить сервер
+    ///
+    /// # Параметры
+    /// * `handler` - ответ на запрос
+    pub async fn start(handler: impl Fn(&MockRequest) -> MockResponse + Send + Sync + 'static) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("http://{}", listener.local_addr().unwrap());
+        let handler: Arc<Handler> = Arc::new(handler);
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let task = tokio::spawn({
+            let (requests, max_in_flight) = (requests.clone(), max_in_flight.clone());
+            async move {
+                loop {
+                    let Ok((socket, _)) = listener.accept().await else {
+                        return;
+                    };
+                    let (handler, requests) = (handler.clone(), requests.clone());
+                    let (in_flight, max_in_flight) = (in_flight.clone(), max_in_flight.clone());
+                    tokio::spawn(async move {
+                        let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                        max_in_flight.fetch_max(current, Ordering::SeqCst);
+                        respond(socket, handler.as_ref(), &requests).await;
+                        in_flight.fetch_sub(1, Ordering::SeqCst);
+                    });
+                }
+            }
+        });
+        MockServer {
+            address,
+            requests,
+            max_in_flight,
+            task,
+        }
+    }
+
+    /// Сервер, отвечающий на все запросы одним ответом
+    pub async fn respond_with(response: MockResponse) -> Self {
+        Self::start(move |_| response.clone()).await
+    }
+
+    /// Адрес сервера (`http://127.0.0.1:port`)
+    pub fn address(&self) -> &str {
+        &self.address
+    }
+
+    /// URL пути на сервере
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.address, path)
+    }
+
+    /// Полученные запросы в порядке получения
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Наибольшее количество одновременно обрабатывавшихся запросов
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Прочитать запрос, записать его и ответить
+async fn respond(mut socket: TcpStream, handler: &Handler, requests: &Mutex<Vec<MockRequest>>) {
+    let mut head = Vec::new();
+    let mut buffer = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        match socket.read(&mut buffer).await {
+            Ok(0) | Err(_) => break,
+            Ok(read) => head.extend_from_slice(&buffer[..read]),
+        }
+    }
+    let request = parse_request(&String::from_utf8_lossy(&head));
+    requests.lock().unwrap().push(request.clone());
+
+    let response = handler(&request);
+    if !response.delay.is_zero() {
+        tokio::time::sleep(response.delay).await;
+    }
+    let mut head = format!("HTTP/1.1 {} {}\r\n", response.status, reason(response.status));
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str(&format!("Content-Length: {}\r\nConnection: close\r\n\r\n", response.body.len()));
+    // Клиент мог закрыть соединение (таймаут проверки) - это не ошибка теста
+    let _ = socket.write_all(head.as_bytes()).await;
+    let _ = socket.write_all(&response.body).await;
+}
+
+fn parse_request(head: &str) -> MockRequest {
+    let mut lines = head.lines();
+    let mut start = lines.next().unwrap_or_default().split_whitespace();
+    let method = start.next().unwrap_or_default().to_string();
+    let path = start.next().unwrap_or("/").to_string();
+    let headers = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_lowercase(), value.trim().to_string()))
+        .collect();
+    MockRequest {
+        method,
+        path,
+        headers,
+        received_at: Instant::now(),
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        206 => "Partial Content",
+        404 => "Not Found",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Status",
+    }
+}
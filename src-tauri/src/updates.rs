@@ -0,0 +1,242 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use chrono::Utc;
+use futures::future::join_all;
+use log::{debug, error, warn};
+use crate::changes;
+use crate::check_runs;
+use crate::drift;
+use crate::jobs;
+use crate::models::{CheckRun, Mod, ModUpdate, Site};
+use crate::state::AppState;
+
+/// Получить список модов сайта
+///
+/// Использует нативный парсер из реестра, если он подходит сайту, иначе
+/// конфигурируемый `ParserEngine`.
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+/// * `site` - сайт из базы данных
+///
+/// # Возвращает
+/// Вектор модов или ошибку
+pub async fn parse_site(state: &AppState, site: &Site) -> Result<Vec<Mod>, String> {
+    match state.parsers.find(site)? {
+        Some(parser) => {
+            debug!("Using native parser '{}' for site {}", parser.name(), site.name);
+            parser.parse(site).await.map_err(|e| e.to_string())
+        }
+        None => state.engine.parse_site(site).await.map_err(|e| e.to_string()),
+    }
+}
+
+/// Выполнить проверку обновлений модов
+///
+/// Загружает страницы сайтов, парсит моды и сравнивает с сохраненными в базе
+/// данных. Сайты проверяются одновременно в пределах лимитов `state.checks`
+/// (см. check_queue.rs), в задаче (jobs.rs) после каждого сайта сообщается
+/// ход проверки. Общая логика команды `check_updates` и CLI; ошибки
+/// отдельных сайтов записываются в лог и не прерывают проверку остальных.
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+/// * `site_id` - ID сайта для проверки (None = все сайты)
+///
+/// # Возвращает
+/// Вектор обновлений модов (ModUpdate) или ошибку
+pub async fn check_updates(state: &AppState, site_id: Option<i64>) -> Result<Vec<ModUpdate>, String> {
+    let db = &state.db;
+    let sites = if let Some(id) = site_id {
+        vec![db.get_site(id).await.map_err(|e| e.to_string())?]
+    } else {
+        db.get_sites().await.map_err(|e| e.to_string())?
+    };
+
+    let done = AtomicUsize::new(0);
+    let results = join_all(sites.iter().map(|site| async {
+        let result = check_site(state, site).await;
+        jobs::report_progress(done.fetch_add(1, Ordering::SeqCst) + 1, sites.len(), &site.name);
+        result
+    }))
+    .await;
+    let mut updates = Vec::new();
+    for (site, result) in sites.iter().zip(results) {
+        match result {
+            Ok(site_updates) => updates.extend(site_updates),
+            Err(e) => error!("Error checking site {}: {}", site.name, e),
+        }
+    }
+
+    Ok(updates)
+}
+
+/// Проверить обновления модов одного сайта
+///
+/// Новые моды добавляются, измененные - обновляются (с записью в историю
+/// версий). Используется `check_updates` и планировщиком (scheduler.rs),
+/// которому нужен результат проверки каждого сайта. Каждая проверка
+/// записывается в `check_runs` (см. check_runs.rs) и сравнивается с
+/// историей сайта для обнаружения сбоя парсера (см. drift.rs).
+///
+/// Проверка ждет места в лимитах `state.checks`, сообщает о своих этапах
+/// и прерывается с ошибкой, если не уложилась в таймаут сайта.
+///
+/// В результат попадают и новые моды (`new_mod`). Флаг `notify` ставится для
+/// изменений модов, за которыми следит пользователь, и для новых модов сайтов
+/// с `notify_new_mods` (кроме первой проверки сайта, когда новые - все моды).
+///
+/// Ошибка базы данных прерывает проверку сайта, чтобы несохраненные
+/// изменения не терялись молча.
+///
+/// # Параметры
+/// * `state` - общее состояние приложения
+/// * `site` - сайт из базы данных
+///
+/// # Возвращает
+/// Вектор обновлений модов сайта или ошибку парсинга/базы данных
+pub async fn check_site(state: &AppState, site: &Site) -> Result<Vec<ModUpdate>, String> {
+    let _permit = state.checks.acquire(site).await;
+    let started_at = Utc::now();
+    let site_timeout = state.checks.site_timeout();
+    let (result, captured) = check_runs::capture_responses(tokio::time::timeout(site_timeout, apply_site_mods(state, site))).await;
+    let result = result.unwrap_or_else(|_| {
+        Err(format!("Проверка сайта не завершилась за {} с", site_timeout.as_secs_f64()))
+    });
+
+    let mut run = CheckRun {
+        id: 0,
+        site_id: site.id,
+        started_at,
+        finished_at: Utc::now(),
+        http_status: captured.http_status,
+        mods_found: 0,
+        new_mods: 0,
+        updated_mods: 0,
+        error: None,
+        null_fraction: None,
+        drift: None,
+        saved_page_id: None,
+    };
+    match &result {
+        Ok((mods_found, null_fraction, updates)) => {
+            run.mods_found = *mods_found as i64;
+            run.null_fraction = *null_fraction;
+            run.new_mods = updates.iter().filter(|update| update.new_mod).count() as i64;
+            run.updated_mods = updates.len() as i64 - run.new_mods;
+        }
+        Err(e) => run.error = Some(e.clone()),
+    }
+    let drift_found = state.drift.inspect(site, &mut run, captured.page.as_ref()).await.unwrap_or_else(|e| {
+        warn!("Failed to compare check of site {} with history: {}", site.name, e);
+        false
+    });
+    match state.db.add_check_run(&run).await {
+        Ok(run) => {
+            if drift_found {
+                state.drift.notify(site, &run);
+            }
+            state.checks.finished(site, &run);
+        }
+        Err(e) => {
+            warn!("Failed to record check run of site {}: {}", site.name, e);
+            state.checks.finished(site, &run);
+        }
+    }
+
+    result.map(|(_, _, updates)| updates)
+}
+
+/// Распарсить сайт и сохранить найденные моды
+///
+/// # Возвращает
+/// Количество найденных модов, долю пустых полей и обновления или ошибку
+async fn apply_site_mods(state: &AppState, site: &Site) -> Result<(usize, Option<f64>, Vec<ModUpdate>), String> {
+    let db = &state.db;
+    let mods = parse_site(state, site).await?;
+    let mods_found = mods.len();
+    let null_fraction = drift::null_fraction(&mods);
+    let first_check = db.get_mods(Some(site.id)).await.map_err(|e| e.to_string())?.is_empty();
+
+    let mut updates = Vec::new();
+    for mod_item in mods {
+        let existing = db.get_mod_by_url(&mod_item.url).await.map_err(|e| e.to_string())?;
+        if let Some(existing) = existing {
+            // Сравниваем извлеченные поля, а не updated_at - парсер всегда ставит текущее время
+            if let Some(mut update) = changes::detect_changes(&existing, &mod_item) {
+                let merged = changes::merge_scraped(&existing, &mod_item);
+                db.update_mod(existing.id, &merged).await.map_err(|e| e.to_string())?;
+                update.notify = existing.followed;
+                updates.push(update);
+            }
+        } else {
+            let added = db.add_mod(&mod_item).await.map_err(|e| e.to_string())?;
+            updates.push(ModUpdate {
+                mod_id: added.id,
+                site_id: added.site_id,
+                old_version: None,
+                new_version: added.version,
+                changes: added.changes,
+                changed_fields: Vec::new(),
+                version_change: None,
+                new_mod: true,
+                notify: site.notify_new_mods && !first_check,
+            });
+        }
+    }
+
+    Ok((mods_found, null_fraction, updates))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use serde_json::json;
+    use crate::test_support::{test_state, MockResponse, MockServer};
+
+    fn rss(items: &[(&str, &str)]) -> String {
+        let items: String = items
+            .iter()
+            .map(|(title, link)| format!("<item><title>{}</title><link>{}</link></item>", title, link))
+            .collect();
+        format!("<rss version=\"2.0\"><channel><title>Mods</title>{}</channel></rss>", items)
+    }
+
+    #[tokio::test]
+    async fn only_followed_and_new_mods_are_notified() {
+        let (_dir, state) = test_state().await;
+        let body = Arc::new(Mutex::new(rss(&[("Trees v1.0", "/trees"), ("Rocks v1.0", "/rocks")])));
+        let server = MockServer::start({
+            let body = body.clone();
+            move |_| MockResponse::ok(body.lock().unwrap().clone()).header("Content-Type", "application/rss+xml")
+        })
+        .await;
+        let address = server.address();
+        let site = state
+            .db
+            .add_site("Feed", address, &json!({"source": {"type": "feed"}}))
+            .await
+            .unwrap();
+
+        // Первая проверка: все моды новые, но уведомлений нет
+        let first = check_updates(&state, Some(site.id)).await.unwrap();
+        assert_eq!(first.len(), 2);
+        assert!(first.iter().all(|update| update.new_mod && !update.notify));
+
+        let trees = state.db.get_mod_by_url(&format!("{}/trees", address)).await.unwrap().unwrap();
+        state.db.set_mod_followed(trees.id, true).await.unwrap();
+        state.db.set_site_notify_new_mods(site.id, true).await.unwrap();
+        *body.lock().unwrap() = rss(&[("Trees v1.1", "/trees"), ("Rocks v1.1", "/rocks"), ("Grass v1.0", "/grass")]);
+
+        let second = check_updates(&state, Some(site.id)).await.unwrap();
+        let notified: Vec<(i64, bool)> = second
+            .iter()
+            .filter(|update| update.notify)
+            .map(|update| (update.mod_id, update.new_mod))
+            .collect();
+        let grass = state.db.get_mod_by_url(&format!("{}/grass", address)).await.unwrap().unwrap();
+        assert_eq!(second.len(), 3);
+        assert_eq!(notified, vec![(trees.id, false), (grass.id, true)]);
+        assert_eq!(state.db.get_followed_mods().await.unwrap().len(), 1);
+    }
+}
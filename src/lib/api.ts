@@ -7,8 +7,82 @@ export interface Site {
   parser_config: any;
   created_at: string;
   updated_at: string;
+  install_dir?: string;
+  notify_new_mods?: boolean;
 }
 
+export const REDACTED_SECRET = '********';
+
+export type Schedule =
+  | { type: 'interval'; seconds: number }
+  | { type: 'cron'; expression: string };
+
+export interface SiteSchedule {
+  site_id: number;
+  schedule: Schedule;
+  enabled: boolean;
+  last_run_at?: string;
+  next_run_at?: string;
+  failure_count: number;
+  last_error?: string;
+}
+
+export interface CheckRun {
+  id: number;
+  site_id: number;
+  started_at: string;
+  finished_at: string;
+  http_status?: number;
+  mods_found: number;
+  new_mods: number;
+  updated_mods: number;
+  error?: string;
+  null_fraction?: number;
+  drift?: string;
+  saved_page_id?: number;
+}
+
+export type HealthStatus = 'ok' | 'degraded' | 'failing' | 'unknown';
+
+export interface SiteHealth {
+  site_id: number;
+  status: HealthStatus;
+  consecutive_failures: number;
+  reason?: string;
+  last_run?: CheckRun;
+  last_success_at?: string;
+}
+
+export type SiteCheckStatus = 'queued' | 'running' | 'completed' | 'failed';
+
+export interface SiteCheckProgress {
+  site_id: number;
+  site_name: string;
+  status: SiteCheckStatus;
+  run?: CheckRun;
+}
+
+export const CHECK_PROGRESS_EVENT = 'site-check-progress';
+
+export type JobKind = 'check_updates' | 'scheduled_check' | 'fetch_page' | 'download';
+
+export type JobStatus = 'running' | 'completed' | 'failed' | 'cancelled' | 'paused';
+
+export interface Job {
+  id: number;
+  kind: JobKind;
+  title: string;
+  status: JobStatus;
+  progress?: number;
+  message?: string;
+  error?: string;
+  download_id?: number;
+  started_at: string;
+  finished_at?: string;
+}
+
+export const JOB_EVENT = 'job-progress';
+
 export interface Mod {
   id: number;
   site_id: number;
@@ -19,18 +93,153 @@ export interface Mod {
   description?: string;
   image_url?: string;
   changes?: string;
+  download_url?: string;
+  files?: ModFile[];
+  followed?: boolean;
   created_at: string;
   updated_at: string;
 }
 
-export interface Notification {
+export interface ModFile {
+  name: string;
+  url: string;
+  size?: number;
+  content_type?: string;
+}
+
+export interface ModVersion {
+  id: number;
+  mod_id: number;
+  version?: string;
+  changes?: string;
+  snapshot: Record<string, any>;
+  scraped_at: string;
+}
+
+export interface FieldDiff {
+  field: string;
+  old_value?: any;
+  new_value?: any;
+}
+
+export interface ModVersionDiff {
+  mod_id: number;
+  from: ModVersion;
+  to: ModVersion;
+  version_change?: 'upgraded' | 'downgraded' | 'reformatted' | 'added' | 'changed';
+  fields: FieldDiff[];
+}
+
+export type DownloadStatus = 'queued' | 'downloading' | 'paused' | 'completed' | 'failed' | 'cancelled';
+
+export interface Download {
   id: number;
   mod_id: number;
+  mod_version_id?: number;
+  version?: string;
+  url: string;
+  file_name: string;
+  path: string;
+  status: DownloadStatus;
+  total_bytes?: number;
+  downloaded_bytes: number;
+  content_type?: string;
+  sha256?: string;
+  error?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export const DOWNLOAD_EVENT = 'download-progress';
+
+export interface Installation {
+  id: number;
+  mod_id: number;
+  download_id?: number;
+  version?: string;
+  install_dir: string;
+  installed_at: string;
+  updated_at: string;
+}
+
+export interface InstalledFile {
+  id: number;
+  installation_id: number;
+  path: string;
+  size: number;
+}
+
+export interface Profile {
+  id: number;
+  name: string;
+  description?: string;
+  install_dir?: string;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface ProfileMod {
+  mod_id: number;
+  pinned_version?: string;
+  enabled: boolean;
+  position: number;
+}
+
+export type ManifestFormat = 'json' | 'toml';
+
+export interface ProfileImport {
+  profile: Profile;
+  missing: string[];
+}
+
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+export type ModSort = 'title' | 'author' | 'created' | 'updated';
+
+export type SortDirection = 'asc' | 'desc';
+
+export interface ModPage {
+  mods: Mod[];
+  total: number;
+  limit: number;
+  offset: number;
+}
+
+export interface SearchFilters {
+  site_id?: number;
+  author?: string;
+  updated_from?: string;
+  updated_to?: string;
+  followed_only?: boolean;
+}
+
+export type SearchSort = 'relevance' | 'updated' | 'created' | 'title';
+
+export interface SearchHit {
+  mod: Mod;
+  snippet?: string;
+}
+
+export interface SearchResults {
+  total: number;
+  hits: SearchHit[];
+}
+
+export type NotificationKind = 'mod_update' | 'new_mod' | 'parser_drift';
+
+export interface Notification {
+  id: number;
+  kind: NotificationKind;
+  mod_id?: number;
   site_id: number;
   title: string;
   message: string;
   read: boolean;
   created_at: string;
+  saved_page_id?: number;
 }
 
 export const api = {
@@ -46,6 +255,10 @@ export const api = {
     return invoke('update_site', { id, name, url, parserConfig });
   },
 
+  async validateParserConfig(url: string, parserConfig: any): Promise<FieldError[]> {
+    return invoke('validate_parser_config', { url, parserConfig });
+  },
+
   async deleteSite(id: number): Promise<void> {
     return invoke('delete_site', { id });
   },
@@ -54,10 +267,166 @@ export const api = {
     return invoke('get_mods', { siteId });
   },
 
+  async getModsPage(
+    siteId?: number,
+    sort?: ModSort,
+    direction?: SortDirection,
+    limit?: number,
+    offset?: number
+  ): Promise<ModPage> {
+    return invoke('get_mods_page', { siteId, sort, direction, limit, offset });
+  },
+
+  async searchMods(
+    query?: string,
+    filters?: SearchFilters,
+    sort?: SearchSort,
+    limit?: number,
+    offset?: number
+  ): Promise<SearchResults> {
+    return invoke('search_mods', { query, filters, sort, limit, offset });
+  },
+
+  async getModHistory(modId: number): Promise<ModVersion[]> {
+    return invoke('get_mod_history', { modId });
+  },
+
+  async getModVersionDiff(fromVersionId: number, toVersionId: number): Promise<ModVersionDiff> {
+    return invoke('get_mod_version_diff', { fromVersionId, toVersionId });
+  },
+
+  async startDownload(modId: number, url?: string): Promise<Download> {
+    return invoke('start_download', { modId, url });
+  },
+
+  async pauseDownload(id: number): Promise<void> {
+    return invoke('pause_download', { id });
+  },
+
+  async resumeDownload(id: number): Promise<Download> {
+    return invoke('resume_download', { id });
+  },
+
+  async cancelDownload(id: number): Promise<void> {
+    return invoke('cancel_download', { id });
+  },
+
+  async getDownloads(modId?: number): Promise<Download[]> {
+    return invoke('get_downloads', { modId });
+  },
+
+  async installMod(downloadId: number, installDir?: string): Promise<Installation> {
+    return invoke('install_mod', { downloadId, installDir });
+  },
+
+  async uninstallMod(installationId: number): Promise<void> {
+    return invoke('uninstall_mod', { installationId });
+  },
+
+  async getInstallations(modId?: number): Promise<Installation[]> {
+    return invoke('get_installations', { modId });
+  },
+
+  async getInstalledFiles(installationId: number): Promise<InstalledFile[]> {
+    return invoke('get_installed_files', { installationId });
+  },
+
+  async setSiteInstallDir(siteId: number, installDir?: string): Promise<void> {
+    return invoke('set_site_install_dir', { siteId, installDir });
+  },
+
+  async followMod(modId: number): Promise<void> {
+    return invoke('follow_mod', { modId });
+  },
+
+  async unfollowMod(modId: number): Promise<void> {
+    return invoke('unfollow_mod', { modId });
+  },
+
+  async getFollowedMods(): Promise<Mod[]> {
+    return invoke('get_followed_mods');
+  },
+
+  async setSiteNotifyNewMods(siteId: number, notify: boolean): Promise<void> {
+    return invoke('set_site_notify_new_mods', { siteId, notify });
+  },
+
+  async getProfiles(): Promise<Profile[]> {
+    return invoke('get_profiles');
+  },
+
+  async createProfile(name: string, description?: string, installDir?: string): Promise<Profile> {
+    return invoke('create_profile', { name, description, installDir });
+  },
+
+  async updateProfile(id: number, name: string, description?: string, installDir?: string): Promise<void> {
+    return invoke('update_profile', { id, name, description, installDir });
+  },
+
+  async deleteProfile(id: number): Promise<void> {
+    return invoke('delete_profile', { id });
+  },
+
+  async cloneProfile(id: number, name: string): Promise<Profile> {
+    return invoke('clone_profile', { id, name });
+  },
+
+  async getProfileMods(id: number): Promise<ProfileMod[]> {
+    return invoke('get_profile_mods', { id });
+  },
+
+  async setProfileMods(id: number, mods: ProfileMod[]): Promise<ProfileMod[]> {
+    return invoke('set_profile_mods', { id, mods });
+  },
+
+  async exportProfile(id: number, format: ManifestFormat): Promise<string> {
+    return invoke('export_profile', { id, format });
+  },
+
+  async importProfile(manifest: string, format: ManifestFormat, name?: string): Promise<ProfileImport> {
+    return invoke('import_profile', { manifest, format, name });
+  },
+
   async checkUpdates(siteId?: number): Promise<any[]> {
     return invoke('check_updates', { siteId });
   },
 
+  async getSiteSchedules(): Promise<SiteSchedule[]> {
+    return invoke('get_site_schedules');
+  },
+
+  async getSiteSchedule(siteId: number): Promise<SiteSchedule> {
+    return invoke('get_site_schedule', { siteId });
+  },
+
+  async setSiteSchedule(siteId: number, schedule: Schedule, enabled: boolean): Promise<SiteSchedule> {
+    return invoke('set_site_schedule', { siteId, schedule, enabled });
+  },
+
+  async runSiteCheckNow(siteId: number): Promise<any[]> {
+    return invoke('run_site_check_now', { siteId });
+  },
+
+  async getCheckRuns(siteId?: number, limit?: number): Promise<CheckRun[]> {
+    return invoke('get_check_runs', { siteId, limit });
+  },
+
+  async getSiteHealth(siteId: number): Promise<SiteHealth> {
+    return invoke('get_site_health', { siteId });
+  },
+
+  async getSitesHealth(): Promise<SiteHealth[]> {
+    return invoke('get_sites_health');
+  },
+
+  async listJobs(): Promise<Job[]> {
+    return invoke('list_jobs');
+  },
+
+  async cancelJob(id: number): Promise<void> {
+    return invoke('cancel_job', { id });
+  },
+
   async buildParser(html: string, selector: string): Promise<any> {
     return invoke('build_parser', { html, selector });
   },
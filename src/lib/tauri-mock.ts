@@ -107,6 +107,18 @@ export async function mockInvoke<T = any>(cmd: string, args?: any): Promise<T> {
       }
       return mockMods as T;
     
+    case 'get_mods_page': {
+      const filtered = args?.siteId ? mockMods.filter(m => m.site_id === args.siteId) : mockMods;
+      const limit = args?.limit ?? 50;
+      const offset = args?.offset ?? 0;
+      return {
+        mods: filtered.slice(offset, offset + limit),
+        total: filtered.length,
+        limit,
+        offset,
+      } as T;
+    }
+    
     case 'add_site':
       const newSite = {
         id: mockSites.length + 1,
@@ -125,6 +137,9 @@ export async function mockInvoke<T = any>(cmd: string, args?: any): Promise<T> {
       }
       throw new Error('Site not found');
     
+    case 'validate_parser_config':
+      return [] as T;
+    
     case 'delete_site':
       const deleteIndex = mockSites.findIndex(s => s.id === args?.id);
       if (deleteIndex >= 0) {
@@ -132,6 +147,9 @@ export async function mockInvoke<T = any>(cmd: string, args?: any): Promise<T> {
       }
       return undefined as T;
     
+    case 'get_mod_history':
+      return [] as T;
+    
     case 'check_updates':
       // Simulate checking for updates
       return [] as T;